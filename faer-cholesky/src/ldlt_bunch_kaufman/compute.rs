@@ -0,0 +1,259 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    mul::triangular::{self, BlockStructure},
+    permutation::PermutationIndicesMut,
+    temp_mat_req, temp_mat_uninit, ColMut, ComplexField, Conj, MatMut, Parallelism,
+};
+use reborrow::*;
+
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct BunchKaufmanParams {}
+
+/// Swaps the rows and columns `a` and `b` of the hermitian matrix whose lower triangular part is
+/// stored in `matrix`, where `a < b`.
+fn swap_rows_and_cols_hermitian_lower<T: ComplexField>(matrix: MatMut<'_, T>, a: usize, b: usize) {
+    let mut matrix = matrix;
+    let n = matrix.nrows();
+
+    let tmp = matrix[(a, a)];
+    matrix[(a, a)] = matrix[(b, b)];
+    matrix[(b, b)] = tmp;
+
+    for j in a + 1..b {
+        let tmp = matrix[(j, a)];
+        matrix[(j, a)] = matrix[(b, j)].conj();
+        matrix[(b, j)] = tmp.conj();
+    }
+    matrix[(b, a)] = matrix[(b, a)].conj();
+
+    for i in b + 1..n {
+        let tmp = matrix[(i, a)];
+        matrix[(i, a)] = matrix[(i, b)];
+        matrix[(i, b)] = tmp;
+    }
+}
+
+/// Computes the size and alignment of required workspace for performing a Bunch-Kaufman
+/// $LDL^*$ decomposition.
+pub fn cholesky_in_place_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+    params: BunchKaufmanParams,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    let _ = params;
+    temp_mat_req::<T>(dim, 2)
+}
+
+/// Computes the Bunch-Kaufman $LDL^*$ decomposition of a hermitian input matrix $A$, with
+/// symmetric pivoting, such that
+/// $$PAP^\top = LDL^*,$$
+/// where $P$ is a permutation matrix, $L$ is a unit lower triangular matrix, and $D$ is a
+/// hermitian block diagonal matrix with $1 \times 1$ and $2 \times 2$ diagonal blocks.
+///
+/// The input matrix is interpreted as hermitian and only the lower triangular part is read.
+///
+/// On exit, the strictly lower triangular part of `matrix` contains $L$, with an implicit unit
+/// diagonal, and the diagonal of `matrix` contains the diagonal of $D$. The subdiagonal of $D$ is
+/// stored in `subdiag`, so that `subdiag[k]` is the element at the index `(k + 1, k)` of $D$ if
+/// the rows `k` and `k + 1` form a $2 \times 2$ block, and zero otherwise. The elements of $L$
+/// that lie at the same positions as the subdiagonal elements of $D$ are zero.
+///
+/// The permutation representing $P$, as well as its inverse, are stored in `perm` and
+/// `perm_inv` respectively.
+///
+/// The strictly upper triangular part of the matrix is clobbered and may be filled with garbage
+/// values.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if `subdiag` doesn't have the same number of rows as the dimension of the matrix.
+/// - Panics if the length of the permutation slices is not equal to the dimension of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn cholesky_in_place<'out, T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    subdiag: ColMut<'_, T>,
+    perm: &'out mut [usize],
+    perm_inv: &'out mut [usize],
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: BunchKaufmanParams,
+) -> PermutationIndicesMut<'out> {
+    let _ = params;
+    fancy_assert!(
+        matrix.ncols() == matrix.nrows(),
+        "only square matrices can be decomposed into cholesky factors",
+    );
+    let n = matrix.nrows();
+    fancy_assert!(subdiag.nrows() == n);
+    fancy_assert!(perm.len() == n);
+    fancy_assert!(perm_inv.len() == n);
+
+    let mut matrix = matrix;
+    let mut subdiag = subdiag;
+
+    for (i, p) in perm.iter_mut().enumerate() {
+        *p = i;
+    }
+
+    temp_mat_uninit! {
+        let (mut work, _) = unsafe { temp_mat_uninit::<T>(n, 2, stack) };
+    }
+
+    // (1 + sqrt(17)) / 8, which minimizes the bound on the element growth
+    let one = T::Real::one();
+    let two = one + one;
+    let eight = two * two * two;
+    let sixteen = eight + eight;
+    let alpha = (one + (sixteen + one).sqrt()) / eight;
+
+    let mut k = 0;
+    while k < n {
+        let abs_akk = matrix[(k, k)].real().abs();
+
+        let mut col_max = T::Real::zero();
+        let mut imax = k;
+        for i in k + 1..n {
+            let abs = matrix[(i, k)].abs();
+            if abs > col_max {
+                col_max = abs;
+                imax = i;
+            }
+        }
+
+        let (pivot, block_size) = if abs_akk >= alpha * col_max {
+            (k, 1)
+        } else {
+            // largest off-diagonal element in the row/column imax
+            let mut row_max = T::Real::zero();
+            for j in k..imax {
+                let abs = matrix[(imax, j)].abs();
+                if abs > row_max {
+                    row_max = abs;
+                }
+            }
+            for i in imax + 1..n {
+                let abs = matrix[(i, imax)].abs();
+                if abs > row_max {
+                    row_max = abs;
+                }
+            }
+
+            if abs_akk * row_max >= alpha * col_max * col_max {
+                (k, 1)
+            } else if matrix[(imax, imax)].real().abs() >= alpha * row_max {
+                (imax, 1)
+            } else {
+                (imax, 2)
+            }
+        };
+
+        let kk = k + block_size - 1;
+        if pivot != kk {
+            swap_rows_and_cols_hermitian_lower(
+                matrix.rb_mut().submatrix(k, k, n - k, n - k),
+                kk - k,
+                pivot - k,
+            );
+            // swap the rows of the already computed part of L, as well as the first column of
+            // the 2×2 block
+            for j in 0..kk {
+                let tmp = matrix[(kk, j)];
+                matrix[(kk, j)] = matrix[(pivot, j)];
+                matrix[(pivot, j)] = tmp;
+            }
+            perm.swap(kk, pivot);
+        }
+
+        let rem = n - k - block_size;
+        if block_size == 1 {
+            let d = matrix[(k, k)].real();
+            matrix[(k, k)] = T::from_real(d);
+            subdiag[k] = T::zero();
+
+            if d != T::Real::zero() {
+                let d_inv = d.inv();
+                let (_, _, bottom_left, a22) = matrix.rb_mut().split_at(k + 1, k + 1);
+                let mut l = bottom_left.col(k);
+                let mut w = work.rb_mut().submatrix(0, 0, rem, 1);
+
+                // w = A21, L21 = A21 / d
+                for i in 0..rem {
+                    w[(i, 0)] = l[i];
+                    l[i] = l[i].scale(d_inv);
+                }
+
+                // A22 -= L21 A21^*
+                triangular::matmul(
+                    a22,
+                    BlockStructure::TriangularLower,
+                    Conj::No,
+                    l.rb().as_2d(),
+                    BlockStructure::Rectangular,
+                    Conj::No,
+                    w.rb().transpose(),
+                    BlockStructure::Rectangular,
+                    Conj::Yes,
+                    Some(T::one()),
+                    -T::one(),
+                    parallelism,
+                );
+            }
+        } else {
+            let d11 = matrix[(k, k)].real();
+            let d21 = matrix[(k + 1, k)];
+            let d22 = matrix[(k + 1, k + 1)].real();
+            matrix[(k, k)] = T::from_real(d11);
+            matrix[(k + 1, k + 1)] = T::from_real(d22);
+            matrix[(k + 1, k)] = T::zero();
+            subdiag[k] = d21;
+            subdiag[k + 1] = T::zero();
+
+            // D^-1 = [d22, -conj(d21); -d21, d11] / (d11 d22 - |d21|^2)
+            let det = d11 * d22 - (d21 * d21.conj()).real();
+            let det_inv = det.inv();
+
+            let (_, _, bottom_left, a22) = matrix.rb_mut().split_at(k + 2, k + 2);
+            let mut l = bottom_left.submatrix(0, k, rem, 2);
+            let mut w = work.rb_mut().submatrix(0, 0, rem, 2);
+
+            // W = A21, L21 = A21 D^-1
+            for i in 0..rem {
+                let w0 = l[(i, 0)];
+                let w1 = l[(i, 1)];
+                w[(i, 0)] = w0;
+                w[(i, 1)] = w1;
+                l[(i, 0)] = (w0.scale(d22) - w1 * d21).scale(det_inv);
+                l[(i, 1)] = (w1.scale(d11) - w0 * d21.conj()).scale(det_inv);
+            }
+
+            // A22 -= L21 A21^*
+            triangular::matmul(
+                a22,
+                BlockStructure::TriangularLower,
+                Conj::No,
+                l.rb(),
+                BlockStructure::Rectangular,
+                Conj::No,
+                w.rb().transpose(),
+                BlockStructure::Rectangular,
+                Conj::Yes,
+                Some(T::one()),
+                -T::one(),
+                parallelism,
+            );
+        }
+
+        k += block_size;
+    }
+
+    for (i, &p) in perm.iter().enumerate() {
+        perm_inv[p] = i;
+    }
+
+    unsafe { PermutationIndicesMut::new_unchecked(perm, perm_inv) }
+}
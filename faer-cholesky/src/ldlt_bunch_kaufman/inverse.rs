@@ -0,0 +1,100 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    inverse::invert_unit_lower_triangular_to,
+    mul::triangular::{self, BlockStructure},
+    permutation::{permute_cols, permute_rows, PermutationIndicesRef},
+    temp_mat_req, temp_mat_uninit, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+use super::solve::solve_block_diagonal_in_place;
+
+/// Computes the size and alignment of required workspace for computing the inverse of a matrix,
+/// given its Bunch-Kaufman $LDL^*$ decomposition.
+pub fn invert_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    StackReq::try_all_of([
+        temp_mat_req::<T>(dim, dim)?,
+        temp_mat_req::<T>(dim, dim)?,
+        temp_mat_req::<T>(dim, dim)?,
+    ])
+}
+
+/// Computes the inverse of a matrix, given its Bunch-Kaufman $LDL^*$ decomposition, and stores
+/// the result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `cholesky_factors` is not a square matrix.
+/// - Panics if `subdiag` or `perm` doesn't have the same dimension as `cholesky_factors`.
+/// - Panics if the destination shape doesn't match the shape of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn invert_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    cholesky_factors: MatRef<'_, T>,
+    subdiag: ColRef<'_, T>,
+    perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    // A^-1 = P^T L^-* D^-1 L^-1 P
+
+    let n = cholesky_factors.nrows();
+    fancy_assert!(cholesky_factors.nrows() == cholesky_factors.ncols());
+    fancy_assert!(subdiag.nrows() == n);
+    fancy_assert!(perm.len() == n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, n));
+
+    temp_mat_uninit! {
+        let (mut l_inv, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+        let (mut d_inv_l_inv, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+        let (mut inv, _) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+    }
+
+    // L^-1
+    invert_unit_lower_triangular_to(l_inv.rb_mut(), cholesky_factors, Conj::No, parallelism);
+    for j in 0..n {
+        for i in 0..j {
+            l_inv[(i, j)] = T::zero();
+        }
+        l_inv[(j, j)] = T::one();
+    }
+
+    // D^-1 L^-1
+    d_inv_l_inv
+        .rb_mut()
+        .cwise()
+        .zip(l_inv.rb())
+        .for_each(|dst, src| *dst = *src);
+    solve_block_diagonal_in_place(
+        cholesky_factors.diagonal(),
+        subdiag,
+        Conj::No,
+        d_inv_l_inv.rb_mut(),
+    );
+
+    // L^-* D^-1 L^-1
+    triangular::matmul(
+        inv.rb_mut(),
+        BlockStructure::Rectangular,
+        Conj::No,
+        l_inv.rb().transpose(),
+        BlockStructure::UnitTriangularUpper,
+        Conj::Yes,
+        d_inv_l_inv.rb(),
+        BlockStructure::Rectangular,
+        Conj::No,
+        None,
+        T::one(),
+        parallelism,
+    );
+
+    // P^T L^-* D^-1 L^-1 P
+    permute_rows(l_inv.rb_mut(), inv.rb(), perm.inverse());
+    permute_cols(dst, l_inv.rb(), perm.inverse());
+}
@@ -0,0 +1,227 @@
+//! The Bunch-Kaufman $LDL^*$ decomposition of a hermitian matrix $A$ is such that:
+//! $$PAP^\top = LDL^*,$$
+//! where $P$ is a permutation matrix, $D$ is a hermitian block diagonal matrix with $1 \times 1$
+//! and $2 \times 2$ diagonal blocks, and $L$ is a unit lower triangular matrix.
+//!
+//! Unlike the Cholesky decomposition with diagonal $D$, the Bunch-Kaufman decomposition is
+//! numerically stable for hermitian indefinite matrices, such as the ones that arise from KKT
+//! systems.
+
+pub mod compute;
+pub mod inverse;
+pub mod reconstruct;
+pub mod solve;
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+    use dyn_stack::{DynStack, GlobalMemBuffer};
+    use rand::random;
+
+    use super::{compute::*, inverse::*, reconstruct::*, solve::*};
+    use faer_core::{
+        c64, mul, permutation::PermutationIndicesRef, ComplexField, Conj, Mat, Parallelism,
+    };
+
+    type T = c64;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn random_hermitian(n: usize) -> Mat<T> {
+        let mut a = Mat::with_dims(
+            |_, _| T::new(random::<f64>() - 0.5, random::<f64>() - 0.5),
+            n,
+            n,
+        );
+        for j in 0..n {
+            a[(j, j)] = T::new(a[(j, j)].re, 0.0);
+            for i in 0..j {
+                a[(i, j)] = a[(j, i)].conj();
+            }
+        }
+        a
+    }
+
+    /// KKT matrix of the form [H A^*; A 0], where H is hermitian positive semidefinite.
+    fn random_kkt(n: usize, m: usize) -> Mat<T> {
+        let b = Mat::with_dims(|_, _| T::new(random(), random()), n, n);
+        let c = Mat::with_dims(|_, _| T::new(random(), random()), m, n);
+        let mut h = Mat::zeros(n, n);
+        mul::matmul(
+            h.as_mut(),
+            Conj::No,
+            b.as_ref().transpose(),
+            Conj::Yes,
+            b.as_ref(),
+            Conj::No,
+            None,
+            T::one(),
+            Parallelism::None,
+        );
+
+        Mat::with_dims(
+            |i, j| {
+                if i < n && j < n {
+                    h[(i, j)]
+                } else if i >= n && j < n {
+                    c[(i - n, j)]
+                } else if i < n && j >= n {
+                    c[(j - n, i)].conj()
+                } else {
+                    T::zero()
+                }
+            },
+            n + m,
+            n + m,
+        )
+    }
+
+    fn factorize(a: &Mat<T>) -> (Mat<T>, Mat<T>, Vec<usize>, Vec<usize>) {
+        let n = a.nrows();
+        let mut factors = a.clone();
+        let mut subdiag = Mat::zeros(n, 1);
+        let mut perm = vec![0; n];
+        let mut perm_inv = vec![0; n];
+        cholesky_in_place(
+            factors.as_mut(),
+            subdiag.as_mut().col(0),
+            &mut perm,
+            &mut perm_inv,
+            Parallelism::Rayon(8),
+            make_stack!(
+                cholesky_in_place_req::<T>(n, Parallelism::Rayon(8), Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+        (factors, subdiag, perm, perm_inv)
+    }
+
+    fn test_roundtrip_impl(a: &Mat<T>) {
+        let n = a.nrows();
+        let (factors, subdiag, perm, perm_inv) = factorize(a);
+        let perm = unsafe { PermutationIndicesRef::new_unchecked(&perm, &perm_inv) };
+
+        let mut a_reconstructed = Mat::zeros(n, n);
+        reconstruct_to(
+            a_reconstructed.as_mut(),
+            factors.as_ref(),
+            subdiag.as_ref().col(0),
+            perm,
+            Parallelism::Rayon(8),
+            make_stack!(reconstruct_req::<T>(n, Parallelism::Rayon(8)).unwrap()),
+        );
+
+        let mut inv = Mat::zeros(n, n);
+        invert_to(
+            inv.as_mut(),
+            factors.as_ref(),
+            subdiag.as_ref().col(0),
+            perm,
+            Parallelism::Rayon(8),
+            make_stack!(invert_req::<T>(n, Parallelism::Rayon(8)).unwrap()),
+        );
+
+        let mut prod = Mat::zeros(n, n);
+        mul::matmul(
+            prod.as_mut(),
+            Conj::No,
+            a.as_ref(),
+            Conj::No,
+            inv.as_ref(),
+            Conj::No,
+            None,
+            T::one(),
+            Parallelism::Rayon(8),
+        );
+
+        for j in 0..n {
+            for i in 0..n {
+                assert_approx_eq!(a_reconstructed[(i, j)], a[(i, j)]);
+                let target = if i == j { T::one() } else { T::zero() };
+                assert_approx_eq!(prod[(i, j)], target, 1e-4);
+            }
+        }
+    }
+
+    fn test_solve_impl(a: &Mat<T>) {
+        let n = a.nrows();
+        let k = 5;
+        let (factors, subdiag, perm, perm_inv) = factorize(a);
+        let perm = unsafe { PermutationIndicesRef::new_unchecked(&perm, &perm_inv) };
+
+        for conj_lhs in [Conj::No, Conj::Yes] {
+            let rhs = Mat::with_dims(|_, _| T::new(random(), random()), n, k);
+            let mut sol = Mat::zeros(n, k);
+            solve_to(
+                sol.as_mut(),
+                factors.as_ref(),
+                subdiag.as_ref().col(0),
+                conj_lhs,
+                perm,
+                rhs.as_ref(),
+                Conj::No,
+                Parallelism::Rayon(8),
+                make_stack!(solve_req::<T>(n, k, Parallelism::Rayon(8)).unwrap()),
+            );
+
+            let mut result = Mat::zeros(n, k);
+            mul::matmul(
+                result.as_mut(),
+                Conj::No,
+                a.as_ref(),
+                conj_lhs,
+                sol.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::Rayon(8),
+            );
+
+            for j in 0..k {
+                for i in 0..n {
+                    assert_approx_eq!(result[(i, j)], rhs[(i, j)], 1e-4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for n in (0..32).chain((2..8).map(|i| i * 16)) {
+            test_roundtrip_impl(&random_hermitian(n));
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_kkt() {
+        for (n, m) in [(1, 1), (2, 1), (3, 2), (10, 4), (32, 16), (64, 20)] {
+            test_roundtrip_impl(&random_kkt(n, m));
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        for n in 0..20 {
+            test_solve_impl(&random_hermitian(n));
+        }
+        for (n, m) in [(1, 1), (3, 2), (10, 4), (32, 16)] {
+            test_solve_impl(&random_kkt(n, m));
+        }
+    }
+
+    #[test]
+    fn test_zero_diagonal() {
+        // LDL^* with diagonal D breaks down on this matrix
+        let n = 6;
+        let mut a = random_hermitian(n);
+        for i in 0..n {
+            a[(i, i)] = T::zero();
+        }
+        test_roundtrip_impl(&a);
+        test_solve_impl(&a);
+    }
+}
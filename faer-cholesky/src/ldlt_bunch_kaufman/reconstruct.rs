@@ -0,0 +1,101 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    mul::triangular::{self, BlockStructure},
+    permutation::{permute_cols, permute_rows, PermutationIndicesRef},
+    temp_mat_req, temp_mat_uninit, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+/// Computes the size and alignment of required workspace for reconstructing a matrix in place,
+/// given its Bunch-Kaufman $LDL^*$ decomposition.
+pub fn reconstruct_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    StackReq::try_all_of([temp_mat_req::<T>(dim, dim)?, temp_mat_req::<T>(dim, dim)?])
+}
+
+/// Computes the reconstructed matrix, given its Bunch-Kaufman $LDL^*$ decomposition, and stores
+/// the result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `cholesky_factors` is not a square matrix.
+/// - Panics if `subdiag` or `perm` doesn't have the same dimension as `cholesky_factors`.
+/// - Panics if the destination shape doesn't match the shape of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn reconstruct_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    cholesky_factors: MatRef<'_, T>,
+    subdiag: ColRef<'_, T>,
+    perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = cholesky_factors.nrows();
+    fancy_assert!(cholesky_factors.nrows() == cholesky_factors.ncols());
+    fancy_assert!(subdiag.nrows() == n);
+    fancy_assert!(perm.len() == n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, n));
+
+    temp_mat_uninit! {
+        let (mut ld, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+        let (mut ldl, _) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+    }
+
+    let l = |i: usize, j: usize| {
+        if i == j {
+            T::one()
+        } else if i > j {
+            cholesky_factors[(i, j)]
+        } else {
+            T::zero()
+        }
+    };
+
+    // L D
+    let mut k = 0;
+    while k < n {
+        if subdiag[k].abs() == T::Real::zero() {
+            let d = cholesky_factors[(k, k)];
+            for i in 0..n {
+                ld[(i, k)] = l(i, k) * d;
+            }
+            k += 1;
+        } else {
+            let d11 = cholesky_factors[(k, k)];
+            let d21 = subdiag[k];
+            let d22 = cholesky_factors[(k + 1, k + 1)];
+            for i in 0..n {
+                let l0 = l(i, k);
+                let l1 = l(i, k + 1);
+                ld[(i, k)] = l0 * d11 + l1 * d21;
+                ld[(i, k + 1)] = l0 * d21.conj() + l1 * d22;
+            }
+            k += 2;
+        }
+    }
+
+    // L D L^*
+    triangular::matmul(
+        ldl.rb_mut(),
+        BlockStructure::Rectangular,
+        Conj::No,
+        ld.rb(),
+        BlockStructure::Rectangular,
+        Conj::No,
+        cholesky_factors.transpose(),
+        BlockStructure::UnitTriangularUpper,
+        Conj::Yes,
+        None,
+        T::one(),
+        parallelism,
+    );
+
+    // P^T L D L^* P
+    permute_rows(ld.rb_mut(), ldl.rb(), perm.inverse());
+    permute_cols(dst, ld.rb(), perm.inverse());
+}
@@ -0,0 +1,205 @@
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    permutation::{permute_rows, PermutationIndicesRef},
+    solve, temp_mat_req, temp_mat_uninit, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+use assert2::assert as fancy_assert;
+
+/// Solves the system $\text{Op}(D) X = B$ in place, where $D$ is the block diagonal matrix whose
+/// diagonal and subdiagonal are given by `diag` and `subdiag`.
+pub(crate) fn solve_block_diagonal_in_place<T: ComplexField>(
+    diag: ColRef<'_, T>,
+    subdiag: ColRef<'_, T>,
+    conj: Conj,
+    rhs: MatMut<'_, T>,
+) {
+    let n = diag.nrows();
+    let mut rhs = rhs;
+
+    let mut k = 0;
+    while k < n {
+        if subdiag[k].abs() == T::Real::zero() {
+            let d_inv = diag[k].real().inv();
+            for j in 0..rhs.ncols() {
+                rhs[(k, j)] = rhs[(k, j)].scale(d_inv);
+            }
+            k += 1;
+        } else {
+            let d11 = diag[k].real();
+            let d22 = diag[k + 1].real();
+            let d21 = match conj {
+                Conj::No => subdiag[k],
+                Conj::Yes => subdiag[k].conj(),
+            };
+            let det_inv = (d11 * d22 - (d21 * d21.conj()).real()).inv();
+
+            for j in 0..rhs.ncols() {
+                let x0 = rhs[(k, j)];
+                let x1 = rhs[(k + 1, j)];
+                rhs[(k, j)] = (x0.scale(d22) - d21.conj() * x1).scale(det_inv);
+                rhs[(k + 1, j)] = (x1.scale(d11) - d21 * x0).scale(det_inv);
+            }
+            k += 2;
+        }
+    }
+}
+
+fn solve_impl<T: ComplexField>(
+    cholesky_factors: MatRef<'_, T>,
+    subdiag: ColRef<'_, T>,
+    conj_lhs: Conj,
+    perm: PermutationIndicesRef<'_>,
+    dst: MatMut<'_, T>,
+    rhs: Option<MatRef<'_, T>>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    // P A P^T = L D L^*
+    // X = P^T L^-* D^-1 L^-1 P B
+
+    let n = cholesky_factors.nrows();
+    let k = dst.ncols();
+
+    fancy_assert!(cholesky_factors.nrows() == cholesky_factors.ncols());
+    fancy_assert!(subdiag.nrows() == n);
+    fancy_assert!(perm.len() == n);
+    fancy_assert!(dst.nrows() == n);
+
+    temp_mat_uninit! {
+        let (mut temp, _) = unsafe { temp_mat_uninit::<T>(n, k, stack) };
+    }
+
+    // temp <- P B
+    let src = match rhs {
+        Some(rhs) => rhs,
+        None => dst.rb(),
+    };
+    permute_rows(temp.rb_mut(), src, perm);
+
+    // temp <- L^-1 P B
+    solve::solve_unit_lower_triangular_in_place(
+        cholesky_factors,
+        conj_lhs,
+        temp.rb_mut(),
+        conj_rhs,
+        parallelism,
+    );
+
+    // temp <- D^-1 L^-1 P B
+    solve_block_diagonal_in_place(
+        cholesky_factors.diagonal(),
+        subdiag,
+        conj_lhs,
+        temp.rb_mut(),
+    );
+
+    // temp <- L^-* D^-1 L^-1 P B
+    solve::solve_unit_upper_triangular_in_place(
+        cholesky_factors.transpose(),
+        match conj_lhs {
+            Conj::No => Conj::Yes,
+            Conj::Yes => Conj::No,
+        },
+        temp.rb_mut(),
+        Conj::No,
+        parallelism,
+    );
+
+    // dst <- P^T L^-* D^-1 L^-1 P B
+    permute_rows(dst, temp.rb(), perm.inverse());
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by a
+/// matrix, given its Bunch-Kaufman $LDL^*$ decomposition.
+pub fn solve_req<T: 'static>(
+    dim: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    temp_mat_req::<T>(dim, rhs_ncols)
+}
+
+/// Given the Bunch-Kaufman $LDL^*$ factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this
+/// function computes the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `cholesky_factors` is not a square matrix.
+/// - Panics if `subdiag` or `perm` doesn't have the same dimension as `cholesky_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `cholesky_factors`.
+/// - Panics if `rhs` and `dst` don't have the same shape.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    cholesky_factors: MatRef<'_, T>,
+    subdiag: ColRef<'_, T>,
+    conj_lhs: Conj,
+    perm: PermutationIndicesRef<'_>,
+    rhs: MatRef<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!((rhs.nrows(), rhs.ncols()) == (dst.nrows(), dst.ncols()));
+    solve_impl(
+        cholesky_factors,
+        subdiag,
+        conj_lhs,
+        perm,
+        dst,
+        Some(rhs),
+        conj_rhs,
+        parallelism,
+        stack,
+    )
+}
+
+/// Given the Bunch-Kaufman $LDL^*$ factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this
+/// function computes the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in `rhs`.
+///
+/// # Panics
+///
+/// - Panics if `cholesky_factors` is not a square matrix.
+/// - Panics if `subdiag` or `perm` doesn't have the same dimension as `cholesky_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `cholesky_factors`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_in_place<T: ComplexField>(
+    cholesky_factors: MatRef<'_, T>,
+    subdiag: ColRef<'_, T>,
+    conj_lhs: Conj,
+    perm: PermutationIndicesRef<'_>,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    solve_impl(
+        cholesky_factors,
+        subdiag,
+        conj_lhs,
+        perm,
+        rhs,
+        None,
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+}
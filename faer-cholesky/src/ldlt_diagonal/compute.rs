@@ -1,7 +1,10 @@
 use assert2::{assert as fancy_assert, debug_assert as fancy_debug_assert};
 use dyn_stack::{DynStack, SizeOverflow, StackReq};
 use faer_core::{
-    izip, mul::triangular::BlockStructure, solve, ComplexField, Conj, MatMut, Parallelism,
+    error::{check_dimensions, check_workspace, FaerError},
+    izip,
+    mul::triangular::BlockStructure,
+    solve, ComplexField, Conj, MatMut, Parallelism, RealField,
 };
 use reborrow::*;
 
@@ -245,3 +248,38 @@ pub fn raw_cholesky_in_place<T: ComplexField>(
     );
     cholesky_in_place_impl(matrix, parallelism, stack)
 }
+
+/// Computes the Cholesky factors $L$ and $D$ of the input matrix such that $L$ is strictly lower
+/// triangular, $D$ is real-valued diagonal, and
+/// $$LDL^* = A.$$
+///
+/// This is the fallible variant of [`raw_cholesky_in_place`]. Instead of panicking, it returns an
+/// error if the input matrix is not square, or if the provided memory in `stack` is insufficient.
+///
+/// It also returns [`FaerError::Singular`] if a diagonal element of $D$ is zero or not finite, in
+/// which case the matrix holds the factors computed so far, which aren't suitable for solving
+/// linear systems. Since a zero pivot makes the following ones non-finite, the returned index is
+/// the one of the pivot at which the factorization breaks down.
+pub fn try_raw_cholesky_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: LdltDiagParams,
+) -> Result<(), FaerError> {
+    let n = matrix.nrows();
+    check_dimensions("matrix", (n, matrix.ncols()), (n, n))?;
+    check_workspace(
+        &stack,
+        raw_cholesky_in_place_req::<T>(n, parallelism, params),
+    )?;
+
+    let mut matrix = matrix;
+    cholesky_in_place_impl(matrix.rb_mut(), parallelism, stack);
+    for k in 0..n {
+        let d = matrix[(k, k)].abs();
+        if d == T::Real::zero() || !d.is_finite() {
+            return Err(FaerError::Singular { index: k });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use assert2::assert as fancy_assert;
+use faer_core::{ComplexField, MatRef, RealField};
+
+/// The inertia of a hermitian matrix, i.e., the number of its positive, negative and zero
+/// eigenvalues.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Inertia {
+    pub positive: usize,
+    pub negative: usize,
+    pub zero: usize,
+}
+
+/// Computes the inertia of a hermitian matrix, given its Cholesky decomposition with diagonal
+/// $D$.
+///
+/// By Sylvester's law of inertia, the inertia of $A = LDL^*$ is equal to the inertia of $D$. Since
+/// $A$ is hermitian, only the real parts of the diagonal elements of $D$ are taken into account.
+///
+/// # Panics
+///
+/// Panics if `cholesky_factors` is not a square matrix.
+#[track_caller]
+pub fn compute_inertia<T: ComplexField>(cholesky_factors: MatRef<'_, T>) -> Inertia {
+    fancy_assert!(cholesky_factors.nrows() == cholesky_factors.ncols());
+
+    let mut inertia = Inertia {
+        positive: 0,
+        negative: 0,
+        zero: 0,
+    };
+    for &d in cholesky_factors.diagonal() {
+        let d = d.real();
+        if d > T::Real::zero() {
+            inertia.positive += 1;
+        } else if d < T::Real::zero() {
+            inertia.negative += 1;
+        } else {
+            inertia.zero += 1;
+        }
+    }
+    inertia
+}
+
+/// Computes the determinant of a hermitian matrix, given its Cholesky decomposition with
+/// diagonal $D$.
+///
+/// The determinant of a hermitian matrix is real, so only the real parts of the diagonal elements
+/// of $D$ are taken into account.
+///
+/// The result may overflow or underflow for large matrices, in which case
+/// [`log_abs_determinant`] should be used instead.
+///
+/// # Panics
+///
+/// Panics if `cholesky_factors` is not a square matrix.
+#[track_caller]
+pub fn determinant<T: ComplexField>(cholesky_factors: MatRef<'_, T>) -> T::Real {
+    fancy_assert!(cholesky_factors.nrows() == cholesky_factors.ncols());
+
+    let mut det = T::Real::one();
+    for &d in cholesky_factors.diagonal() {
+        det = det * d.real();
+    }
+    det
+}
+
+/// Computes the sign and the logarithm of the absolute value of the determinant of a hermitian
+/// matrix, given its Cholesky decomposition with diagonal $D$, such that
+/// $$\det(A) = \text{sign} \times \exp(\text{log\\_abs\\_det}).$$
+///
+/// # Output
+///
+/// - The sign of the determinant, which is either $1$, $-1$, or $0$ if the matrix is singular,
+/// - the logarithm of the absolute value of the determinant, which is $-\infty$ if the matrix is
+///   singular.
+///
+/// # Panics
+///
+/// Panics if `cholesky_factors` is not a square matrix.
+#[track_caller]
+pub fn log_abs_determinant<T: ComplexField>(cholesky_factors: MatRef<'_, T>) -> (T::Real, T::Real) {
+    fancy_assert!(cholesky_factors.nrows() == cholesky_factors.ncols());
+
+    let mut sign = T::Real::one();
+    let mut log_abs_det = T::Real::zero();
+    for &d in cholesky_factors.diagonal() {
+        let d = d.real();
+        if d < T::Real::zero() {
+            sign = -sign;
+        } else if d == T::Real::zero() {
+            sign = T::Real::zero();
+        }
+        log_abs_det = log_abs_det + d.abs().ln();
+    }
+    (sign, log_abs_det)
+}
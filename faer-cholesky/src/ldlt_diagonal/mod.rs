@@ -6,8 +6,11 @@
 //! with non positive definite matrices. In the general case, it is recommended to first permute the
 //! matrix using [`crate::compute_cholesky_permutation`] and
 //! [`permute_rows_and_cols_symmetric`](faer_core::permutation::permute_rows_and_cols_symmetric_lower).
+//! For indefinite matrices, the Bunch-Kaufman decomposition in [`crate::ldlt_bunch_kaufman`] should
+//! be preferred.
 
 pub mod compute;
+pub mod inertia;
 pub mod solve;
 pub mod update;
 
@@ -21,6 +24,7 @@ mod tests {
     use super::*;
     use compute::*;
     use faer_core::{mul, mul::triangular::BlockStructure, ComplexField, Mat, MatRef, Parallelism};
+    use inertia::*;
     use solve::*;
     use update::*;
 
@@ -98,6 +102,140 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_inertia() {
+        for (positive, negative) in [(0, 0), (1, 0), (0, 1), (3, 2), (5, 11), (20, 20)] {
+            let n = positive + negative;
+
+            // A = L D L^*, with a well conditioned L and a known D
+            let mut factors = Mat::with_dims(
+                |i, j| {
+                    if i > j {
+                        T::new(random::<f64>() - 0.5, random::<f64>() - 0.5).scale(0.1)
+                    } else {
+                        T::zero()
+                    }
+                },
+                n,
+                n,
+            );
+            // the negative diagonal elements are placed at random positions
+            let mut signs = (0..n)
+                .map(|i| if i < negative { -1.0 } else { 1.0 })
+                .collect::<Vec<f64>>();
+            for i in (1..n).rev() {
+                signs.swap(i, random::<usize>() % (i + 1));
+            }
+            for i in 0..n {
+                factors[(i, i)] = T::new(signs[i] * (1.0 + random::<f64>()), 0.0);
+            }
+            let mut a = reconstruct_matrix(factors.as_ref());
+            let target_det = (0..n).map(|i| factors[(i, i)].re).product::<f64>();
+            for j in 0..n {
+                for i in 0..j {
+                    a[(i, j)] = a[(j, i)].conj();
+                }
+            }
+
+            raw_cholesky_in_place(
+                a.as_mut(),
+                Parallelism::Rayon(8),
+                DynStack::new(&mut []),
+                Default::default(),
+            );
+
+            let inertia = compute_inertia(a.as_ref());
+            assert!(inertia.positive + inertia.negative + inertia.zero == n);
+            assert!(inertia.positive == positive);
+            assert!(inertia.negative == negative);
+            assert!(inertia.zero == 0);
+
+            let det = determinant(a.as_ref());
+            assert_approx_eq!(det, target_det, 1e-6 * target_det.abs());
+
+            let (sign, log_abs_det) = log_abs_determinant(a.as_ref());
+            assert!(sign == target_det.signum());
+            assert_approx_eq!(log_abs_det, target_det.abs().ln(), 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_inertia_singular() {
+        // the zero pivot is last, so that the factorization doesn't break down
+        let n = 4;
+        let mut a = Mat::zeros(n, n);
+        a[(0, 0)] = T::new(2.0, 0.0);
+        a[(1, 1)] = T::new(-3.0, 0.0);
+        a[(2, 2)] = T::new(1.0, 0.0);
+        raw_cholesky_in_place(
+            a.as_mut(),
+            Parallelism::Rayon(8),
+            DynStack::new(&mut []),
+            Default::default(),
+        );
+
+        let inertia = compute_inertia(a.as_ref());
+        assert!(
+            inertia
+                == Inertia {
+                    positive: 2,
+                    negative: 1,
+                    zero: 1,
+                }
+        );
+        assert!(determinant(a.as_ref()) == 0.0);
+        let (sign, log_abs_det) = log_abs_determinant(a.as_ref());
+        assert!(sign == 0.0);
+        assert!(log_abs_det == f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_try_raw_cholesky() {
+        use faer_core::error::FaerError;
+
+        let mut a = Mat::<T>::zeros(3, 2);
+        assert!(matches!(
+            try_raw_cholesky_in_place(
+                a.as_mut(),
+                Parallelism::None,
+                DynStack::new(&mut []),
+                Default::default(),
+            ),
+            Err(FaerError::DimensionMismatch {
+                operand: "matrix",
+                ..
+            })
+        ));
+
+        // the second pivot is 1 - 1 * 1 = 0
+        let mut a = Mat::with_dims(|_, _| T::one(), 3, 3);
+        assert!(
+            try_raw_cholesky_in_place(
+                a.as_mut(),
+                Parallelism::None,
+                DynStack::new(&mut []),
+                Default::default(),
+            ) == Err(FaerError::Singular { index: 1 })
+        );
+
+        let n = 20;
+        let mut a = random_positive_definite(n);
+        let a_orig = a.clone();
+        try_raw_cholesky_in_place(
+            a.as_mut(),
+            Parallelism::None,
+            DynStack::new(&mut []),
+            Default::default(),
+        )
+        .unwrap();
+        let reconstructed = reconstruct_matrix(a.as_ref());
+        for j in 0..n {
+            for i in j..n {
+                assert_approx_eq!(reconstructed[(i, j)], a_orig[(i, j)]);
+            }
+        }
+    }
+
     #[test]
     fn test_solve() {
         let n = 511;
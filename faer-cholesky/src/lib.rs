@@ -1,10 +1,14 @@
+#![allow(clippy::too_many_arguments)]
+
 use assert2::assert as fancy_assert;
 use core::cmp::Ordering;
 use faer_core::{permutation::PermutationIndicesMut, MatRef};
 use num_traits::Signed;
 
+pub mod ldlt_bunch_kaufman;
 pub mod ldlt_diagonal;
 pub mod llt;
+pub mod modified;
 
 /// Computes a permutation that reduces the chance of numerical errors during the $LDL^\top$
 /// factorization with diagonal $D$, then stores the result in `perm_indices` and
@@ -1,19 +1,120 @@
 use assert2::{assert as fancy_assert, debug_assert as fancy_debug_assert};
 use dyn_stack::{DynStack, SizeOverflow, StackReq};
 use faer_core::{
-    mul::triangular::BlockStructure, parallelism_degree, solve, ComplexField, Conj, MatMut,
-    Parallelism,
+    error::{check_dimensions, check_workspace, FaerError},
+    mul::triangular::BlockStructure,
+    parallelism_degree, solve, ColMut, ComplexField, Conj, MatMut, Parallelism, RealField,
 };
 use reborrow::*;
 
 use super::CholeskyError;
 
+#[inline]
+pub(crate) fn offset_error<R>(error: CholeskyError<R>, offset: usize) -> CholeskyError<R> {
+    CholeskyError {
+        column: offset + error.column,
+        pivot: error.pivot,
+    }
+}
+
+/// Regularization of the pivots with the Gill-Murray strategy, used by the modified Cholesky
+/// factorization.
+///
+/// Each pivot is replaced by the largest of its absolute value, `min_pivot`, and the squared
+/// largest absolute value of the elements below it divided by `off_diagonal_bound_squared`, and
+/// the amount that was added to it is stored in `perturbation`.
+pub(crate) struct Regularization<'a, R> {
+    pub(crate) min_pivot: R,
+    pub(crate) off_diagonal_bound_squared: R,
+    pub(crate) perturbation: ColMut<'a, R>,
+}
+
+impl<'a, R: RealField> Regularization<'a, R> {
+    #[inline]
+    fn subrange(&mut self, start: usize, len: usize) -> Regularization<'_, R> {
+        Regularization {
+            min_pivot: self.min_pivot,
+            off_diagonal_bound_squared: self.off_diagonal_bound_squared,
+            perturbation: self.perturbation.rb_mut().subrows(start, len),
+        }
+    }
+
+    #[inline]
+    fn regularize(&mut self, index: usize, pivot: R, max_off_diagonal: R) -> R {
+        let mut regularized = pivot.abs();
+        // bounds the elements below the diagonal of the factor by the off-diagonal bound
+        let bound = max_off_diagonal * max_off_diagonal / self.off_diagonal_bound_squared;
+        if bound > regularized {
+            regularized = bound;
+        }
+        if self.min_pivot > regularized {
+            regularized = self.min_pivot;
+        }
+        self.perturbation[index] = regularized - pivot;
+        regularized
+    }
+}
+
+/// Computes the regularized Cholesky factor of the block column `panel`, whose top block lies on
+/// the diagonal of the matrix, and which has already been updated with the previous columns.
+fn cholesky_in_place_regularized_panel<T: ComplexField>(
+    panel: MatMut<'_, T>,
+    regularization: Regularization<'_, T::Real>,
+    parallelism: Parallelism,
+) {
+    let mut panel = panel;
+    let mut regularization = regularization;
+    let m = panel.nrows();
+    let n = panel.ncols();
+
+    for j in 0..n {
+        let (_, _, l_left, a_right) = panel.rb_mut().split_at(j, j);
+        let l_left = l_left.into_const();
+        let mut col = a_right.submatrix(0, 0, m - j, 1);
+
+        // c[j.., j] = a[j.., j] - L[j.., ..j] × L[j, ..j]^*
+        if j > 0 {
+            faer_core::mul::matmul(
+                col.rb_mut(),
+                Conj::No,
+                l_left,
+                Conj::No,
+                l_left.submatrix(0, 0, 1, j).transpose(),
+                Conj::Yes,
+                Some(T::one()),
+                -T::one(),
+                parallelism,
+            );
+        }
+
+        let mut max_off_diagonal = T::Real::zero();
+        for i in 1..m - j {
+            let abs = col[(i, 0)].abs();
+            if abs > max_off_diagonal {
+                max_off_diagonal = abs;
+            }
+        }
+        let pivot = regularization.regularize(j, col[(0, 0)].real(), max_off_diagonal);
+
+        let l_jj = pivot.sqrt();
+        let inv = T::from_real(l_jj.inv());
+        col[(0, 0)] = T::from_real(l_jj);
+        for i in 1..m - j {
+            col[(i, 0)] = col[(i, 0)] * inv;
+        }
+    }
+}
+
+/// On failure, the leading block of the matrix up to the offending column holds its Cholesky
+/// factor.
 fn cholesky_in_place_left_looking_impl<T: ComplexField>(
     matrix: MatMut<'_, T>,
     block_size: usize,
+    regularization: Option<Regularization<'_, T::Real>>,
     parallelism: Parallelism,
-) -> Result<(), CholeskyError> {
+) -> Result<(), CholeskyError<T::Real>> {
     let mut matrix = matrix;
+    let mut regularization = regularization;
     fancy_debug_assert!(
         matrix.ncols() == matrix.nrows(),
         "only square matrices can be decomposed into cholesky factors",
@@ -23,14 +124,17 @@ fn cholesky_in_place_left_looking_impl<T: ComplexField>(
 
     match n {
         0 => return Ok(()),
-        1 => {
+        1 if regularization.is_none() => {
             let elem = &mut matrix[(0, 0)];
             let real = (*elem).into_real_imag().0;
             return if real > T::Real::zero() {
                 *elem = T::from_real(real.sqrt());
                 Ok(())
             } else {
-                Err(CholeskyError)
+                Err(CholeskyError {
+                    column: 0,
+                    pivot: real,
+                })
             };
         }
         _ => (),
@@ -42,7 +146,8 @@ fn cholesky_in_place_left_looking_impl<T: ComplexField>(
 
         let (_, _, bottom_left, bottom_right) = matrix.rb_mut().split_at(idx, idx);
         let (_, l10, _, l20) = bottom_left.into_const().split_at(block_size, 0);
-        let (mut a11, _, mut a21, _) = bottom_right.split_at(block_size, block_size);
+        let (_, _, mut panel, _) = bottom_right.split_at(0, block_size);
+        let (_, mut a11, _, mut a21) = panel.rb_mut().split_at(block_size, 0);
 
         //
         //      L00
@@ -61,8 +166,8 @@ fn cholesky_in_place_left_looking_impl<T: ComplexField>(
         // L10×L00^H  L10×L10^H + L11×L11^H
         // L20×L00^H  L20×L10^H + L21×L11^H  L20×L20^H + L21×L21^H + L22×L22^H
 
-        // A11 -= L10 × L10^H
         if l10.ncols() > 0 {
+            // A11 -= L10 × L10^H
             faer_core::mul::triangular::matmul(
                 a11.rb_mut(),
                 BlockStructure::TriangularLower,
@@ -77,44 +182,59 @@ fn cholesky_in_place_left_looking_impl<T: ComplexField>(
                 -T::one(),
                 parallelism,
             );
-        }
 
-        cholesky_in_place_left_looking_impl(a11.rb_mut(), block_size / 2, parallelism)?;
-
-        if idx + block_size == n {
-            break;
+            // A21 -= L20 × L10^H
+            faer_core::mul::matmul(
+                a21.rb_mut(),
+                Conj::No,
+                l20,
+                Conj::No,
+                l10.transpose(),
+                Conj::Yes,
+                Some(T::one()),
+                -T::one(),
+                parallelism,
+            );
         }
 
-        let ld11 = a11.into_const();
-        let l11 = ld11;
+        match regularization.as_mut() {
+            None => {
+                cholesky_in_place_left_looking_impl(
+                    a11.rb_mut(),
+                    block_size / 2,
+                    None,
+                    parallelism,
+                )
+                .map_err(|e| offset_error(e, idx))?;
 
-        // A21 -= L20 × L10^H
-        faer_core::mul::matmul(
-            a21.rb_mut(),
-            Conj::No,
-            l20,
-            Conj::No,
-            l10.transpose(),
-            Conj::Yes,
-            Some(T::one()),
-            -T::one(),
-            parallelism,
-        );
-
-        // A21 is now L21×L11^H
-        // find L21
-        //
-        // conj(L11) L21^T = A21^T
+                // A21 is now L21×L11^H
+                // find L21
+                //
+                // conj(L11) L21^T = A21^T
 
-        solve::solve_lower_triangular_in_place(
-            l11,
-            Conj::Yes,
-            a21.rb_mut().transpose(),
-            Conj::No,
-            parallelism,
-        );
+                solve::solve_lower_triangular_in_place(
+                    a11.into_const(),
+                    Conj::Yes,
+                    a21.rb_mut().transpose(),
+                    Conj::No,
+                    parallelism,
+                );
+            }
+            Some(regularization) => {
+                // the regularized pivots depend on the elements below them, so the whole block
+                // column is factorized at once
+                cholesky_in_place_regularized_panel(
+                    panel,
+                    regularization.subrange(idx, block_size),
+                    parallelism,
+                );
+            }
+        }
 
         idx += block_size;
+        if idx == n {
+            break;
+        }
     }
     Ok(())
 }
@@ -136,11 +256,14 @@ pub fn cholesky_in_place_req<T: 'static>(
     Ok(StackReq::default())
 }
 
-fn cholesky_in_place_impl<T: ComplexField>(
+/// On failure, the leading block of the matrix up to the offending column holds its Cholesky
+/// factor.
+pub(crate) fn cholesky_in_place_impl<T: ComplexField>(
     matrix: MatMut<'_, T>,
+    regularization: Option<Regularization<'_, T::Real>>,
     parallelism: Parallelism,
     stack: DynStack<'_>,
-) -> Result<(), CholeskyError> {
+) -> Result<(), CholeskyError<T::Real>> {
     // right looking cholesky
 
     fancy_debug_assert!(matrix.nrows() == matrix.ncols());
@@ -148,13 +271,18 @@ fn cholesky_in_place_impl<T: ComplexField>(
     let mut stack = stack;
 
     let n = matrix.nrows();
-    if n < 4 {
-        cholesky_in_place_left_looking_impl(matrix, 1, parallelism)
+    if regularization.is_some() {
+        // the regularized pivots depend on the whole columns below them, which the right looking
+        // recursion only updates after the leading block is factorized
+        let block_size = (n / 2).min(128 * parallelism_degree(parallelism)).max(1);
+        cholesky_in_place_left_looking_impl(matrix, block_size, regularization, parallelism)
+    } else if n < 4 {
+        cholesky_in_place_left_looking_impl(matrix, 1, None, parallelism)
     } else {
         let block_size = (n / 2).min(128 * parallelism_degree(parallelism));
         let (mut l00, _, mut a10, mut a11) = matrix.rb_mut().split_at(block_size, block_size);
 
-        cholesky_in_place_impl(l00.rb_mut(), parallelism, stack.rb_mut())?;
+        cholesky_in_place_impl(l00.rb_mut(), None, parallelism, stack.rb_mut())?;
 
         let l00 = l00.into_const();
 
@@ -181,7 +309,8 @@ fn cholesky_in_place_impl<T: ComplexField>(
             parallelism,
         );
 
-        cholesky_in_place_impl(a11, parallelism, stack)
+        cholesky_in_place_impl(a11, None, parallelism, stack)
+            .map_err(|e| offset_error(e, block_size))
     }
 }
 
@@ -190,7 +319,8 @@ fn cholesky_in_place_impl<T: ComplexField>(
 /// $$LL^* == A.$$
 ///
 /// The result is stored back in the same matrix, or an error is returned if the matrix is not
-/// positive definite.
+/// positive definite. The error holds the index of the column at which the factorization breaks
+/// down, along with the value of the offending pivot.
 ///
 /// The input matrix is interpreted as symmetric and only the lower triangular part is read.
 ///
@@ -208,11 +338,81 @@ pub fn cholesky_in_place<T: ComplexField>(
     parallelism: Parallelism,
     stack: DynStack<'_>,
     params: LltParams,
-) -> Result<(), CholeskyError> {
+) -> Result<(), CholeskyError<T::Real>> {
+    let _ = params;
+    fancy_assert!(
+        matrix.ncols() == matrix.nrows(),
+        "only square matrices can be decomposed into cholesky factors",
+    );
+    cholesky_in_place_impl(matrix, None, parallelism, stack)
+}
+
+/// Result of a partial Cholesky factorization, computed by [`cholesky_in_place_partial`].
+pub struct PartialCholesky<'a, T: ComplexField> {
+    /// Leading block of the input matrix, holding the Cholesky factor of the corresponding block
+    /// of the input matrix. Its dimension is the number of columns that were successfully
+    /// factorized.
+    pub factor: MatMut<'a, T>,
+    /// Error at which the factorization stopped, or `None` if the whole matrix was factorized.
+    pub error: Option<CholeskyError<T::Real>>,
+}
+
+/// Computes the Cholesky factor of the largest leading block of a hermitian input matrix $A$ that
+/// can be factorized, i.e., such that the factorization doesn't break down before reaching its
+/// last column.
+///
+/// If the matrix is positive definite, this is equivalent to [`cholesky_in_place`]. Otherwise, the
+/// factorization stops at the first column whose pivot is not positive, and the Cholesky factor
+/// of the leading positive definite block is returned, similarly to LAPACK's `potrf` with
+/// `info > 0`.
+///
+/// The input matrix is interpreted as symmetric and only the lower triangular part is read.
+///
+/// The part of the matrix outside of the returned factor is clobbered and may be filled with
+/// garbage values.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn cholesky_in_place_partial<'a, T: ComplexField>(
+    matrix: MatMut<'a, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: LltParams,
+) -> PartialCholesky<'a, T> {
     let _ = params;
     fancy_assert!(
         matrix.ncols() == matrix.nrows(),
         "only square matrices can be decomposed into cholesky factors",
     );
-    cholesky_in_place_impl(matrix, parallelism, stack)
+    let mut matrix = matrix;
+    let n = matrix.nrows();
+    let error = cholesky_in_place_impl(matrix.rb_mut(), None, parallelism, stack).err();
+    let dim = error.as_ref().map(|e| e.column).unwrap_or(n);
+    PartialCholesky {
+        factor: matrix.submatrix(0, 0, dim, dim),
+        error,
+    }
+}
+
+/// Computes the Cholesky factor $L$ of a hermitian positive definite input matrix $A$ such that
+/// $L$ is lower triangular, and
+/// $$LL^* == A.$$
+///
+/// This is the fallible variant of [`cholesky_in_place`]. Instead of panicking, it returns an
+/// error if the input matrix is not square, or if the provided memory in `stack` is
+/// insufficient. If the matrix is not positive definite, the returned error holds the index of
+/// the column at which the factorization breaks down.
+pub fn try_cholesky_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: LltParams,
+) -> Result<(), FaerError> {
+    let n = matrix.nrows();
+    check_dimensions("matrix", (n, matrix.ncols()), (n, n))?;
+    check_workspace(&stack, cholesky_in_place_req::<T>(n, parallelism, params))?;
+    cholesky_in_place_impl(matrix, None, parallelism, stack).map_err(FaerError::from)
 }
@@ -0,0 +1,80 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    norm::norm_one_estimate, temp_mat_req, temp_mat_uninit, ColMut, ComplexField, Conj, MatRef,
+    Parallelism,
+};
+use reborrow::*;
+
+use super::solve::solve_in_place;
+
+/// Computes the size and alignment of required workspace for estimating the reciprocal condition
+/// number of a matrix, given its Cholesky decomposition.
+pub fn reciprocal_condition_number_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    StackReq::try_all_of([temp_mat_req::<T>(dim, 1)?, temp_mat_req::<T>(dim, 1)?])
+}
+
+/// Estimates the reciprocal of the condition number of a hermitian positive definite matrix $A$ in
+/// the 1-norm, given its Cholesky decomposition and the 1-norm of $A$, i.e., its maximum absolute
+/// column sum, which must be computed before the factorization.
+///
+/// The reciprocal condition number is defined as
+/// $$\frac{1}{\|A\|_1 \|A^{-1}\|_1},$$
+/// where $\|A^{-1}\|_1$ is estimated with Hager's method, as refined by Higham, without forming
+/// the inverse. The estimate of $\|A^{-1}\|_1$ is a lower bound that is exact in most cases, so
+/// the returned value may slightly overestimate the reciprocal condition number.
+///
+/// A value close to the machine epsilon indicates that the solutions of linear systems involving
+/// $A$ are not trustworthy. If $A$ is exactly singular, zero is returned.
+///
+/// # Panics
+///
+/// - Panics if `cholesky_factor` is not a square matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn reciprocal_condition_number<T: ComplexField>(
+    cholesky_factor: MatRef<'_, T>,
+    matrix_norm_one: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> T::Real {
+    let n = cholesky_factor.nrows();
+    fancy_assert!(cholesky_factor.ncols() == n);
+
+    if n == 0 {
+        return T::Real::one();
+    }
+    if matrix_norm_one == T::Real::zero() {
+        return T::Real::zero();
+    }
+    // the matrix is exactly singular
+    for &l in cholesky_factor.diagonal() {
+        if l.abs() == T::Real::zero() {
+            return T::Real::zero();
+        }
+    }
+
+    temp_mat_uninit! {
+        let (mut x, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut sign, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+    }
+    let mut x = x.rb_mut().col(0);
+    let mut sign = sign.rb_mut().col(0);
+
+    // solves A x = x, which is also the adjoint system since A is hermitian
+    let solve = |x: ColMut<'_, T>, _: DynStack<'_>| {
+        solve_in_place(cholesky_factor, Conj::No, x.as_2d(), Conj::No, parallelism)
+    };
+
+    let inverse_norm = norm_one_estimate(x.rb_mut(), sign.rb_mut(), solve, solve, stack);
+
+    if inverse_norm == T::Real::zero() {
+        T::Real::zero()
+    } else {
+        (matrix_norm_one * inverse_norm).inv()
+    }
+}
@@ -0,0 +1,22 @@
+use assert2::assert as fancy_assert;
+use faer_core::{ComplexField, MatRef, RealField};
+
+/// Computes the logarithm of the determinant of a hermitian positive definite matrix, given its
+/// Cholesky decomposition.
+///
+/// Since $\det(A) = \det(L)\det(L^*) = \prod_i |L_{ii}|^2$, the result is computed as a sum of
+/// logarithms, which avoids the overflow or underflow of the determinant itself.
+///
+/// # Panics
+///
+/// Panics if `cholesky_factor` is not a square matrix.
+#[track_caller]
+pub fn log_determinant<T: ComplexField>(cholesky_factor: MatRef<'_, T>) -> T::Real {
+    fancy_assert!(cholesky_factor.nrows() == cholesky_factor.ncols());
+
+    let mut log_det = T::Real::zero();
+    for &l in cholesky_factor.diagonal() {
+        log_det = log_det + l.real().ln();
+    }
+    log_det + log_det
+}
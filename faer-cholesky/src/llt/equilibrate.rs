@@ -0,0 +1,119 @@
+use assert2::assert as fancy_assert;
+use core::cmp::Ordering;
+use faer_core::{ColMut, ColRef, ComplexField, MatMut, MatRef, RealField};
+
+use super::CholeskyError;
+
+/// Information about the scaling of a matrix, computed by [`compute_equilibration`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EquilibrationInfo<T> {
+    /// Ratio of the smallest to the largest scale factor. If it is at least `0.1`, and `max_abs`
+    /// is neither too close to overflow nor to underflow, scaling is not worth it.
+    pub condition: T,
+    /// Largest diagonal element of the matrix.
+    pub max_abs: T,
+}
+
+/// Returns the largest power of two that is less than or equal to `value`, which must be positive
+/// and finite.
+fn round_to_power_of_two<T: RealField>(value: T) -> T {
+    let one = T::one();
+    let two = one + one;
+    let half = two.inv();
+
+    let mut power = one;
+    while power > value {
+        power = power * half;
+    }
+    while power * two <= value {
+        power = power * two;
+    }
+    power
+}
+
+/// Computes the scale factors $S$ intended to equilibrate the hermitian positive definite matrix
+/// $A$, so that the diagonal elements of $SAS$ are in $(1/4, 1]$, which reduces its condition
+/// number and avoids spurious failures of the Cholesky decomposition of badly scaled matrices.
+///
+/// The scale factors are the reciprocals of the square roots of the diagonal elements, rounded to
+/// powers of two, so that scaling a matrix doesn't introduce rounding errors. They are stored in
+/// `scale`.
+///
+/// Only the diagonal of `matrix` is accessed. An error is returned if one of the diagonal
+/// elements is not positive, in which case the matrix is not positive definite.
+///
+/// # Panics
+///
+/// - Panics if `matrix` is not a square matrix.
+/// - Panics if `scale` doesn't have the same length as the dimension of `matrix`.
+#[track_caller]
+pub fn compute_equilibration<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    scale: ColMut<'_, T::Real>,
+) -> Result<EquilibrationInfo<T::Real>, CholeskyError<T::Real>> {
+    let n = matrix.nrows();
+    fancy_assert!(matrix.ncols() == n);
+    fancy_assert!(scale.nrows() == n);
+
+    let mut scale = scale;
+
+    let zero = T::Real::zero();
+    let one = T::Real::one();
+
+    if n == 0 {
+        return Ok(EquilibrationInfo {
+            condition: one,
+            max_abs: zero,
+        });
+    }
+
+    let mut min = matrix[(0, 0)].real();
+    let mut max = min;
+    for i in 0..n {
+        let d = matrix[(i, i)].real();
+        // also rejects NaN
+        if d.partial_cmp(&zero) != Some(Ordering::Greater) {
+            return Err(CholeskyError {
+                column: i,
+                pivot: d,
+            });
+        }
+        if d < min {
+            min = d;
+        }
+        if d > max {
+            max = d;
+        }
+        scale[i] = round_to_power_of_two(d.sqrt().inv());
+    }
+
+    Ok(EquilibrationInfo {
+        condition: min.sqrt() / max.sqrt(),
+        max_abs: max,
+    })
+}
+
+/// Scales the hermitian matrix $A$ in place, replacing it with $SAS$, where $S$ is the diagonal
+/// matrix whose diagonal elements are stored in `scale`.
+///
+/// Only the lower triangular part of `matrix` is accessed.
+///
+/// # Panics
+///
+/// - Panics if `matrix` is not a square matrix.
+/// - Panics if `scale` doesn't have the same length as the dimension of `matrix`.
+#[track_caller]
+pub fn equilibrate_in_place<T: ComplexField>(matrix: MatMut<'_, T>, scale: ColRef<'_, T::Real>) {
+    let n = matrix.nrows();
+    fancy_assert!(matrix.ncols() == n);
+    fancy_assert!(scale.nrows() == n);
+
+    let mut matrix = matrix;
+    for j in 0..n {
+        let s = scale[j];
+        for i in j..n {
+            let a = matrix[(i, j)];
+            matrix[(i, j)] = a.scale(scale[i] * s);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    mixed_precision::{
+        has_converged, FallbackReason, MixedPrecision, MixedPrecisionInfo, MixedPrecisionParams,
+    },
+    mul::triangular::{self, BlockStructure},
+    temp_mat_req, temp_mat_uninit, ComplexField, Conj, MatMut, MatRef, Parallelism, RealField,
+};
+use reborrow::*;
+
+use assert2::assert as fancy_assert;
+
+use super::{
+    compute::{cholesky_in_place, cholesky_in_place_req},
+    solve, CholeskyError,
+};
+
+/// Returns the maximum absolute row sum of the hermitian matrix whose lower triangular part is
+/// stored in `matrix`.
+fn norm_inf<T: ComplexField>(matrix: MatRef<'_, T>) -> T::Real {
+    let mut max = T::Real::zero();
+    for i in 0..matrix.nrows() {
+        let mut sum = T::Real::zero();
+        for j in 0..matrix.ncols() {
+            sum = sum
+                + if i >= j {
+                    matrix[(i, j)]
+                } else {
+                    matrix[(j, i)]
+                }
+                .abs();
+        }
+        // propagate NaN values
+        if sum > max || !sum.is_finite() {
+            max = sum;
+        }
+    }
+    max
+}
+
+/// Computes `acc - A x`, where `A` is the hermitian matrix whose lower triangular part is stored
+/// in `matrix`, and stores the result in `acc`.
+fn sub_hermitian_mul<T: ComplexField>(
+    acc: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    x: MatRef<'_, T>,
+    parallelism: Parallelism,
+) {
+    let mut acc = acc;
+    triangular::matmul(
+        acc.rb_mut(),
+        BlockStructure::Rectangular,
+        Conj::No,
+        matrix,
+        BlockStructure::TriangularLower,
+        Conj::No,
+        x,
+        BlockStructure::Rectangular,
+        Conj::No,
+        Some(T::one()),
+        -T::one(),
+        parallelism,
+    );
+    triangular::matmul(
+        acc,
+        BlockStructure::Rectangular,
+        Conj::No,
+        matrix.transpose(),
+        BlockStructure::StrictTriangularUpper,
+        Conj::Yes,
+        x,
+        BlockStructure::Rectangular,
+        Conj::No,
+        Some(T::one()),
+        -T::one(),
+        parallelism,
+    );
+}
+
+fn solve_mixed_precision_req<T: MixedPrecision>(
+    dim: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T::Lower>(dim, dim)?,
+        temp_mat_req::<T::Lower>(dim, rhs_ncols)?,
+        temp_mat_req::<T>(dim, rhs_ncols)?,
+        cholesky_in_place_req::<T::Lower>(dim, parallelism, Default::default())?,
+    ])
+}
+
+fn solve_working_precision_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T>(dim, dim)?,
+        cholesky_in_place_req::<T>(dim, parallelism, Default::default())?,
+    ])
+}
+
+/// Attempts to solve the system with a lower precision factorization and iterative refinement.
+fn solve_mixed_precision<T: MixedPrecision>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    rhs: MatRef<'_, T>,
+    max_iterations: usize,
+    tolerance: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> MixedPrecisionInfo {
+    let n = matrix.nrows();
+    let k = rhs.ncols();
+    let mut dst = dst;
+
+    let fallback = |iterations, reason| MixedPrecisionInfo {
+        iterations,
+        fallback_reason: Some(reason),
+    };
+
+    let threshold = tolerance * norm_inf(matrix);
+
+    temp_mat_uninit! {
+        let (mut llt, stack) = unsafe { temp_mat_uninit::<T::Lower>(n, n, stack) };
+        let (mut correction, stack) = unsafe { temp_mat_uninit::<T::Lower>(n, k, stack) };
+        let (mut residual, stack) = unsafe { temp_mat_uninit::<T>(n, k, stack) };
+    }
+
+    // only the lower triangular part is read
+    let mut representable = true;
+    for j in 0..n {
+        for i in j..n {
+            let value = matrix[(i, j)].to_lower();
+            representable &= value.abs().is_finite();
+            llt[(i, j)] = value;
+        }
+    }
+    if !representable {
+        return fallback(0, FallbackReason::NotRepresentable);
+    }
+
+    if cholesky_in_place(llt.rb_mut(), parallelism, stack, Default::default()).is_err() {
+        return fallback(0, FallbackReason::FactorizationBreakdown);
+    }
+    for &l in llt.rb().diagonal() {
+        let abs = l.abs();
+        if !abs.is_finite() || abs == <T::Lower as ComplexField>::Real::zero() {
+            return fallback(0, FallbackReason::FactorizationBreakdown);
+        }
+    }
+
+    // x = A^-1 b, computed in lower precision
+    correction
+        .rb_mut()
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| *dst = src.to_lower());
+    solve::solve_in_place(
+        llt.rb(),
+        Conj::No,
+        correction.rb_mut(),
+        Conj::No,
+        parallelism,
+    );
+    dst.rb_mut()
+        .cwise()
+        .zip(correction.rb())
+        .for_each(|dst, src| *dst = T::from_lower(*src));
+
+    let mut iterations = 0;
+    loop {
+        // r = b - A x, computed in working precision
+        residual
+            .rb_mut()
+            .cwise()
+            .zip(rhs)
+            .for_each(|dst, src| *dst = *src);
+        sub_hermitian_mul(residual.rb_mut(), matrix, dst.rb(), parallelism);
+
+        if has_converged(residual.rb(), dst.rb(), threshold) {
+            return MixedPrecisionInfo {
+                iterations,
+                fallback_reason: None,
+            };
+        }
+        if iterations == max_iterations {
+            return fallback(iterations, FallbackReason::NoConvergence);
+        }
+
+        // x += A^-1 r, where the correction is computed in lower precision
+        correction
+            .rb_mut()
+            .cwise()
+            .zip(residual.rb())
+            .for_each(|dst, src| *dst = src.to_lower());
+        solve::solve_in_place(
+            llt.rb(),
+            Conj::No,
+            correction.rb_mut(),
+            Conj::No,
+            parallelism,
+        );
+        dst.rb_mut()
+            .cwise()
+            .zip(correction.rb())
+            .for_each(|dst, src| *dst = *dst + T::from_lower(*src));
+        iterations += 1;
+    }
+}
+
+fn solve_working_precision<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    rhs: MatRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> Result<(), CholeskyError<T::Real>> {
+    let n = matrix.nrows();
+    let mut dst = dst;
+
+    temp_mat_uninit! {
+        let (mut llt, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+    }
+
+    llt.rb_mut()
+        .cwise()
+        .zip(matrix)
+        .for_each(|dst, src| *dst = *src);
+    cholesky_in_place(llt.rb_mut(), parallelism, stack, Default::default())?;
+
+    dst.rb_mut()
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| *dst = *src);
+    solve::solve_in_place(llt.rb(), Conj::No, dst, Conj::No, parallelism);
+    Ok(())
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system with a
+/// mixed precision Cholesky decomposition.
+pub fn solve_req<T: MixedPrecision>(
+    dim: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        solve_mixed_precision_req::<T>(dim, rhs_ncols, parallelism)?,
+        solve_working_precision_req::<T>(dim, parallelism)?,
+    ])
+}
+
+/// Solves the linear system $AX = B$ in the working precision `T`, where $A$ is a hermitian
+/// positive definite matrix whose Cholesky decomposition is computed in the lower precision
+/// `T::Lower` (e.g. `f32` for `f64`), and the solution is refined iteratively, with the residuals
+/// computed in the working precision.
+///
+/// The input matrix is interpreted as hermitian and only the lower triangular part is read.
+///
+/// The refinement stops once the normwise backward error of each column of the solution is below
+/// the tolerance. If that doesn't happen within the maximum number of iterations, or if the
+/// matrix can't be represented in the lower precision, or if the lower precision factorization
+/// breaks down, the system is solved using a working precision factorization instead, and the
+/// reason is reported in the returned information.
+///
+/// The solution is stored in `dst`, or an error is returned if the matrix is not positive
+/// definite in the working precision.
+///
+/// # Panics
+///
+/// - Panics if `matrix` is not a square matrix.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `matrix`.
+/// - Panics if `rhs` and `dst` don't have the same shape.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve<T: MixedPrecision>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    rhs: MatRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: MixedPrecisionParams<T::Real>,
+) -> Result<MixedPrecisionInfo, CholeskyError<T::Real>> {
+    let n = matrix.nrows();
+    fancy_assert!(matrix.ncols() == n);
+    fancy_assert!(rhs.nrows() == n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (rhs.nrows(), rhs.ncols()));
+
+    let mut dst = dst;
+    let mut stack = stack;
+
+    let (max_iterations, tolerance) = params.normalize(n);
+
+    let info = solve_mixed_precision(
+        dst.rb_mut(),
+        matrix,
+        rhs,
+        max_iterations,
+        tolerance,
+        parallelism,
+        stack.rb_mut(),
+    );
+    if info.fallback_reason.is_some() {
+        solve_working_precision(dst, matrix, rhs, parallelism, stack)?;
+    }
+    Ok(info)
+}
@@ -3,22 +3,59 @@
 //! where $L$ is a lower triangular matrix.
 
 pub mod compute;
+pub mod condition;
+pub mod determinant;
+pub mod equilibrate;
 pub mod inverse;
+pub mod mixed_precision;
 pub mod reconstruct;
 pub mod solve;
 pub mod update;
 
-#[derive(Debug, Clone, Copy)]
-pub struct CholeskyError;
+use faer_core::error::FaerError;
+
+/// Error returned when the Cholesky factorization of a matrix breaks down because the matrix is
+/// not positive definite.
+///
+/// When returned by [`compute::cholesky_in_place`], and only then, the leading `column × column`
+/// block of the matrix holds the Cholesky factor of the corresponding block of the input matrix.
+/// See [`compute::cholesky_in_place_partial`]. The other functions, such as the updates or the
+/// equilibration, only guarantee that `column` is the index of the offending column of the matrix
+/// they operate on, and leave the content of their output unspecified.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CholeskyError<R> {
+    /// Index of the first column whose pivot is not positive.
+    pub column: usize,
+    /// Value of the offending pivot, after the updates from the previous columns.
+    pub pivot: R,
+}
+
+impl<R> From<CholeskyError<R>> for FaerError {
+    #[inline]
+    fn from(error: CholeskyError<R>) -> Self {
+        FaerError::NotPositiveDefinite {
+            index: error.column,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use assert_approx_eq::assert_approx_eq;
     use dyn_stack::{DynStack, GlobalMemBuffer};
     use rand::random;
-
-    use super::{compute::*, inverse::*, reconstruct::*, solve::*, update::*};
-    use faer_core::{c64, mul, ComplexField, Conj, Mat, MatRef, Parallelism};
+    use reborrow::*;
+
+    use super::{
+        compute::*, condition::*, determinant::*, equilibrate::*, inverse::*, mixed_precision,
+        reconstruct::*, solve::*, update::*, CholeskyError,
+    };
+    use faer_core::{
+        c64,
+        error::FaerError,
+        mixed_precision::{FallbackReason, MixedPrecisionInfo, MixedPrecisionParams},
+        mul, ComplexField, Conj, Mat, MatRef, Parallelism,
+    };
 
     type T = c64;
 
@@ -112,6 +149,221 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_log_determinant() {
+        for n in [0, 1, 2, 3, 4, 10, 32, 128] {
+            // A = L L^*, where L has a known positive diagonal
+            let l = Mat::with_dims(
+                |i, j| {
+                    if i == j {
+                        T::new(0.5 + random::<f64>(), 0.0)
+                    } else if i > j {
+                        // small off-diagonal elements keep L well conditioned
+                        T::new(random(), random()).scale(1.0 / n as f64)
+                    } else {
+                        T::zero()
+                    }
+                },
+                n,
+                n,
+            );
+            let target = 2.0 * (0..n).map(|i| l[(i, i)].re.ln()).sum::<f64>();
+
+            let mut a = reconstruct_matrix(l.as_ref());
+            cholesky_in_place(
+                a.as_mut(),
+                Parallelism::Rayon(8),
+                DynStack::new(&mut []),
+                Default::default(),
+            )
+            .unwrap();
+
+            assert_approx_eq!(log_determinant(a.as_ref()), target, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_rcond() {
+        for n in [1, 2, 3, 4, 10, 32, 64] {
+            for scale in [1.0, 1e-6] {
+                let mut a = random_positive_definite(n);
+                // make the matrix ill conditioned by scaling its first row and column
+                for i in 0..n {
+                    a[(i, 0)] = a[(i, 0)].scale(scale);
+                    a[(0, i)] = a[(0, i)].scale(scale);
+                }
+
+                let norm_one = |a: &Mat<T>| {
+                    (0..n)
+                        .map(|j| (0..n).map(|i| a[(i, j)].abs()).sum::<f64>())
+                        .fold(0.0, f64::max)
+                };
+                let a_norm = norm_one(&a);
+
+                cholesky_in_place(
+                    a.as_mut(),
+                    Parallelism::Rayon(8),
+                    DynStack::new(&mut []),
+                    Default::default(),
+                )
+                .unwrap();
+
+                let rcond = reciprocal_condition_number(
+                    a.as_ref(),
+                    a_norm,
+                    Parallelism::Rayon(0),
+                    DynStack::new(&mut GlobalMemBuffer::new(
+                        reciprocal_condition_number_req::<T>(n, Parallelism::Rayon(0)).unwrap(),
+                    )),
+                );
+
+                let mut inv = Mat::zeros(n, n);
+                invert_lower_to(
+                    inv.as_mut(),
+                    a.as_ref(),
+                    Parallelism::Rayon(0),
+                    DynStack::new(&mut GlobalMemBuffer::new(
+                        invert_lower_req::<T>(n, Parallelism::Rayon(0)).unwrap(),
+                    )),
+                );
+                for j in 0..n {
+                    for i in 0..j {
+                        inv[(i, j)] = inv[(j, i)].conj();
+                    }
+                }
+                let exact = 1.0 / (a_norm * norm_one(&inv));
+
+                // the estimate of the norm of the inverse is a lower bound, and is usually within
+                // a small factor of the exact value
+                assert!(rcond >= exact * (1.0 - 1e-8));
+                assert!(rcond <= exact * 10.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_cholesky() {
+        for n in [1, 2, 3, 4, 10, 32, 100, 300] {
+            for k in [0, n / 3, n / 2, n - 1] {
+                // the leading k×k block stays positive definite
+                let mut a = random_positive_definite(n);
+                a[(k, k)] = T::new(-1.0, 0.0);
+                let err = try_cholesky_in_place(
+                    a.as_mut(),
+                    Parallelism::Rayon(8),
+                    DynStack::new(&mut []),
+                    Default::default(),
+                );
+                assert_eq!(err, Err(FaerError::NotPositiveDefinite { index: k }));
+            }
+        }
+
+        let mut a = Mat::<T>::zeros(3, 4);
+        assert_eq!(
+            try_cholesky_in_place(
+                a.as_mut(),
+                Parallelism::None,
+                DynStack::new(&mut []),
+                Default::default(),
+            ),
+            Err(FaerError::DimensionMismatch {
+                operand: "matrix",
+                expected: (3, 3),
+                found: (3, 4),
+            })
+        );
+
+        let mut a = random_positive_definite(4);
+        try_cholesky_in_place(
+            a.as_mut(),
+            Parallelism::None,
+            DynStack::new(&mut []),
+            Default::default(),
+        )
+        .unwrap();
+        let mut rhs = Mat::<T>::zeros(5, 2);
+        assert_eq!(
+            try_solve_in_place(
+                a.as_ref(),
+                Conj::No,
+                rhs.as_mut(),
+                Conj::No,
+                Parallelism::None
+            ),
+            Err(FaerError::DimensionMismatch {
+                operand: "rhs",
+                expected: (4, 2),
+                found: (5, 2),
+            })
+        );
+    }
+
+    #[test]
+    fn test_cholesky_error() {
+        for n in [3, 4, 10, 32, 100] {
+            for k in [0, n / 3, n / 2, n - 1] {
+                let mut a = Mat::with_dims(
+                    |i, j| if i == j { T::new(4.0, 0.0) } else { T::zero() },
+                    n,
+                    n,
+                );
+                a[(k, k)] = T::new(-9.0, 0.0);
+                let err = cholesky_in_place(
+                    a.as_mut(),
+                    Parallelism::Rayon(8),
+                    DynStack::new(&mut []),
+                    Default::default(),
+                );
+                assert_eq!(
+                    err,
+                    Err(CholeskyError {
+                        column: k,
+                        pivot: -9.0
+                    })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_partial() {
+        for n in [1, 2, 3, 4, 10, 32, 100] {
+            for k in [0, n / 3, n / 2, n - 1] {
+                let mut a = random_positive_definite(n);
+                a[(k, k)] = T::new(-1.0, 0.0);
+                let a_orig = a.clone();
+
+                let partial = cholesky_in_place_partial(
+                    a.as_mut(),
+                    Parallelism::Rayon(8),
+                    DynStack::new(&mut []),
+                    Default::default(),
+                );
+                let err = partial.error.unwrap();
+                assert_eq!(err.column, k);
+                assert!(err.pivot < 0.0);
+                assert_eq!(partial.factor.nrows(), k);
+
+                let a_reconstructed = reconstruct_matrix(partial.factor.into_const());
+                for j in 0..k {
+                    for i in j..k {
+                        assert_approx_eq!(a_reconstructed[(i, j)], a_orig[(i, j)]);
+                    }
+                }
+            }
+
+            let mut a = random_positive_definite(n);
+            let partial = cholesky_in_place_partial(
+                a.as_mut(),
+                Parallelism::Rayon(8),
+                DynStack::new(&mut []),
+                Default::default(),
+            );
+            assert!(partial.error.is_none());
+            assert_eq!(partial.factor.nrows(), n);
+        }
+    }
+
     #[test]
     fn test_solve() {
         for n in 0..20 {
@@ -176,6 +428,149 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_solve_expert() {
+        for n in [0, 1, 2, 3, 4, 10, 32, 100] {
+            for conj_lhs in [Conj::No, Conj::Yes] {
+                let mut a = random_positive_definite(n);
+                // shift the spectrum to keep the matrix well conditioned
+                for i in 0..n {
+                    a[(i, i)] += T::from_real(n as f64);
+                }
+                let k = 3;
+                let x_true = Mat::with_dims(|_, _| T::new(random(), random()), n, k);
+                let mut rhs = Mat::zeros(n, k);
+                mul::matmul(
+                    rhs.as_mut(),
+                    Conj::No,
+                    a.as_ref(),
+                    conj_lhs,
+                    x_true.as_ref(),
+                    Conj::No,
+                    None,
+                    T::one(),
+                    Parallelism::None,
+                );
+
+                let mut llt = a.clone();
+                cholesky_in_place(
+                    llt.as_mut(),
+                    Parallelism::Rayon(8),
+                    DynStack::new(&mut []),
+                    Default::default(),
+                )
+                .unwrap();
+
+                let mut sol = Mat::zeros(n, k);
+                let mut berr = Mat::<f64>::zeros(k, 1);
+                let mut ferr = Mat::<f64>::zeros(k, 1);
+                solve_expert_to(
+                    sol.as_mut(),
+                    berr.as_mut().col(0),
+                    ferr.as_mut().col(0),
+                    a.as_ref(),
+                    llt.as_ref(),
+                    conj_lhs,
+                    rhs.as_ref(),
+                    Conj::No,
+                    Parallelism::Rayon(8),
+                    DynStack::new(&mut GlobalMemBuffer::new(
+                        solve_expert_req::<T>(n, Parallelism::Rayon(8)).unwrap(),
+                    )),
+                    Default::default(),
+                );
+
+                for j in 0..k {
+                    let mut error = 0.0f64;
+                    let mut x_norm = 0.0f64;
+                    for i in 0..n {
+                        error = error.max((sol[(i, j)] - x_true[(i, j)]).abs());
+                        x_norm = x_norm.max(sol[(i, j)].abs());
+                    }
+                    assert!(berr[(j, 0)] < 1e-14);
+                    assert!(error <= ferr[(j, 0)] * x_norm);
+                    assert!(ferr[(j, 0)] < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_equilibrate() {
+        for n in [1, 2, 3, 4, 10, 32, 64] {
+            // badly scaled rows and columns
+            let factor: Vec<f64> = (0..n).map(|_| 10.0f64.powi(random::<i32>() % 20)).collect();
+            let mut a = random_positive_definite(n);
+            for j in 0..n {
+                for i in 0..n {
+                    a[(i, j)] = a[(i, j)].scale(factor[i] * factor[j]);
+                }
+            }
+
+            let mut scale = Mat::<f64>::zeros(n, 1);
+            let info = compute_equilibration(a.as_ref(), scale.as_mut().col(0)).unwrap();
+            assert!(info.condition <= 1.0);
+
+            let mut llt = a.clone();
+            equilibrate_in_place(llt.as_mut(), scale.as_ref().col(0));
+            for i in 0..n {
+                let s = scale[(i, 0)];
+                assert!(s.to_bits() & ((1 << 52) - 1) == 0);
+                assert!(llt[(i, i)].re > 0.25);
+                assert!(llt[(i, i)].re <= 1.0);
+            }
+
+            cholesky_in_place(
+                llt.as_mut(),
+                Parallelism::Rayon(8),
+                DynStack::new(&mut []),
+                Default::default(),
+            )
+            .unwrap();
+
+            let k = 2;
+            let rhs = Mat::with_dims(|_, _| T::new(random(), random()), n, k);
+            let mut sol = rhs.clone();
+            solve_equilibrated_in_place(
+                llt.as_ref(),
+                Conj::No,
+                scale.as_ref().col(0),
+                sol.as_mut(),
+                Conj::No,
+                Parallelism::Rayon(8),
+            );
+
+            // the backward error is small relative to the magnitude of each row
+            let mut residual = rhs.clone();
+            mul::matmul(
+                residual.as_mut(),
+                Conj::No,
+                a.as_ref(),
+                Conj::No,
+                sol.as_ref(),
+                Conj::No,
+                Some(T::one()),
+                -T::one(),
+                Parallelism::None,
+            );
+            for j in 0..k {
+                for i in 0..n {
+                    let mut bound = rhs[(i, j)].abs();
+                    for l in 0..n {
+                        bound += a[(i, l)].abs() * sol[(l, j)].abs();
+                    }
+                    assert!(residual[(i, j)].abs() <= 1e-8 * bound);
+                }
+            }
+        }
+
+        // not positive definite
+        let mut a = random_positive_definite(4);
+        a[(2, 2)] = T::new(-1.0, 0.0);
+        let mut scale = Mat::<f64>::zeros(4, 1);
+        assert!(compute_equilibration(a.as_ref(), scale.as_mut().col(0)).is_err());
+    }
+
     #[test]
     fn test_update() {
         use mul::triangular::BlockStructure::*;
@@ -365,4 +760,227 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_insert_not_positive_definite() {
+        let n = 4;
+        let r = 2;
+        let position = 1;
+
+        // the factor of the identity, and the inserted columns of the extended matrix, whose
+        // rows are split into [a01; a11; a21], with a01 = 0
+        let extended_identity = || {
+            Mat::with_dims(
+                |i, j| if i == j && i < n { T::one() } else { T::zero() },
+                n + r,
+                n + r,
+            )
+        };
+        let insert = |a: &mut Mat<T>, w: &mut Mat<T>| {
+            insert_rows_and_cols_clobber(
+                a.as_mut(),
+                position,
+                w.as_mut(),
+                Parallelism::None,
+                DynStack::new(&mut GlobalMemBuffer::new(
+                    insert_rows_and_cols_clobber_req::<T>(r, Parallelism::None).unwrap(),
+                )),
+            )
+        };
+
+        // a11 = diag(1, -1) breaks down at its second column
+        let mut a = extended_identity();
+        let mut w = Mat::zeros(n + r, r);
+        w[(position, 0)] = T::one();
+        w[(position + 1, 1)] = -T::one();
+        let err = insert(&mut a, &mut w).unwrap_err();
+        assert!(err.column == position + 1);
+
+        // a11 = I, and the first row of a21 is [2, 0], so the trailing block becomes
+        // I - [2, 0]^* [2, 0], which breaks down at its first column
+        let mut a = extended_identity();
+        let mut w = Mat::zeros(n + r, r);
+        w[(position, 0)] = T::one();
+        w[(position + 1, 1)] = T::one();
+        w[(position + r, 0)] = T::new(2.0, 0.0);
+        let err = insert(&mut a, &mut w).unwrap_err();
+        assert!(err.column == position + r);
+    }
+
+    fn mixed_precision_solve_and_check(
+        a: &Mat<f64>,
+        params: MixedPrecisionParams<f64>,
+    ) -> Result<MixedPrecisionInfo, CholeskyError<f64>> {
+        let n = a.nrows();
+        let k = 3;
+        let rhs = Mat::with_dims(|_, _| random::<f64>(), n, k);
+        let mut x = Mat::zeros(n, k);
+
+        let info = mixed_precision::solve(
+            x.as_mut(),
+            a.as_ref(),
+            rhs.as_ref(),
+            Parallelism::None,
+            DynStack::new(&mut GlobalMemBuffer::new(
+                mixed_precision::solve_req::<f64>(n, k, Parallelism::None).unwrap(),
+            )),
+            params,
+        )?;
+
+        let mut residual = rhs.clone();
+        mul::matmul(
+            residual.as_mut(),
+            Conj::No,
+            a.as_ref(),
+            Conj::No,
+            x.as_ref(),
+            Conj::No,
+            Some(1.0),
+            -1.0,
+            Parallelism::None,
+        );
+        let a_norm = (0..n)
+            .map(|i| (0..n).map(|j| a[(i, j)].abs()).sum::<f64>())
+            .fold(0.0, f64::max);
+        for j in 0..k {
+            let r_norm = (0..n).map(|i| residual[(i, j)].abs()).fold(0.0, f64::max);
+            let x_norm = (0..n).map(|i| x[(i, j)].abs()).fold(0.0, f64::max);
+            assert!(r_norm <= 1e-13 * a_norm * x_norm);
+        }
+        Ok(info)
+    }
+
+    #[test]
+    fn test_mixed_precision() {
+        for n in [1, 2, 3, 4, 10, 33, 64, 200] {
+            let b = Mat::with_dims(|_, _| random::<f64>(), n, n);
+            let mut a = Mat::zeros(n, n);
+            mul::matmul(
+                a.as_mut(),
+                Conj::No,
+                b.as_ref().transpose(),
+                Conj::No,
+                b.as_ref(),
+                Conj::No,
+                None,
+                1.0,
+                Parallelism::None,
+            );
+            // shift the spectrum to keep the matrix well conditioned
+            for i in 0..n {
+                a[(i, i)] += n as f64;
+            }
+            let info = mixed_precision_solve_and_check(&a, Default::default()).unwrap();
+            assert!(info.fallback_reason.is_none());
+        }
+    }
+
+    #[test]
+    fn test_mixed_precision_complex() {
+        for n in [1, 2, 3, 10, 64] {
+            let b = Mat::with_dims(|_, _| T::new(random(), random()), n, n);
+            let mut a = Mat::zeros(n, n);
+            mul::matmul(
+                a.as_mut(),
+                Conj::No,
+                b.as_ref().transpose(),
+                Conj::Yes,
+                b.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+            for i in 0..n {
+                a[(i, i)] += T::from_real(n as f64);
+            }
+            let rhs = Mat::with_dims(|_, _| T::new(random(), random()), n, 2);
+            let mut x = Mat::zeros(n, 2);
+
+            let info = mixed_precision::solve(
+                x.as_mut(),
+                a.as_ref(),
+                rhs.as_ref(),
+                Parallelism::None,
+                DynStack::new(&mut GlobalMemBuffer::new(
+                    mixed_precision::solve_req::<T>(n, 2, Parallelism::None).unwrap(),
+                )),
+                Default::default(),
+            )
+            .unwrap();
+            assert!(info.fallback_reason.is_none());
+
+            let mut residual = rhs.clone();
+            mul::matmul(
+                residual.as_mut(),
+                Conj::No,
+                a.as_ref(),
+                Conj::No,
+                x.as_ref(),
+                Conj::No,
+                Some(T::one()),
+                -T::one(),
+                Parallelism::None,
+            );
+            for j in 0..2 {
+                for i in 0..n {
+                    assert!(residual[(i, j)].abs() <= 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixed_precision_fallback() {
+        // the hilbert matrix is too ill conditioned for the single precision factorization to
+        // be useful
+        let n = 8;
+        let a = Mat::with_dims(|i, j| 1.0 / (i + j + 1) as f64, n, n);
+        let info = mixed_precision_solve_and_check(&a, Default::default()).unwrap();
+        assert!(info.fallback_reason.is_some());
+
+        // the refinement isn't allowed to run
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), n, n);
+        for j in 0..n {
+            for i in 0..j {
+                a[(i, j)] = a[(j, i)];
+            }
+            a[(j, j)] += n as f64;
+        }
+        let mut params = MixedPrecisionParams::default();
+        params.max_iterations = Some(0);
+        let info = mixed_precision_solve_and_check(&a, params).unwrap();
+        assert!(
+            info == MixedPrecisionInfo {
+                iterations: 0,
+                fallback_reason: Some(FallbackReason::NoConvergence),
+            }
+        );
+
+        // the number of iterations that were performed is reported
+        params.max_iterations = Some(1);
+        params.tolerance = Some(0.0);
+        let info = mixed_precision_solve_and_check(&a, params).unwrap();
+        assert!(
+            info == MixedPrecisionInfo {
+                iterations: 1,
+                fallback_reason: Some(FallbackReason::NoConvergence),
+            }
+        );
+
+        // out of the range of single precision
+        let mut a_large = a.clone();
+        a_large[(0, 0)] = 1e300;
+        let info = mixed_precision_solve_and_check(&a_large, Default::default()).unwrap();
+        assert!(
+            info == MixedPrecisionInfo {
+                iterations: 0,
+                fallback_reason: Some(FallbackReason::NotRepresentable),
+            }
+        );
+
+        // not positive definite
+        let a = Mat::with_dims(|i, j| if i == j { -1.0 } else { 0.0 }, n, n);
+        assert!(mixed_precision_solve_and_check(&a, Default::default()).is_err());
+    }
 }
@@ -1,8 +1,18 @@
-use faer_core::{solve, ComplexField, Conj, MatMut, MatRef, Parallelism};
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    error::{check_dimensions, FaerError},
+    mul::triangular::{self, BlockStructure},
+    norm::norm_one_estimate,
+    solve, temp_mat_req, temp_mat_uninit, ColMut, ColRef, ComplexField, Conj, MatMut, MatRef,
+    Parallelism, RealField,
+};
 use reborrow::*;
 
 use assert2::assert as fancy_assert;
 
+/// Default maximum number of refinement steps of [`solve_expert_to`].
+const DEFAULT_MAX_REFINEMENT_STEPS: usize = 5;
+
 /// Given the Cholesky factor of a matrix $A$ and a matrix $B$ stored in `rhs`, this function
 /// computes the solution of the linear system:
 /// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
@@ -46,6 +56,70 @@ pub fn solve_in_place<T: ComplexField>(
     );
 }
 
+/// Given the Cholesky factor of a matrix $A$ and a matrix $B$ stored in `rhs`, this function
+/// computes the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// This is the fallible variant of [`solve_in_place`], which returns an error instead of
+/// panicking if the dimensions of the inputs don't match.
+pub fn try_solve_in_place<T: ComplexField>(
+    cholesky_factors: MatRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+) -> Result<(), FaerError> {
+    let n = cholesky_factors.nrows();
+    check_dimensions("cholesky_factors", (n, cholesky_factors.ncols()), (n, n))?;
+    check_dimensions("rhs", (rhs.nrows(), rhs.ncols()), (n, rhs.ncols()))?;
+    solve_in_place(cholesky_factors, conj_lhs, rhs, conj_rhs, parallelism);
+    Ok(())
+}
+
+/// Given the Cholesky factor of the equilibrated matrix $SAS$, where $S$ is the diagonal matrix
+/// whose diagonal elements are stored in `scale`, and a matrix $B$ stored in `rhs`, this function
+/// computes the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B),$$
+/// by solving $\text{Op}_A(SAS)Y = S\text{Op}_B(B)$, then undoing the scaling with $X = SY$.
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in `rhs`.
+#[track_caller]
+pub fn solve_equilibrated_in_place<T: ComplexField>(
+    cholesky_factors: MatRef<'_, T>,
+    conj_lhs: Conj,
+    scale: ColRef<'_, T::Real>,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+) {
+    let n = cholesky_factors.nrows();
+    fancy_assert!(scale.nrows() == n);
+    fancy_assert!(rhs.nrows() == n);
+
+    let mut rhs = rhs;
+    let scale_rows = |mut rhs: MatMut<'_, T>| {
+        for j in 0..rhs.ncols() {
+            for i in 0..n {
+                let x = rhs[(i, j)];
+                rhs[(i, j)] = x.scale(scale[i]);
+            }
+        }
+    };
+
+    scale_rows(rhs.rb_mut());
+    solve_in_place(
+        cholesky_factors,
+        conj_lhs,
+        rhs.rb_mut(),
+        conj_rhs,
+        parallelism,
+    );
+    scale_rows(rhs);
+}
+
 /// Given the Cholesky factor of a matrix $A$ and a matrix $B$ stored in `rhs`, this function
 /// computes the solution of the linear system:
 /// $$\text{Op}_A(A)^\top X = \text{Op}_B(B).$$
@@ -74,3 +148,276 @@ pub fn solve_transpose_in_place<T: ComplexField>(
         parallelism,
     )
 }
+
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct SolveExpertParams {
+    /// Maximum number of iterative refinement steps performed for each right-hand side. Defaults
+    /// to `5`.
+    pub max_refinement_steps: Option<usize>,
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by a
+/// hermitian positive definite matrix, given its Cholesky decomposition, along with error bounds
+/// on the solution.
+pub fn solve_expert_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    StackReq::try_all_of([
+        temp_mat_req::<T>(dim, 1)?,
+        temp_mat_req::<T>(dim, 1)?,
+        temp_mat_req::<T>(dim, 1)?,
+    ])
+}
+
+/// Given a hermitian positive definite matrix $A$, its Cholesky factor, and a matrix $B$ stored
+/// in `rhs`, this function computes the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B),$$
+/// improves it with iterative refinement, and computes error bounds for each of its columns.
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// Only the lower triangular part of `matrix` is accessed. The solution of the linear system is
+/// stored in `dst`.
+///
+/// For each column $x$ of the solution, with corresponding column $b$ of $\text{Op}_B(B)$, the
+/// componentwise relative backward error
+/// $$\max_i \frac{|b - \text{Op}_A(A) x|_i}{(|\text{Op}_A(A)| |x| + |b|)_i}$$
+/// is stored in `berr`, and an estimated bound on the relative forward error
+/// $\|x - x_{\text{true}}\|_\infty / \|x\|_\infty$ is stored in `ferr`.
+///
+/// The refinement of a column stops once its backward error reaches the machine epsilon, stops
+/// decreasing by at least a factor of two, or the maximum number of refinement steps is reached.
+///
+/// # Panics
+///
+/// - Panics if `cholesky_factors` is not a square matrix.
+/// - Panics if `matrix` doesn't have the same shape as `cholesky_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `cholesky_factors`.
+/// - Panics if `rhs` and `dst` don't have the same shape.
+/// - Panics if `berr` or `ferr` don't have the same length as the number of columns of `rhs`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_expert_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    berr: ColMut<'_, T::Real>,
+    ferr: ColMut<'_, T::Real>,
+    matrix: MatRef<'_, T>,
+    cholesky_factors: MatRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatRef<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: SolveExpertParams,
+) {
+    let n = cholesky_factors.nrows();
+    let k = rhs.ncols();
+    fancy_assert!(cholesky_factors.ncols() == n);
+    fancy_assert!((matrix.nrows(), matrix.ncols()) == (n, n));
+    fancy_assert!(rhs.nrows() == n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, k));
+    fancy_assert!(berr.nrows() == k);
+    fancy_assert!(ferr.nrows() == k);
+
+    let mut dst = dst;
+    let mut berr = berr;
+    let mut ferr = ferr;
+
+    let max_refinement_steps = params
+        .max_refinement_steps
+        .unwrap_or(DEFAULT_MAX_REFINEMENT_STEPS);
+
+    dst.rb_mut()
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| *dst = *src);
+    solve_in_place(
+        cholesky_factors,
+        conj_lhs,
+        dst.rb_mut(),
+        conj_rhs,
+        parallelism,
+    );
+
+    temp_mat_uninit! {
+        let (mut residual, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut weights, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut sign, mut stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+    }
+    let mut residual = residual.rb_mut().col(0);
+    let mut weights = weights.rb_mut().col(0);
+    let mut sign = sign.rb_mut().col(0);
+
+    let zero = T::Real::zero();
+    let one = T::Real::one();
+    let eps = T::Real::epsilon();
+    // maximum number of nonzeros in a row of A, plus one
+    let nz = T::Real::from_f64((n + 1) as f64);
+    // thresholds that guard against dividing by a tiny or zero denominator
+    let safe1 = nz * T::Real::min_positive();
+    let safe2 = safe1 / eps;
+
+    let conj_b = |b: T| match conj_rhs {
+        Conj::No => b,
+        Conj::Yes => b.conj(),
+    };
+    let conj_upper = match conj_lhs {
+        Conj::No => Conj::Yes,
+        Conj::Yes => Conj::No,
+    };
+
+    for j in 0..k {
+        let mut x = dst.rb_mut().col(j);
+        let b = rhs.col(j);
+
+        let mut prev_berr = one + one + one;
+        let mut step = 0;
+        loop {
+            // r = Op_B(b) - Op_A(A) x, where the upper triangular part of A is the adjoint of its
+            // lower triangular part
+            for i in 0..n {
+                residual[i] = conj_b(b[i]);
+            }
+            triangular::matmul(
+                residual.rb_mut().as_2d(),
+                BlockStructure::Rectangular,
+                Conj::No,
+                matrix,
+                BlockStructure::TriangularLower,
+                conj_lhs,
+                x.rb().as_2d(),
+                BlockStructure::Rectangular,
+                Conj::No,
+                Some(T::one()),
+                -T::one(),
+                parallelism,
+            );
+            triangular::matmul(
+                residual.rb_mut().as_2d(),
+                BlockStructure::Rectangular,
+                Conj::No,
+                matrix.transpose(),
+                BlockStructure::StrictTriangularUpper,
+                conj_upper,
+                x.rb().as_2d(),
+                BlockStructure::Rectangular,
+                Conj::No,
+                Some(T::one()),
+                -T::one(),
+                parallelism,
+            );
+
+            // w = |A| |x| + |b|
+            for i in 0..n {
+                let mut w = b[i].abs();
+                for l in 0..n {
+                    let a = if i >= l {
+                        matrix[(i, l)]
+                    } else {
+                        matrix[(l, i)]
+                    };
+                    w = w + a.abs() * x[l].abs();
+                }
+                weights[i] = T::from_real(w);
+            }
+
+            let mut backward_error = zero;
+            for i in 0..n {
+                let r = residual[i].abs();
+                let w = weights[i].real();
+                let ratio = if w > safe2 {
+                    r / w
+                } else {
+                    (r + safe1) / (w + safe1)
+                };
+                if ratio > backward_error {
+                    backward_error = ratio;
+                }
+            }
+            berr[j] = backward_error;
+
+            if !(backward_error > eps
+                && backward_error + backward_error <= prev_berr
+                && step < max_refinement_steps)
+            {
+                break;
+            }
+
+            // x += Op_A(A)^-1 r
+            solve_in_place(
+                cholesky_factors,
+                conj_lhs,
+                residual.rb_mut().as_2d(),
+                Conj::No,
+                parallelism,
+            );
+            for i in 0..n {
+                x[i] = x[i] + residual[i];
+            }
+
+            prev_berr = backward_error;
+            step += 1;
+        }
+
+        // the error is bounded by || |A^-1| (|r| + nz eps (|A| |x| + |b|)) ||, where the
+        // second term accounts for the rounding errors in the computation of the residual
+        for i in 0..n {
+            let r = residual[i].abs();
+            let w = weights[i].real();
+            let w = if w > safe2 {
+                r + nz * eps * w
+            } else {
+                r + nz * eps * w + safe1
+            };
+            weights[i] = T::from_real(w);
+        }
+        let weights = weights.rb();
+
+        // || |A^-1| w ||_inf = ||A^-1 diag(w)||_inf is the 1-norm of diag(w) A^-1, since A is
+        // hermitian
+        let apply = |v: ColMut<'_, T>, _: DynStack<'_>| {
+            let mut v = v;
+            solve_in_place(
+                cholesky_factors,
+                conj_lhs,
+                v.rb_mut().as_2d(),
+                Conj::No,
+                parallelism,
+            );
+            for i in 0..n {
+                v[i] = v[i].scale(weights[i].real());
+            }
+        };
+        let apply_adjoint = |v: ColMut<'_, T>, _: DynStack<'_>| {
+            let mut v = v;
+            for i in 0..n {
+                v[i] = v[i].scale(weights[i].real());
+            }
+            solve_in_place(cholesky_factors, conj_lhs, v.as_2d(), Conj::No, parallelism);
+        };
+        let error_norm = norm_one_estimate(
+            residual.rb_mut(),
+            sign.rb_mut(),
+            apply,
+            apply_adjoint,
+            stack.rb_mut(),
+        );
+
+        let mut x_norm = zero;
+        for i in 0..n {
+            let abs = x[i].abs();
+            if abs > x_norm {
+                x_norm = abs;
+            }
+        }
+        ferr[j] = if x_norm == zero {
+            error_norm
+        } else {
+            error_norm / x_norm
+        };
+    }
+}
@@ -12,7 +12,7 @@ use seq_macro::seq;
 
 use crate::{
     ldlt_diagonal::update::{delete_rows_and_cols_triangular, rank_update_indices},
-    llt::compute::{cholesky_in_place, cholesky_in_place_req},
+    llt::compute::{cholesky_in_place, cholesky_in_place_req, offset_error},
 };
 
 use super::CholeskyError;
@@ -160,7 +160,7 @@ struct RankRUpdate<'a, T> {
 }
 
 impl<'a, T: ComplexField> pulp::WithSimd for RankRUpdate<'a, T> {
-    type Output = Result<(), CholeskyError>;
+    type Output = Result<(), CholeskyError<T::Real>>;
 
     #[inline(always)]
     fn with_simd<S: Simd>(self, s: S) -> Self::Output {
@@ -208,7 +208,10 @@ impl<'a, T: ComplexField> pulp::WithSimd for RankRUpdate<'a, T> {
 
                         let sqr_nljj = ljj * ljj + alpha_conj_wj * wj;
                         if !(sqr_nljj.real() > T::Real::zero()) {
-                            return Err(CholeskyError);
+                            return Err(CholeskyError {
+                                column: j,
+                                pivot: sqr_nljj.real(),
+                            });
                         }
                         let nljj = sqr_nljj.sqrt();
                         let inv_ljj = ljj.inv();
@@ -421,7 +424,7 @@ pub fn rank_r_update_clobber<T: ComplexField>(
     cholesky_factor: MatMut<'_, T>,
     w: MatMut<'_, T>,
     alpha: ColMut<'_, T>,
-) -> Result<(), CholeskyError> {
+) -> Result<(), CholeskyError<T::Real>> {
     let n = cholesky_factor.nrows();
     let k = w.ncols();
 
@@ -536,6 +539,9 @@ pub fn insert_rows_and_cols_clobber_req<T: 'static>(
 /// $L$ of a matrix $A$ in its top left corner, of dimension `n`, and computes the Cholesky factor
 /// of $A$ with the provided `inserted_matrix` inserted at the position starting at
 /// `insertion_index`.
+///
+/// If the extended matrix is not positive definite, the column held by the returned error is the
+/// index of the offending column in the extended matrix.
 #[track_caller]
 pub fn insert_rows_and_cols_clobber<T: ComplexField>(
     cholesky_factor_extended: MatMut<'_, T>,
@@ -543,7 +549,7 @@ pub fn insert_rows_and_cols_clobber<T: ComplexField>(
     inserted_matrix: MatMut<'_, T>,
     parallelism: Parallelism,
     stack: DynStack<'_>,
-) -> Result<(), CholeskyError> {
+) -> Result<(), CholeskyError<T::Real>> {
     let new_n = cholesky_factor_extended.nrows();
     let r = inserted_matrix.ncols();
 
@@ -637,7 +643,8 @@ pub fn insert_rows_and_cols_clobber<T: ComplexField>(
         parallelism,
         stack.rb_mut(),
         Default::default(),
-    )?;
+    )
+    .map_err(|e| offset_error(e, insertion_index))?;
     let l11 = l11.into_const();
 
     let rem = l21.nrows();
@@ -684,5 +691,5 @@ pub fn insert_rows_and_cols_clobber<T: ComplexField>(
         }
     }
 
-    rank_r_update_clobber(ld22, w, alpha)
+    rank_r_update_clobber(ld22, w, alpha).map_err(|e| offset_error(e, insertion_index + r))
 }
@@ -0,0 +1,345 @@
+//! The modified Cholesky decomposition of a hermitian matrix $A$, which may be indefinite, is such
+//! that:
+//! $$A + E = LL^*,$$
+//! where $E$ is a nonnegative diagonal perturbation that makes $A + E$ positive definite, and $L$
+//! is a lower triangular matrix.
+//!
+//! This is typically used in optimization, to compute descent directions from indefinite
+//! Hessians. If $A$ is sufficiently positive definite, the perturbation is zero and the
+//! decomposition is the same as the one computed by [`crate::llt::compute::cholesky_in_place`].
+//!
+//! The perturbation is computed with the Gill-Murray strategy. When column $j$ is reached, with
+//! $c_{jj}$ its diagonal element and $\theta_j$ the largest absolute value of its elements below
+//! the diagonal, after the updates from the previous columns, the pivot is set to
+//! $$d_j = \max\left(|c_{jj}|, \frac{\theta_j^2}{\beta^2}, \delta\right),$$
+//! which bounds the off-diagonal elements of $L$ by $\beta$, and therefore the norm of $E$. The
+//! default values of $\beta$ and $\delta$ are computed from $\gamma$ and $\xi$, the largest
+//! absolute values of the diagonal and off-diagonal elements of $A$.
+
+use assert2::{assert as fancy_assert, debug_assert as fancy_debug_assert};
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{ColMut, ComplexField, MatMut, MatRef, Parallelism, RealField};
+use reborrow::*;
+
+use crate::llt::compute::{cholesky_in_place_impl, cholesky_in_place_req, Regularization};
+
+#[derive(Copy, Clone)]
+#[non_exhaustive]
+pub struct ModifiedCholeskyParams<R> {
+    /// Minimum value $\delta$ of the pivots. Defaults to $\varepsilon \max(\gamma, \xi, 1)$,
+    /// where $\varepsilon$ is the machine epsilon.
+    pub min_pivot: Option<R>,
+    /// Bound $\beta$ on the absolute values of the off-diagonal elements of $L$. Defaults to
+    /// $\sqrt{\max(\gamma, \xi / \sqrt{n^2 - 1}, \varepsilon)}$, where $n$ is the dimension of
+    /// the matrix, which minimizes the a priori bound on the norm of $E$.
+    pub off_diagonal_bound: Option<R>,
+}
+
+impl<R> Default for ModifiedCholeskyParams<R> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            min_pivot: None,
+            off_diagonal_bound: None,
+        }
+    }
+}
+
+/// Returns $\gamma$ and $\xi$, the largest absolute values of the diagonal and off-diagonal
+/// elements of the lower triangular half of `matrix`.
+fn max_abs_diagonal_and_off_diagonal<T: ComplexField>(matrix: MatRef<'_, T>) -> (T::Real, T::Real) {
+    let n = matrix.nrows();
+    let mut gamma = T::Real::zero();
+    let mut xi = T::Real::zero();
+    for j in 0..n {
+        let abs = matrix[(j, j)].real().abs();
+        if abs > gamma {
+            gamma = abs;
+        }
+        for i in j + 1..n {
+            let abs = matrix[(i, j)].abs();
+            if abs > xi {
+                xi = abs;
+            }
+        }
+    }
+    (gamma, xi)
+}
+
+/// Computes the size and alignment of required workspace for performing a modified Cholesky
+/// decomposition.
+pub fn modified_cholesky_in_place_req<T: ComplexField>(
+    dim: usize,
+    parallelism: Parallelism,
+    params: ModifiedCholeskyParams<T::Real>,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = params;
+    cholesky_in_place_req::<T>(dim, parallelism, Default::default())
+}
+
+/// Computes the modified Cholesky factor $L$ of a hermitian input matrix $A$, and the diagonal
+/// perturbation $E$ such that $L$ is lower triangular, and
+/// $$LL^* == A + E.$$
+///
+/// The factor is stored back in the same matrix, and the diagonal elements of $E$ are stored in
+/// `perturbation`.
+///
+/// The input matrix is interpreted as symmetric and only the lower triangular part is read.
+///
+/// The strictly upper triangular part of the matrix is clobbered and may be filled with garbage
+/// values.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if `perturbation` doesn't have the same length as the dimension of the matrix.
+/// - Panics if the minimum pivot or the off-diagonal bound in `params` is not positive.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn modified_cholesky_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    perturbation: ColMut<'_, T::Real>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: ModifiedCholeskyParams<T::Real>,
+) {
+    let n = matrix.nrows();
+    fancy_assert!(
+        matrix.ncols() == n,
+        "only square matrices can be decomposed into cholesky factors",
+    );
+    fancy_assert!(perturbation.nrows() == n);
+
+    let (gamma, xi) = max_abs_diagonal_and_off_diagonal(matrix.rb());
+    let one = T::Real::one();
+    let epsilon = T::Real::epsilon();
+
+    let min_pivot = params.min_pivot.unwrap_or_else(|| {
+        let mut max = one;
+        if gamma > max {
+            max = gamma;
+        }
+        if xi > max {
+            max = xi;
+        }
+        epsilon * max
+    });
+    let off_diagonal_bound = params.off_diagonal_bound.unwrap_or_else(|| {
+        let mut beta_squared = epsilon;
+        if gamma > beta_squared {
+            beta_squared = gamma;
+        }
+        if n > 1 {
+            let n_real = T::Real::from_f64(n as f64);
+            let xi_scaled = xi / (n_real * n_real - one).sqrt();
+            if xi_scaled > beta_squared {
+                beta_squared = xi_scaled;
+            }
+        }
+        beta_squared.sqrt()
+    });
+    fancy_assert!(min_pivot > T::Real::zero());
+    fancy_assert!(off_diagonal_bound > T::Real::zero());
+
+    let result = cholesky_in_place_impl(
+        matrix,
+        Some(Regularization {
+            min_pivot,
+            off_diagonal_bound_squared: off_diagonal_bound * off_diagonal_bound,
+            perturbation,
+        }),
+        parallelism,
+        stack,
+    );
+    // the regularized pivots are positive, so the factorization doesn't break down
+    fancy_debug_assert!(result.is_ok());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llt::{compute::cholesky_in_place, reconstruct::reconstruct_lower_to};
+    use assert_approx_eq::assert_approx_eq;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c64, mul, Conj, Mat};
+    use rand::random;
+
+    type T = c64;
+
+    fn random_hermitian(n: usize) -> Mat<T> {
+        let a = Mat::with_dims(|_, _| T::new(random(), random()), n, n);
+        let mut ata = Mat::zeros(n, n);
+
+        mul::matmul(
+            ata.as_mut(),
+            Conj::No,
+            a.as_ref().transpose(),
+            Conj::Yes,
+            a.as_ref(),
+            Conj::No,
+            None,
+            T::one(),
+            Parallelism::Rayon(8),
+        );
+
+        ata
+    }
+
+    fn modified_cholesky(
+        a: &Mat<T>,
+        params: ModifiedCholeskyParams<f64>,
+    ) -> (Mat<T>, Mat<f64>, Mat<T>) {
+        let n = a.nrows();
+        let mut l = a.clone();
+        let mut e = Mat::zeros(n, 1);
+        modified_cholesky_in_place(
+            l.as_mut(),
+            e.as_mut().col(0),
+            Parallelism::Rayon(8),
+            DynStack::new(&mut GlobalMemBuffer::new(
+                modified_cholesky_in_place_req::<T>(n, Parallelism::Rayon(8), params).unwrap(),
+            )),
+            params,
+        );
+
+        let mut reconstructed = Mat::zeros(n, n);
+        reconstruct_lower_to(reconstructed.as_mut(), l.as_ref(), Parallelism::Rayon(8));
+        (l, e, reconstructed)
+    }
+
+    /// Checks that the off-diagonal elements of `l` are bounded by the default $\beta$, and that
+    /// the perturbation satisfies the a priori bound of the Gill-Murray strategy.
+    fn check_gill_murray_bounds(a: &Mat<T>, l: &Mat<T>, e: &Mat<f64>) {
+        let n = a.nrows();
+        let (gamma, xi) = max_abs_diagonal_and_off_diagonal(a.as_ref());
+        let nf = n as f64;
+        let beta_squared = if n > 1 {
+            gamma.max(xi / (nf * nf - 1.0).sqrt())
+        } else {
+            gamma
+        }
+        .max(f64::EPSILON);
+        let beta = beta_squared.sqrt();
+        let delta = f64::EPSILON * gamma.max(xi).max(1.0);
+        let bound = (xi / beta + (nf - 1.0) * beta).powi(2)
+            + 2.0 * (gamma + (nf - 1.0) * beta_squared)
+            + delta;
+
+        for j in 0..n {
+            assert!(e[(j, 0)] <= bound * (1.0 + 1e-10));
+            for i in j + 1..n {
+                assert!(l[(i, j)].abs() <= beta * (1.0 + 1e-10));
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_pivot() {
+        // a perturbation that only makes the pivots positive would add about 1e10 to the second
+        // diagonal element
+        let a = Mat::with_dims(
+            |i, j| match (i, j) {
+                (0, 0) => T::new(1e-10, 0.0),
+                (0, 1) | (1, 0) => T::one(),
+                _ => T::zero(),
+            },
+            2,
+            2,
+        );
+        let (l, e, reconstructed) = modified_cholesky(&a, Default::default());
+
+        check_gill_murray_bounds(&a, &l, &e);
+        for j in 0..2 {
+            assert!(e[(j, 0)] <= 2.0);
+            for i in j..2 {
+                let expected = if i == j {
+                    a[(i, j)] + T::new(e[(j, 0)], 0.0)
+                } else {
+                    a[(i, j)]
+                };
+                assert_approx_eq!(reconstructed[(i, j)], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_positive_definite() {
+        for n in [0, 1, 2, 3, 4, 10, 64, 100] {
+            let a = random_hermitian(n);
+            let (l, e, _) = modified_cholesky(&a, Default::default());
+
+            let mut llt = a.clone();
+            cholesky_in_place(
+                llt.as_mut(),
+                Parallelism::Rayon(8),
+                DynStack::new(&mut []),
+                Default::default(),
+            )
+            .unwrap();
+
+            for j in 0..n {
+                assert_eq!(e[(j, 0)], 0.0);
+                for i in j..n {
+                    assert_approx_eq!(l[(i, j)], llt[(i, j)]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_indefinite() {
+        for n in [1, 2, 3, 4, 10, 64, 100] {
+            let mut a = random_hermitian(n);
+            let mut indices = vec![0, n / 3, n / 2, n - 1];
+            indices.dedup();
+            for k in indices {
+                a[(k, k)] = -a[(k, k)];
+            }
+
+            let (l, e, reconstructed) = modified_cholesky(&a, Default::default());
+
+            assert!((0..n).any(|j| e[(j, 0)] > 0.0));
+            check_gill_murray_bounds(&a, &l, &e);
+            for j in 0..n {
+                assert!(e[(j, 0)] >= 0.0);
+                assert!(l[(j, j)].re > 0.0);
+                for i in j..n {
+                    let expected = if i == j {
+                        a[(i, j)] + T::new(e[(j, 0)], 0.0)
+                    } else {
+                        a[(i, j)]
+                    };
+                    assert_approx_eq!(reconstructed[(i, j)], expected, 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_perturbation() {
+        let n = 10;
+        let mut a = Mat::with_dims(
+            |i, j| if i == j { T::new(4.0, 0.0) } else { T::zero() },
+            n,
+            n,
+        );
+        a[(3, 3)] = T::new(-9.0, 0.0);
+        a[(7, 7)] = T::new(1e-3, 0.0);
+
+        let params = ModifiedCholeskyParams {
+            min_pivot: Some(1e-2),
+            ..Default::default()
+        };
+        let (l, e, _) = modified_cholesky(&a, params);
+
+        for j in 0..n {
+            let (expected_e, expected_l) = match j {
+                3 => (18.0, 3.0),
+                7 => (1e-2 - 1e-3, 0.1),
+                _ => (0.0, 2.0),
+            };
+            assert_approx_eq!(e[(j, 0)], expected_e);
+            assert_approx_eq!(l[(j, j)], T::new(expected_l, 0.0));
+        }
+    }
+}
@@ -0,0 +1,824 @@
+//! This module provides level 1 (vector-vector) and level 2 (matrix-vector) linear algebra
+//! routines.
+//!
+//! These routines don't go through the blocked matrix multiplication code, which makes them
+//! faster than [`matmul`](crate::mul::matmul) for small workloads.
+
+use crate::{
+    mul::triangular::{BlockStructure, DiagonalKind},
+    ColMut, ColRef, ComplexField, Conj, MatMut, MatRef, RowRef,
+};
+use assert2::assert as fancy_assert;
+use reborrow::*;
+
+#[inline(always)]
+fn identity<T>(x: T) -> T {
+    x
+}
+
+#[inline(always)]
+fn conj<T: ComplexField>(x: T) -> T {
+    x.conj()
+}
+
+/// Evaluates the body with the conjugation functions corresponding to the given `Conj` values,
+/// inside a SIMD dispatch context.
+macro_rules! dispatch_conj {
+    ($conj: expr, |$c: ident| $body: expr) => {
+        pulp::Arch::new().dispatch(
+            #[inline(always)]
+            || match $conj {
+                Conj::No => {
+                    let $c = identity;
+                    $body
+                }
+                Conj::Yes => {
+                    let $c = conj;
+                    $body
+                }
+            },
+        )
+    };
+    ($conj_lhs: expr, $conj_rhs: expr, |$cl: ident, $cr: ident| $body: expr) => {
+        pulp::Arch::new().dispatch(
+            #[inline(always)]
+            || match ($conj_lhs, $conj_rhs) {
+                (Conj::No, Conj::No) => {
+                    let ($cl, $cr) = (identity, identity);
+                    $body
+                }
+                (Conj::No, Conj::Yes) => {
+                    let ($cl, $cr) = (identity, conj);
+                    $body
+                }
+                (Conj::Yes, Conj::No) => {
+                    let ($cl, $cr) = (conj, identity);
+                    $body
+                }
+                (Conj::Yes, Conj::Yes) => {
+                    let ($cl, $cr) = (conj, conj);
+                    $body
+                }
+            },
+        )
+    };
+}
+
+/// Computes $\sum_i f_l(l_i) f_r(r_i)$.
+///
+/// # Safety
+///
+/// `lhs` and `rhs` must have the same length.
+#[inline(always)]
+unsafe fn dot_kernel<T: ComplexField>(
+    lhs: RowRef<'_, T>,
+    rhs: ColRef<'_, T>,
+    maybe_conj_lhs: impl Fn(T) -> T,
+    maybe_conj_rhs: impl Fn(T) -> T,
+) -> T {
+    let n = lhs.ncols();
+    let mut acc0 = T::zero();
+    let mut acc1 = T::zero();
+    let mut acc2 = T::zero();
+    let mut acc3 = T::zero();
+
+    if n == 0 {
+        return acc0;
+    }
+
+    if lhs.col_stride() == 1 && rhs.row_stride() == 1 {
+        let lhs = core::slice::from_raw_parts(lhs.as_ptr(), n);
+        let rhs = core::slice::from_raw_parts(rhs.as_ptr(), n);
+
+        // independent accumulators, to shorten the dependency chain
+        let lhs_chunks = lhs.chunks_exact(4);
+        let rhs_chunks = rhs.chunks_exact(4);
+        let lhs_rem = lhs_chunks.remainder();
+        let rhs_rem = rhs_chunks.remainder();
+        for (l, r) in lhs_chunks.zip(rhs_chunks) {
+            acc0 = acc0 + maybe_conj_lhs(l[0]) * maybe_conj_rhs(r[0]);
+            acc1 = acc1 + maybe_conj_lhs(l[1]) * maybe_conj_rhs(r[1]);
+            acc2 = acc2 + maybe_conj_lhs(l[2]) * maybe_conj_rhs(r[2]);
+            acc3 = acc3 + maybe_conj_lhs(l[3]) * maybe_conj_rhs(r[3]);
+        }
+        for (&l, &r) in lhs_rem.iter().zip(rhs_rem) {
+            acc0 = acc0 + maybe_conj_lhs(l) * maybe_conj_rhs(r);
+        }
+    } else {
+        for i in 0..n {
+            acc0 = acc0
+                + maybe_conj_lhs(*lhs.get_unchecked(i)) * maybe_conj_rhs(*rhs.get_unchecked(i));
+        }
+    }
+
+    (acc0 + acc1) + (acc2 + acc3)
+}
+
+/// Computes $d_i \leftarrow d_i + \alpha f(s_i)$.
+///
+/// # Safety
+///
+/// `dst` and `src` must have the same length.
+#[inline(always)]
+unsafe fn axpy_kernel<T: ComplexField>(
+    dst: ColMut<'_, T>,
+    alpha: T,
+    src: ColRef<'_, T>,
+    maybe_conj_src: impl Fn(T) -> T,
+) {
+    let n = dst.nrows();
+    if n == 0 {
+        return;
+    }
+
+    if dst.row_stride() == 1 && src.row_stride() == 1 {
+        let dst = core::slice::from_raw_parts_mut(dst.as_ptr(), n);
+        let src = core::slice::from_raw_parts(src.as_ptr(), n);
+        for (d, &s) in dst.iter_mut().zip(src) {
+            *d = *d + alpha * maybe_conj_src(s);
+        }
+    } else {
+        let mut dst = dst;
+        for i in 0..n {
+            let d = dst.rb_mut().ptr_in_bounds_at_unchecked(i);
+            *d = *d + alpha * maybe_conj_src(*src.get_unchecked(i));
+        }
+    }
+}
+
+/// Computes $d_i \leftarrow \alpha d_i$, or $d_i \leftarrow 0$ if $\alpha$ is not provided.
+#[inline(always)]
+fn scale_or_zero<T: ComplexField>(dst: ColMut<'_, T>, alpha: Option<T>) {
+    match alpha {
+        Some(alpha) => dst.cwise().for_each(|d| *d = alpha * *d),
+        None => dst.cwise().for_each(|d| *d = T::zero()),
+    }
+}
+
+/// Computes the dot product `sum(Op_lhs(lhs[i]) * Op_rhs(rhs[i]))`.
+///
+/// `Op_lhs` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+/// `Op_rhs` is the identity if `conj_rhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+///
+/// # Panics
+///
+/// Panics if `lhs.ncols() != rhs.nrows()`.
+#[track_caller]
+pub fn dot<T: ComplexField>(
+    lhs: RowRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: ColRef<'_, T>,
+    conj_rhs: Conj,
+) -> T {
+    fancy_assert!(lhs.ncols() == rhs.nrows());
+    dispatch_conj!(conj_lhs, conj_rhs, |cl, cr| unsafe {
+        dot_kernel(lhs, rhs, cl, cr)
+    })
+}
+
+/// Computes `dst + alpha * Op_src(src)` and stores the result in `dst`.
+///
+/// `Op_src` is the identity if `conj_src` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+/// Row vectors can be handled by transposing them.
+///
+/// # Panics
+///
+/// Panics if `dst.nrows() != src.nrows()`.
+#[track_caller]
+pub fn axpy<T: ComplexField>(dst: ColMut<'_, T>, alpha: T, src: ColRef<'_, T>, conj_src: Conj) {
+    fancy_assert!(dst.nrows() == src.nrows());
+    dispatch_conj!(conj_src, |cs| unsafe { axpy_kernel(dst, alpha, src, cs) })
+}
+
+/// Computes `alpha * dst` and stores the result in `dst`.
+pub fn scal<T: ComplexField>(dst: ColMut<'_, T>, alpha: T) {
+    pulp::Arch::new().dispatch(
+        #[inline(always)]
+        || scale_or_zero(dst, Some(alpha)),
+    )
+}
+
+/// Computes the rank one update `acc + alpha * Op_lhs(lhs) * Op_rhs(rhs)` and stores the result in
+/// `acc`.
+///
+/// `Op_lhs` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+/// `Op_rhs` is the identity if `conj_rhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+///
+/// # Panics
+///
+/// Panics if `acc.nrows() != lhs.nrows()` or `acc.ncols() != rhs.ncols()`.
+#[track_caller]
+pub fn ger<T: ComplexField>(
+    acc: MatMut<'_, T>,
+    alpha: T,
+    lhs: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: RowRef<'_, T>,
+    conj_rhs: Conj,
+) {
+    fancy_assert!(acc.nrows() == lhs.nrows());
+    fancy_assert!(acc.ncols() == rhs.ncols());
+
+    if acc.row_stride().unsigned_abs() > acc.col_stride().unsigned_abs() {
+        // the transpose of the update is `alpha * Op_rhs(rhs)^T * Op_lhs(lhs)^T`
+        return ger(
+            acc.transpose(),
+            alpha,
+            rhs.transpose(),
+            conj_rhs,
+            lhs.transpose(),
+            conj_lhs,
+        );
+    }
+
+    let mut acc = acc;
+    dispatch_conj!(conj_lhs, conj_rhs, |cl, cr| {
+        for j in 0..acc.ncols() {
+            let factor = alpha * cr(unsafe { *rhs.get_unchecked(j) });
+            unsafe { axpy_kernel(acc.rb_mut().col(j), factor, lhs, cl) };
+        }
+    })
+}
+
+/// Computes the hermitian rank one update `acc + alpha * lhs * lhs^*`, where `alpha` is real, and
+/// stores the result in `acc`.
+///
+/// Only the lower triangular part of `acc` is accessed. The imaginary parts of its diagonal
+/// elements are set to zero.
+///
+/// # Panics
+///
+/// Panics if `acc` is not a square matrix, or if `acc.nrows() != lhs.nrows()`.
+#[track_caller]
+pub fn her<T: ComplexField>(acc: MatMut<'_, T>, alpha: T::Real, lhs: ColRef<'_, T>) {
+    let n = acc.nrows();
+    fancy_assert!(acc.ncols() == n);
+    fancy_assert!(lhs.nrows() == n);
+
+    let mut acc = acc;
+    pulp::Arch::new().dispatch(
+        #[inline(always)]
+        || {
+            for j in 0..n {
+                let factor = lhs[j].conj().scale(alpha);
+                let acc = acc.rb_mut().col(j).split_at(j).1;
+                unsafe { axpy_kernel(acc, factor, lhs.split_at(j).1, identity) };
+            }
+        },
+    );
+    for j in 0..n {
+        let d = acc[(j, j)];
+        acc[(j, j)] = T::from_real(d.real());
+    }
+}
+
+/// Computes the hermitian rank two update `acc + alpha * lhs * rhs^* + conj(alpha) * rhs * lhs^*`
+/// and stores the result in `acc`.
+///
+/// Only the lower triangular part of `acc` is accessed. The imaginary parts of its diagonal
+/// elements are set to zero.
+///
+/// # Panics
+///
+/// Panics if `acc` is not a square matrix, or if `acc.nrows() != lhs.nrows()` or
+/// `acc.nrows() != rhs.nrows()`.
+#[track_caller]
+pub fn her2<T: ComplexField>(acc: MatMut<'_, T>, alpha: T, lhs: ColRef<'_, T>, rhs: ColRef<'_, T>) {
+    let n = acc.nrows();
+    fancy_assert!(acc.ncols() == n);
+    fancy_assert!(lhs.nrows() == n);
+    fancy_assert!(rhs.nrows() == n);
+
+    let mut acc = acc;
+    pulp::Arch::new().dispatch(
+        #[inline(always)]
+        || {
+            for j in 0..n {
+                let lhs_factor = alpha * rhs[j].conj();
+                let rhs_factor = alpha.conj() * lhs[j].conj();
+                unsafe {
+                    axpy_kernel(
+                        acc.rb_mut().col(j).split_at(j).1,
+                        lhs_factor,
+                        lhs.split_at(j).1,
+                        identity,
+                    );
+                    axpy_kernel(
+                        acc.rb_mut().col(j).split_at(j).1,
+                        rhs_factor,
+                        rhs.split_at(j).1,
+                        identity,
+                    );
+                }
+            }
+        },
+    );
+    for j in 0..n {
+        let d = acc[(j, j)];
+        acc[(j, j)] = T::from_real(d.real());
+    }
+}
+
+/// Computes the matrix-vector product `[alpha * acc] + beta * Op_lhs(lhs) * Op_rhs(rhs)` and
+/// stores the result in `acc`.
+///
+/// If `alpha` is not provided, the preexisting values in `acc` are not read.
+///
+/// `Op_lhs` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+/// `Op_rhs` is the identity if `conj_rhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+///
+/// # Panics
+///
+/// Panics if `acc.nrows() != lhs.nrows()` or `lhs.ncols() != rhs.nrows()`.
+#[track_caller]
+pub fn gemv<T: ComplexField>(
+    acc: ColMut<'_, T>,
+    lhs: MatRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: ColRef<'_, T>,
+    conj_rhs: Conj,
+    alpha: Option<T>,
+    beta: T,
+) {
+    fancy_assert!(acc.nrows() == lhs.nrows());
+    fancy_assert!(lhs.ncols() == rhs.nrows());
+
+    let mut acc = acc;
+    let row_major = lhs.row_stride().unsigned_abs() > lhs.col_stride().unsigned_abs();
+    dispatch_conj!(conj_lhs, conj_rhs, |cl, cr| {
+        if row_major {
+            for i in 0..lhs.nrows() {
+                let dot = unsafe { dot_kernel(lhs.row(i), rhs, cl, cr) };
+                let a = &mut acc[i];
+                *a = match alpha {
+                    Some(alpha) => alpha * *a + beta * dot,
+                    None => beta * dot,
+                };
+            }
+        } else {
+            scale_or_zero(acc.rb_mut(), alpha);
+            for j in 0..lhs.ncols() {
+                let factor = beta * cr(rhs[j]);
+                unsafe { axpy_kernel(acc.rb_mut(), factor, lhs.col(j), cl) };
+            }
+        }
+    })
+}
+
+/// Computes the matrix-vector product `[alpha * acc] + beta * Op_lhs(lhs) * Op_rhs(rhs)`, where
+/// `lhs` is a hermitian matrix, and stores the result in `acc`.
+///
+/// Only the lower triangular part of `lhs` is accessed, and the imaginary parts of its diagonal
+/// elements are assumed to be zero.
+/// If `alpha` is not provided, the preexisting values in `acc` are not read.
+///
+/// `Op_lhs` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+/// `Op_rhs` is the identity if `conj_rhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+///
+/// # Panics
+///
+/// Panics if `lhs` is not a square matrix, or if `acc.nrows() != lhs.nrows()` or
+/// `lhs.ncols() != rhs.nrows()`.
+#[track_caller]
+pub fn symv<T: ComplexField>(
+    acc: ColMut<'_, T>,
+    lhs: MatRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: ColRef<'_, T>,
+    conj_rhs: Conj,
+    alpha: Option<T>,
+    beta: T,
+) {
+    let n = lhs.nrows();
+    fancy_assert!(lhs.ncols() == n);
+    fancy_assert!(acc.nrows() == n);
+    fancy_assert!(rhs.nrows() == n);
+
+    // the strictly upper triangular part is the adjoint of the strictly lower triangular part
+    let conj_lhs_adjoint = match conj_lhs {
+        Conj::No => Conj::Yes,
+        Conj::Yes => Conj::No,
+    };
+
+    let mut acc = acc;
+    dispatch_conj!(conj_lhs, conj_rhs, |cl, cr| {
+        scale_or_zero(acc.rb_mut(), alpha);
+        for j in 0..n {
+            let x = cr(rhs[j]);
+            let (_, below) = lhs.col(j).split_at(j + 1);
+            let (_, rhs_below) = rhs.split_at(j + 1);
+
+            // lower part: column j
+            unsafe { axpy_kernel(acc.rb_mut().split_at(j + 1).1, beta * x, below, cl) };
+
+            // upper part: row j
+            let dot = match conj_lhs_adjoint {
+                Conj::No => unsafe { dot_kernel(below.transpose(), rhs_below, identity, cr) },
+                Conj::Yes => unsafe { dot_kernel(below.transpose(), rhs_below, conj, cr) },
+            };
+            let diag = T::from_real(lhs[(j, j)].real());
+            acc[j] = acc[j] + beta * (diag * x + dot);
+        }
+    })
+}
+
+/// Computes the matrix-vector product `Op_lhs(lhs) * acc`, where `lhs` is a triangular matrix,
+/// and stores the result in `acc`.
+///
+/// `lhs_structure` indicates which part of `lhs` is accessed, and whether its diagonal is
+/// implicitly zero or one.
+/// `Op_lhs` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+/// `Conj::Yes`.
+///
+/// # Panics
+///
+/// Panics if `lhs` is not a square matrix, if `acc.nrows() != lhs.nrows()`, or if
+/// `lhs_structure` is `BlockStructure::Rectangular`.
+#[track_caller]
+pub fn trmv<T: ComplexField>(
+    acc: ColMut<'_, T>,
+    lhs: MatRef<'_, T>,
+    lhs_structure: BlockStructure,
+    conj_lhs: Conj,
+) {
+    let n = lhs.nrows();
+    fancy_assert!(lhs.ncols() == n);
+    fancy_assert!(acc.nrows() == n);
+    fancy_assert!(!lhs_structure.is_dense());
+
+    let lower = lhs_structure.is_lower();
+    let diag_kind = lhs_structure.diag_kind();
+
+    let mut acc = acc;
+    dispatch_conj!(conj_lhs, |cl| {
+        let mut update = |j: usize| {
+            let x = acc[j];
+            if lower {
+                let (_, below) = lhs.col(j).split_at(j + 1);
+                unsafe { axpy_kernel(acc.rb_mut().split_at(j + 1).1, x, below, cl) };
+            } else {
+                let (above, _) = lhs.col(j).split_at(j);
+                unsafe { axpy_kernel(acc.rb_mut().split_at(j).0, x, above, cl) };
+            }
+            acc[j] = match diag_kind {
+                DiagonalKind::Zero => T::zero(),
+                DiagonalKind::Unit => x,
+                DiagonalKind::Generic => cl(lhs[(j, j)]) * x,
+            };
+        };
+
+        // each element must be read before it is overwritten
+        if lower {
+            (0..n).rev().for_each(&mut update);
+        } else {
+            (0..n).for_each(&mut update);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{c64, mul::matmul, Mat, Parallelism};
+    use rand::random;
+
+    fn random_mat(m: usize, n: usize) -> Mat<c64> {
+        Mat::with_dims(|_, _| c64::new(random(), random()), m, n)
+    }
+
+    fn transposed(a: &Mat<c64>) -> Mat<c64> {
+        Mat::with_dims(|i, j| a[(j, i)], a.ncols(), a.nrows())
+    }
+
+    fn assert_close(lhs: MatRef<'_, c64>, rhs: MatRef<'_, c64>) {
+        fancy_assert!((lhs.nrows(), lhs.ncols()) == (rhs.nrows(), rhs.ncols()));
+        for j in 0..lhs.ncols() {
+            for i in 0..lhs.nrows() {
+                fancy_assert!((lhs[(i, j)] - rhs[(i, j)]).abs() < 1e-10);
+            }
+        }
+    }
+
+    const CONJ: [Conj; 2] = [Conj::No, Conj::Yes];
+
+    #[test]
+    fn test_dot() {
+        for n in [0, 1, 3, 4, 7, 64, 101] {
+            let lhs = random_mat(1, n);
+            let rhs = random_mat(n, 1);
+            // strided views
+            let lhs_t = random_mat(n, 1);
+            for conj_lhs in CONJ {
+                for conj_rhs in CONJ {
+                    let mut target = Mat::zeros(1, 1);
+                    for lhs in [lhs.as_ref(), lhs_t.as_ref().transpose()] {
+                        matmul(
+                            target.as_mut(),
+                            Conj::No,
+                            lhs,
+                            conj_lhs,
+                            rhs.as_ref(),
+                            conj_rhs,
+                            None,
+                            c64::one(),
+                            Parallelism::None,
+                        );
+                        let dot = dot(lhs.row(0), conj_lhs, rhs.as_ref().col(0), conj_rhs);
+                        fancy_assert!((dot - target[(0, 0)]).abs() < 1e-10);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_axpy_scal() {
+        for n in [0, 1, 7, 64] {
+            let src = random_mat(n, 1);
+            let dst = random_mat(n, 1);
+            let alpha = c64::new(random(), random());
+            for conj_src in CONJ {
+                let mut result = dst.clone();
+                axpy(result.as_mut().col(0), alpha, src.as_ref().col(0), conj_src);
+
+                let target = Mat::with_dims(
+                    |i, _| {
+                        let s = match conj_src {
+                            Conj::No => src[(i, 0)],
+                            Conj::Yes => src[(i, 0)].conj(),
+                        };
+                        dst[(i, 0)] + alpha * s
+                    },
+                    n,
+                    1,
+                );
+                assert_close(result.as_ref(), target.as_ref());
+
+                // row vector
+                let mut result = transposed(&dst);
+                axpy(
+                    result.as_mut().row(0).transpose(),
+                    alpha,
+                    src.as_ref().col(0),
+                    conj_src,
+                );
+                assert_close(result.as_ref().transpose(), target.as_ref());
+            }
+
+            let mut result = dst.clone();
+            scal(result.as_mut().col(0), alpha);
+            let target = Mat::with_dims(|i, _| alpha * dst[(i, 0)], n, 1);
+            assert_close(result.as_ref(), target.as_ref());
+        }
+    }
+
+    #[test]
+    fn test_ger() {
+        for (m, n) in [(0, 0), (1, 1), (4, 7), (13, 5)] {
+            let lhs = random_mat(m, 1);
+            let rhs = random_mat(1, n);
+            let alpha = c64::new(random(), random());
+            for conj_lhs in CONJ {
+                for conj_rhs in CONJ {
+                    let acc = random_mat(m, n);
+                    let mut target = acc.clone();
+                    matmul(
+                        target.as_mut(),
+                        Conj::No,
+                        lhs.as_ref(),
+                        conj_lhs,
+                        rhs.as_ref(),
+                        conj_rhs,
+                        Some(c64::one()),
+                        alpha,
+                        Parallelism::None,
+                    );
+
+                    let mut result = acc.clone();
+                    ger(
+                        result.as_mut(),
+                        alpha,
+                        lhs.as_ref().col(0),
+                        conj_lhs,
+                        rhs.as_ref().row(0),
+                        conj_rhs,
+                    );
+                    assert_close(result.as_ref(), target.as_ref());
+
+                    // row major destination
+                    let mut result = transposed(&acc);
+                    ger(
+                        result.as_mut().transpose(),
+                        alpha,
+                        lhs.as_ref().col(0),
+                        conj_lhs,
+                        rhs.as_ref().row(0),
+                        conj_rhs,
+                    );
+                    assert_close(result.as_ref().transpose(), target.as_ref());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_her_her2() {
+        for n in [0, 1, 4, 13] {
+            let x = random_mat(n, 1);
+            let y = random_mat(n, 1);
+            let acc = random_mat(n, n);
+            let alpha = c64::new(random(), random());
+            let alpha_real: f64 = random();
+
+            let mut result = acc.clone();
+            her(result.as_mut(), alpha_real, x.as_ref().col(0));
+            let mut result2 = acc.clone();
+            her2(
+                result2.as_mut(),
+                alpha,
+                x.as_ref().col(0),
+                y.as_ref().col(0),
+            );
+
+            for j in 0..n {
+                for i in 0..n {
+                    let mut target = acc[(i, j)] + (x[(i, 0)] * x[(j, 0)].conj()).scale(alpha_real);
+                    let mut target2 = acc[(i, j)]
+                        + alpha * x[(i, 0)] * y[(j, 0)].conj()
+                        + alpha.conj() * y[(i, 0)] * x[(j, 0)].conj();
+                    if i == j {
+                        target = c64::new(target.re, 0.0);
+                        target2 = c64::new(target2.re, 0.0);
+                    }
+                    if i < j {
+                        // the strictly upper triangular part is not accessed
+                        target = acc[(i, j)];
+                        target2 = acc[(i, j)];
+                    }
+                    fancy_assert!((result[(i, j)] - target).abs() < 1e-10);
+                    fancy_assert!((result2[(i, j)] - target2).abs() < 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gemv() {
+        for (m, n) in [(0, 0), (1, 1), (4, 7), (13, 5), (64, 64)] {
+            let lhs = random_mat(m, n);
+            let lhs_t = transposed(&lhs);
+            let rhs = random_mat(n, 1);
+            let acc = random_mat(m, 1);
+            let beta = c64::new(random(), random());
+            for alpha in [None, Some(c64::new(random(), random()))] {
+                for conj_lhs in CONJ {
+                    for conj_rhs in CONJ {
+                        let mut target = acc.clone();
+                        matmul(
+                            target.as_mut(),
+                            Conj::No,
+                            lhs.as_ref(),
+                            conj_lhs,
+                            rhs.as_ref(),
+                            conj_rhs,
+                            alpha,
+                            beta,
+                            Parallelism::None,
+                        );
+
+                        // column major and row major matrices
+                        for lhs in [lhs.as_ref(), lhs_t.as_ref().transpose()] {
+                            let mut result = acc.clone();
+                            gemv(
+                                result.as_mut().col(0),
+                                lhs,
+                                conj_lhs,
+                                rhs.as_ref().col(0),
+                                conj_rhs,
+                                alpha,
+                                beta,
+                            );
+                            assert_close(result.as_ref(), target.as_ref());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_symv() {
+        for n in [0, 1, 4, 13, 64] {
+            let lower = random_mat(n, n);
+            let full = Mat::with_dims(
+                |i, j| {
+                    if i > j {
+                        lower[(i, j)]
+                    } else if i < j {
+                        lower[(j, i)].conj()
+                    } else {
+                        c64::new(lower[(i, i)].re, 0.0)
+                    }
+                },
+                n,
+                n,
+            );
+            let rhs = random_mat(n, 1);
+            let acc = random_mat(n, 1);
+            let beta = c64::new(random(), random());
+            for alpha in [None, Some(c64::new(random(), random()))] {
+                for conj_lhs in CONJ {
+                    for conj_rhs in CONJ {
+                        let mut target = acc.clone();
+                        matmul(
+                            target.as_mut(),
+                            Conj::No,
+                            full.as_ref(),
+                            conj_lhs,
+                            rhs.as_ref(),
+                            conj_rhs,
+                            alpha,
+                            beta,
+                            Parallelism::None,
+                        );
+
+                        let mut result = acc.clone();
+                        symv(
+                            result.as_mut().col(0),
+                            lower.as_ref(),
+                            conj_lhs,
+                            rhs.as_ref().col(0),
+                            conj_rhs,
+                            alpha,
+                            beta,
+                        );
+                        assert_close(result.as_ref(), target.as_ref());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_trmv() {
+        use BlockStructure::*;
+        for n in [0, 1, 4, 13, 64] {
+            let lhs = random_mat(n, n);
+            let rhs = random_mat(n, 1);
+            for structure in [
+                TriangularLower,
+                StrictTriangularLower,
+                UnitTriangularLower,
+                TriangularUpper,
+                StrictTriangularUpper,
+                UnitTriangularUpper,
+            ] {
+                let tri = Mat::with_dims(
+                    |i, j| {
+                        let in_part = if structure.is_lower() { i > j } else { i < j };
+                        if in_part {
+                            lhs[(i, j)]
+                        } else if i == j {
+                            match structure.diag_kind() {
+                                DiagonalKind::Zero => c64::zero(),
+                                DiagonalKind::Unit => c64::one(),
+                                DiagonalKind::Generic => lhs[(i, i)],
+                            }
+                        } else {
+                            c64::zero()
+                        }
+                    },
+                    n,
+                    n,
+                );
+                for conj_lhs in CONJ {
+                    let mut target = Mat::zeros(n, 1);
+                    matmul(
+                        target.as_mut(),
+                        Conj::No,
+                        tri.as_ref(),
+                        conj_lhs,
+                        rhs.as_ref(),
+                        Conj::No,
+                        None,
+                        c64::one(),
+                        Parallelism::None,
+                    );
+
+                    let mut result = rhs.clone();
+                    trmv(result.as_mut().col(0), lhs.as_ref(), structure, conj_lhs);
+                    assert_close(result.as_ref(), target.as_ref());
+                }
+            }
+        }
+    }
+}
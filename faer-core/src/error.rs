@@ -0,0 +1,203 @@
+//! Error type returned by the fallible (`try_*`) variants of the decomposition functions.
+//!
+//! The panicking functions check their preconditions with assertions. Their `try_*` counterparts
+//! perform the same checks up front, and report the first one that fails as a [`FaerError`]
+//! instead. Some of them also report the numerical breakdowns of the factorization, such as a zero
+//! pivot.
+
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+
+/// Error returned by the fallible variants of the decomposition functions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FaerError {
+    /// An operand doesn't have the dimensions required by the operation.
+    DimensionMismatch {
+        /// Name of the offending operand.
+        operand: &'static str,
+        /// Expected `(nrows, ncols)` of the operand.
+        expected: (usize, usize),
+        /// Actual `(nrows, ncols)` of the operand.
+        found: (usize, usize),
+    },
+    /// An operand doesn't have the memory layout required by the operation, e.g., a unit row
+    /// stride.
+    StrideRequirement {
+        /// Name of the offending operand.
+        operand: &'static str,
+    },
+    /// The provided workspace is too small for the operation.
+    InsufficientWorkspace {
+        /// Required workspace.
+        required: StackReq,
+        /// Size of the provided workspace, in bytes.
+        available_bytes: usize,
+    },
+    /// The size of the required workspace overflows `usize`.
+    WorkspaceSizeOverflow,
+    /// The matrix is singular, and its factorization has a zero pivot at the given index.
+    Singular {
+        /// Index of the first zero pivot.
+        index: usize,
+    },
+    /// The matrix is not positive definite, and its Cholesky factorization breaks down at the
+    /// given column.
+    NotPositiveDefinite {
+        /// Index of the first column whose pivot is not positive.
+        index: usize,
+    },
+    /// An iterative algorithm failed to converge. See [`ConvergenceError`].
+    NoConvergence {
+        /// Index at which the iterations failed to converge.
+        index: usize,
+    },
+}
+
+impl core::fmt::Display for FaerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            FaerError::DimensionMismatch {
+                operand,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{operand}` has dimensions {}x{}, expected {}x{}",
+                found.0, found.1, expected.0, expected.1,
+            ),
+            FaerError::StrideRequirement { operand } => {
+                write!(f, "`{operand}` doesn't have the required memory layout")
+            }
+            FaerError::InsufficientWorkspace {
+                required,
+                available_bytes,
+            } => write!(
+                f,
+                "insufficient workspace: {} bytes with alignment {} are required, {} bytes were provided",
+                required.size_bytes(),
+                required.align_bytes(),
+                available_bytes,
+            ),
+            FaerError::WorkspaceSizeOverflow => write!(f, "workspace size overflow"),
+            FaerError::Singular { index } => {
+                write!(f, "the matrix is singular, with a zero pivot at index {index}")
+            }
+            FaerError::NotPositiveDefinite { index } => write!(
+                f,
+                "the matrix is not positive definite, the factorization breaks down at column {index}"
+            ),
+            FaerError::NoConvergence { index } => {
+                write!(f, "the iterations failed to converge at index {index}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FaerError {}
+
+/// Error returned when an iterative algorithm, such as the QR algorithm used by the eigenvalue and
+/// singular value decompositions, fails to converge within its maximum number of iterations.
+///
+/// This usually happens when the input contains non-finite values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ConvergenceError {
+    /// Index of the eigenvalue or singular value that failed to converge.
+    pub index: usize,
+}
+
+impl core::fmt::Display for ConvergenceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "the iterations failed to converge at index {}",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConvergenceError {}
+
+impl From<ConvergenceError> for FaerError {
+    #[inline]
+    fn from(error: ConvergenceError) -> Self {
+        FaerError::NoConvergence { index: error.index }
+    }
+}
+
+impl From<SizeOverflow> for FaerError {
+    #[inline]
+    fn from(_: SizeOverflow) -> Self {
+        FaerError::WorkspaceSizeOverflow
+    }
+}
+
+/// Checks that `stack` can hold the workspace described by `req`.
+#[inline]
+pub fn check_workspace(
+    stack: &DynStack<'_>,
+    req: Result<StackReq, SizeOverflow>,
+) -> Result<(), FaerError> {
+    let required = req?;
+    if stack.can_hold(required) {
+        Ok(())
+    } else {
+        Err(FaerError::InsufficientWorkspace {
+            required,
+            available_bytes: stack.len_bytes(),
+        })
+    }
+}
+
+/// Checks that the operand named `operand` with dimensions `found` has the dimensions `expected`.
+#[inline]
+pub fn check_dimensions(
+    operand: &'static str,
+    found: (usize, usize),
+    expected: (usize, usize),
+) -> Result<(), FaerError> {
+    if found == expected {
+        Ok(())
+    } else {
+        Err(FaerError::DimensionMismatch {
+            operand,
+            expected,
+            found,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+
+    #[test]
+    fn test_check_workspace() {
+        let req = StackReq::try_new::<f64>(16);
+        let mut small = GlobalMemBuffer::new(StackReq::new::<f64>(4));
+        let mut large = GlobalMemBuffer::new(req.unwrap());
+
+        fancy_assert!(check_workspace(&DynStack::new(&mut large), req) == Ok(()));
+        fancy_assert!(matches!(
+            check_workspace(&DynStack::new(&mut small), req),
+            Err(FaerError::InsufficientWorkspace { .. })
+        ));
+        fancy_assert!(
+            check_workspace(
+                &DynStack::new(&mut large),
+                StackReq::try_new::<f64>(usize::MAX)
+            ) == Err(FaerError::WorkspaceSizeOverflow)
+        );
+        fancy_assert!(
+            check_dimensions("rhs", (3, 2), (4, 2))
+                == Err(FaerError::DimensionMismatch {
+                    operand: "rhs",
+                    expected: (4, 2),
+                    found: (3, 2),
+                })
+        );
+    }
+}
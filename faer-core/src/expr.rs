@@ -0,0 +1,554 @@
+//! Lazy element-wise matrix expressions.
+//!
+//! Arithmetic on [`Expr`] values doesn't compute anything, it only builds an expression tree.
+//! The tree is evaluated when it is written into a destination matrix, in a single pass over the
+//! elements, without allocating intermediate matrices. Expressions implement
+//! [`CwiseMat`](crate::zip::CwiseMat), so they can also be zipped with other matrices.
+//!
+//! # Example
+//!
+//! ```
+//! use faer_core::{mat, Mat};
+//!
+//! let a = mat![[1.0, 2.0], [3.0, 4.0]];
+//! let b = mat![[5.0, 6.0], [7.0, 8.0]];
+//! let c = mat![[1.0, 1.0], [1.0, 1.0]];
+//!
+//! // single fused pass, no temporaries
+//! let mut dst = Mat::<f64>::zeros(2, 2);
+//! ((a.as_ref().lazy() + b.as_ref() - c.as_ref()) * 2.0).eval_to(dst.as_mut());
+//!
+//! assert_eq!(dst.as_ref(), mat![[10.0, 14.0], [18.0, 22.0]].as_ref());
+//! ```
+
+use crate::{
+    c32, c64,
+    zip::{CwiseMat, MatUninit},
+    ComplexField, Mat, MatMut, MatRef,
+};
+use assert2::assert as fancy_assert;
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// Node of a lazy element-wise expression tree.
+///
+/// # Safety
+///
+/// Implementors must report dimensions and layouts that are consistent with the matrices they
+/// read from, since the accessors are called without bounds checks.
+pub unsafe trait LazyMat {
+    type Elem: ComplexField;
+
+    fn nrows(&self) -> usize;
+    fn ncols(&self) -> usize;
+    fn is_col_major(&self) -> bool;
+    fn is_row_major(&self) -> bool;
+    fn transpose(self) -> Self;
+
+    /// Returns the element at position `(i, j)`.
+    ///
+    /// # Safety
+    ///
+    /// `i` and `j` must be in bounds.
+    unsafe fn get_unchecked(&self, i: usize, j: usize) -> Self::Elem;
+
+    /// Returns the element at position `(i, j)`, assuming all the leaves are column major.
+    ///
+    /// # Safety
+    ///
+    /// `i` and `j` must be in bounds, and [`LazyMat::is_col_major`] must return `true`.
+    unsafe fn get_col_major_unchecked(&self, i: usize, j: usize) -> Self::Elem;
+
+    /// Returns the element at position `(i, j)`, assuming all the leaves are row major.
+    ///
+    /// # Safety
+    ///
+    /// `i` and `j` must be in bounds, and [`LazyMat::is_row_major`] must return `true`.
+    unsafe fn get_row_major_unchecked(&self, i: usize, j: usize) -> Self::Elem;
+}
+
+/// Lazy element-wise matrix expression. See the [module level documentation](self).
+#[derive(Copy, Clone, Debug)]
+pub struct Expr<E> {
+    inner: E,
+}
+
+/// Leaf of an expression, reading the elements of a matrix view.
+#[derive(Copy, Clone, Debug)]
+pub struct Leaf<'a, T: 'static> {
+    mat: MatRef<'a, T>,
+}
+
+/// Element-wise sum of two expressions.
+#[derive(Copy, Clone, Debug)]
+pub struct Sum<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+/// Element-wise difference of two expressions.
+#[derive(Copy, Clone, Debug)]
+pub struct Difference<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+/// Element-wise negation of an expression.
+#[derive(Copy, Clone, Debug)]
+pub struct Negation<E> {
+    inner: E,
+}
+
+/// Expression scaled by a constant factor.
+#[derive(Copy, Clone, Debug)]
+pub struct Scaled<E: LazyMat> {
+    inner: E,
+    factor: E::Elem,
+}
+
+unsafe impl<'a, T: ComplexField> LazyMat for Leaf<'a, T> {
+    type Elem = T;
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.mat.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.mat.ncols()
+    }
+    #[inline]
+    fn is_col_major(&self) -> bool {
+        self.mat.row_stride() == 1
+    }
+    #[inline]
+    fn is_row_major(&self) -> bool {
+        self.mat.col_stride() == 1
+    }
+    #[inline]
+    fn transpose(self) -> Self {
+        Leaf {
+            mat: self.mat.transpose(),
+        }
+    }
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, i: usize, j: usize) -> T {
+        *self.mat.get_unchecked(i, j)
+    }
+    #[inline(always)]
+    unsafe fn get_col_major_unchecked(&self, i: usize, j: usize) -> T {
+        *self.mat.ptr_in_bounds_at_unchecked(0, j).add(i)
+    }
+    #[inline(always)]
+    unsafe fn get_row_major_unchecked(&self, i: usize, j: usize) -> T {
+        *self.mat.ptr_in_bounds_at_unchecked(i, 0).add(j)
+    }
+}
+
+macro_rules! impl_binary_node {
+    ($node: ident, $op: tt) => {
+        unsafe impl<L: LazyMat, R: LazyMat<Elem = L::Elem>> LazyMat for $node<L, R> {
+            type Elem = L::Elem;
+
+            #[inline]
+            fn nrows(&self) -> usize {
+                self.lhs.nrows()
+            }
+            #[inline]
+            fn ncols(&self) -> usize {
+                self.lhs.ncols()
+            }
+            #[inline]
+            fn is_col_major(&self) -> bool {
+                self.lhs.is_col_major() && self.rhs.is_col_major()
+            }
+            #[inline]
+            fn is_row_major(&self) -> bool {
+                self.lhs.is_row_major() && self.rhs.is_row_major()
+            }
+            #[inline]
+            fn transpose(self) -> Self {
+                $node {
+                    lhs: self.lhs.transpose(),
+                    rhs: self.rhs.transpose(),
+                }
+            }
+            #[inline(always)]
+            unsafe fn get_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+                self.lhs.get_unchecked(i, j) $op self.rhs.get_unchecked(i, j)
+            }
+            #[inline(always)]
+            unsafe fn get_col_major_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+                self.lhs.get_col_major_unchecked(i, j) $op self.rhs.get_col_major_unchecked(i, j)
+            }
+            #[inline(always)]
+            unsafe fn get_row_major_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+                self.lhs.get_row_major_unchecked(i, j) $op self.rhs.get_row_major_unchecked(i, j)
+            }
+        }
+    };
+}
+
+impl_binary_node!(Sum, +);
+impl_binary_node!(Difference, -);
+
+unsafe impl<E: LazyMat> LazyMat for Negation<E> {
+    type Elem = E::Elem;
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.inner.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.inner.ncols()
+    }
+    #[inline]
+    fn is_col_major(&self) -> bool {
+        self.inner.is_col_major()
+    }
+    #[inline]
+    fn is_row_major(&self) -> bool {
+        self.inner.is_row_major()
+    }
+    #[inline]
+    fn transpose(self) -> Self {
+        Negation {
+            inner: self.inner.transpose(),
+        }
+    }
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+        -self.inner.get_unchecked(i, j)
+    }
+    #[inline(always)]
+    unsafe fn get_col_major_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+        -self.inner.get_col_major_unchecked(i, j)
+    }
+    #[inline(always)]
+    unsafe fn get_row_major_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+        -self.inner.get_row_major_unchecked(i, j)
+    }
+}
+
+unsafe impl<E: LazyMat> LazyMat for Scaled<E> {
+    type Elem = E::Elem;
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.inner.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.inner.ncols()
+    }
+    #[inline]
+    fn is_col_major(&self) -> bool {
+        self.inner.is_col_major()
+    }
+    #[inline]
+    fn is_row_major(&self) -> bool {
+        self.inner.is_row_major()
+    }
+    #[inline]
+    fn transpose(self) -> Self {
+        Scaled {
+            inner: self.inner.transpose(),
+            factor: self.factor,
+        }
+    }
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+        self.factor * self.inner.get_unchecked(i, j)
+    }
+    #[inline(always)]
+    unsafe fn get_col_major_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+        self.factor * self.inner.get_col_major_unchecked(i, j)
+    }
+    #[inline(always)]
+    unsafe fn get_row_major_unchecked(&self, i: usize, j: usize) -> Self::Elem {
+        self.factor * self.inner.get_row_major_unchecked(i, j)
+    }
+}
+
+impl<E> crate::seal::Seal for Expr<E> {}
+
+impl<'short, E: LazyMat> CwiseMat<'short> for Expr<E> {
+    type Item = E::Elem;
+
+    #[inline]
+    fn transpose(self) -> Self {
+        Expr {
+            inner: self.inner.transpose(),
+        }
+    }
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.inner.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.inner.ncols()
+    }
+    #[inline]
+    fn is_col_major(&self) -> bool {
+        self.inner.is_col_major()
+    }
+    #[inline]
+    fn is_row_major(&self) -> bool {
+        self.inner.is_row_major()
+    }
+    #[inline(always)]
+    unsafe fn get_unchecked(&'short mut self, i: usize, j: usize) -> Self::Item {
+        self.inner.get_unchecked(i, j)
+    }
+    #[inline(always)]
+    unsafe fn get_col_major_unchecked(&'short mut self, i: usize, j: usize) -> Self::Item {
+        self.inner.get_col_major_unchecked(i, j)
+    }
+    #[inline(always)]
+    unsafe fn get_row_major_unchecked(&'short mut self, i: usize, j: usize) -> Self::Item {
+        self.inner.get_row_major_unchecked(i, j)
+    }
+}
+
+impl<'a, T: ComplexField> MatRef<'a, T> {
+    /// Returns a lazy expression reading the elements of `self`. See the [`expr`](crate::expr)
+    /// module.
+    #[inline]
+    pub fn lazy(self) -> Expr<Leaf<'a, T>> {
+        Expr {
+            inner: Leaf { mat: self },
+        }
+    }
+}
+
+impl<E: LazyMat> Expr<E> {
+    /// Returns the number of rows of the expression.
+    #[inline]
+    pub fn nrows(&self) -> usize {
+        self.inner.nrows()
+    }
+
+    /// Returns the number of columns of the expression.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.inner.ncols()
+    }
+
+    /// Evaluates the expression and stores the result in `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` doesn't have the same dimensions as the expression.
+    #[track_caller]
+    pub fn eval_to(self, dst: MatMut<'_, E::Elem>) {
+        let zip = dst.cwise().zip(self);
+        pulp::Arch::new().dispatch(
+            #[inline(always)]
+            || zip.for_each(|dst, src| *dst = src),
+        );
+    }
+
+    /// Evaluates the expression and adds the result to `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` doesn't have the same dimensions as the expression.
+    #[track_caller]
+    pub fn add_to(self, dst: MatMut<'_, E::Elem>) {
+        let zip = dst.cwise().zip(self);
+        pulp::Arch::new().dispatch(
+            #[inline(always)]
+            || zip.for_each(|dst, src| *dst = *dst + src),
+        );
+    }
+
+    /// Evaluates the expression into a newly allocated matrix.
+    pub fn eval(self) -> Mat<E::Elem> {
+        let nrows = self.nrows();
+        let ncols = self.ncols();
+        let mut mat = Mat::<E::Elem>::with_capacity(nrows, ncols);
+        // SAFETY: the values are written before they are read
+        unsafe { mat.set_dims(nrows, ncols) };
+        let zip = MatUninit(mat.as_mut()).cwise().zip(self);
+        pulp::Arch::new().dispatch(
+            #[inline(always)]
+            || zip.for_each(|dst, src| unsafe { dst.write(src) }),
+        );
+        mat
+    }
+}
+
+impl<L: LazyMat, R: LazyMat<Elem = L::Elem>> Add<Expr<R>> for Expr<L> {
+    type Output = Expr<Sum<L, R>>;
+
+    #[track_caller]
+    #[inline]
+    fn add(self, rhs: Expr<R>) -> Self::Output {
+        fancy_assert!((self.nrows(), self.ncols()) == (rhs.nrows(), rhs.ncols()));
+        Expr {
+            inner: Sum {
+                lhs: self.inner,
+                rhs: rhs.inner,
+            },
+        }
+    }
+}
+
+impl<L: LazyMat, R: LazyMat<Elem = L::Elem>> Sub<Expr<R>> for Expr<L> {
+    type Output = Expr<Difference<L, R>>;
+
+    #[track_caller]
+    #[inline]
+    fn sub(self, rhs: Expr<R>) -> Self::Output {
+        fancy_assert!((self.nrows(), self.ncols()) == (rhs.nrows(), rhs.ncols()));
+        Expr {
+            inner: Difference {
+                lhs: self.inner,
+                rhs: rhs.inner,
+            },
+        }
+    }
+}
+
+impl<'a, L: LazyMat> Add<MatRef<'a, L::Elem>> for Expr<L> {
+    type Output = Expr<Sum<L, Leaf<'a, L::Elem>>>;
+
+    #[track_caller]
+    #[inline]
+    fn add(self, rhs: MatRef<'a, L::Elem>) -> Self::Output {
+        self + rhs.lazy()
+    }
+}
+
+impl<'a, L: LazyMat> Sub<MatRef<'a, L::Elem>> for Expr<L> {
+    type Output = Expr<Difference<L, Leaf<'a, L::Elem>>>;
+
+    #[track_caller]
+    #[inline]
+    fn sub(self, rhs: MatRef<'a, L::Elem>) -> Self::Output {
+        self - rhs.lazy()
+    }
+}
+
+impl<E: LazyMat> Neg for Expr<E> {
+    type Output = Expr<Negation<E>>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Expr {
+            inner: Negation { inner: self.inner },
+        }
+    }
+}
+
+impl<E: LazyMat> Mul<E::Elem> for Expr<E> {
+    type Output = Expr<Scaled<E>>;
+
+    #[inline]
+    fn mul(self, rhs: E::Elem) -> Self::Output {
+        Expr {
+            inner: Scaled {
+                inner: self.inner,
+                factor: rhs,
+            },
+        }
+    }
+}
+
+// scalars on the left hand side can only be supported for concrete types
+macro_rules! impl_scalar_lhs_mul {
+    ($($ty: ty),*) => {
+        $(
+            impl<E: LazyMat<Elem = $ty>> Mul<Expr<E>> for $ty {
+                type Output = Expr<Scaled<E>>;
+
+                #[inline]
+                fn mul(self, rhs: Expr<E>) -> Self::Output {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_lhs_mul!(f32, f64, c32, c64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::random;
+
+    #[test]
+    fn test_fused() {
+        for (m, n) in [(0, 0), (1, 1), (3, 5), (17, 4)] {
+            let a = Mat::with_dims(|_, _| c64::new(random(), random()), m, n);
+            let b = Mat::with_dims(|_, _| c64::new(random(), random()), m, n);
+            let c = Mat::with_dims(|_, _| c64::new(random(), random()), m, n);
+            let alpha = c64::new(random(), random());
+
+            let target = Mat::with_dims(
+                |i, j| alpha * (a[(i, j)] + b[(i, j)] - c[(i, j)]) - a[(i, j)],
+                m,
+                n,
+            );
+            let expr = alpha * (a.as_ref().lazy() + b.as_ref() - c.as_ref()) + -a.as_ref().lazy();
+
+            let result = expr.eval();
+            fancy_assert!((result.nrows(), result.ncols()) == (m, n));
+
+            let mut dst = Mat::zeros(m, n);
+            expr.eval_to(dst.as_mut());
+
+            let mut acc = a.clone();
+            expr.add_to(acc.as_mut());
+
+            for j in 0..n {
+                for i in 0..m {
+                    fancy_assert!((result[(i, j)] - target[(i, j)]).abs() < 1e-12);
+                    fancy_assert!((dst[(i, j)] - target[(i, j)]).abs() < 1e-12);
+                    fancy_assert!((acc[(i, j)] - a[(i, j)] - target[(i, j)]).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_layouts() {
+        let a = Mat::with_dims(|i, j| (i + 10 * j) as f64, 4, 3);
+        let b = Mat::with_dims(|i, j| (i * j) as f64, 3, 4);
+
+        // column major and row major operands mixed together
+        let expr = a.as_ref().lazy() - b.as_ref().transpose().lazy() * 2.0;
+        let mut dst = Mat::zeros(4, 3);
+        expr.eval_to(dst.as_mut());
+        let mut dst_t = Mat::zeros(3, 4);
+        expr.eval_to(dst_t.as_mut().transpose());
+
+        for j in 0..3 {
+            for i in 0..4 {
+                let target = a[(i, j)] - 2.0 * b[(j, i)];
+                fancy_assert!(dst[(i, j)] == target);
+                fancy_assert!(dst_t[(j, i)] == target);
+            }
+        }
+
+        // expressions can be zipped with other matrices
+        let mut sum = 0.0;
+        a.as_ref()
+            .cwise()
+            .zip(a.as_ref().lazy() * 3.0)
+            .for_each(|a, e| sum += e - *a);
+        let mut target = 0.0;
+        a.as_ref().cwise().for_each(|a| target += 2.0 * *a);
+        fancy_assert!(sum == target);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dimension_mismatch() {
+        let a = Mat::<f64>::zeros(2, 3);
+        let b = Mat::<f64>::zeros(3, 2);
+        let _ = a.as_ref().lazy() + b.as_ref();
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
     mul::{matmul, triangular},
-    temp_mat_uninit, ColMut, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
+    temp_mat_req, temp_mat_uninit, ColMut, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
 };
 
 use assert2::assert as fancy_assert;
-use dyn_stack::DynStack;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
 use reborrow::*;
 
 pub fn make_householder_in_place<T: ComplexField>(
@@ -12,8 +12,18 @@ pub fn make_householder_in_place<T: ComplexField>(
     head: T,
     tail_squared_norm: T::Real,
 ) -> (T, T) {
-    let norm = ((head * head.conj()).real() + tail_squared_norm).sqrt();
-    let sign = head / (head * head.conj()).sqrt();
+    let head_squared_norm = (head * head.conj()).real();
+    let norm = (head_squared_norm + tail_squared_norm).sqrt();
+    if norm == T::Real::zero() {
+        // the column is already zero, so the reflection is the identity
+        return (T::zero(), T::zero());
+    }
+
+    let sign = if head_squared_norm == T::Real::zero() {
+        T::one()
+    } else {
+        head / T::from_real(head_squared_norm.sqrt())
+    };
 
     let signed_norm = sign * T::from_real(norm);
     let head_with_beta = head + signed_norm;
@@ -181,3 +191,263 @@ pub fn apply_block_househodler_on_the_left<T: ComplexField>(
         parallelism,
     )
 }
+
+/// Returns the default size of the blocks in which householder reflections are applied to an
+/// `m×n` matrix.
+pub fn default_blocksize(m: usize, n: usize) -> usize {
+    let prod = m * n;
+
+    if prod > 8192 * 8192 {
+        128
+    } else if prod > 2048 * 2048 {
+        64
+    } else if prod > 1024 * 1024 {
+        32
+    } else if prod > 512 * 512 {
+        16
+    } else {
+        8
+    }
+}
+
+/// Computes the size and alignment of required workspace for computing the upper triangular
+/// factor of a block of householder reflections.
+pub fn make_householder_factor_unblocked_req<T: 'static>(
+    size: usize,
+) -> Result<StackReq, SizeOverflow> {
+    temp_mat_req::<T>(size, 1)
+}
+
+/// Computes the upper triangular factor $T$ of the block of householder reflections whose
+/// essential parts are stored below the diagonal of `matrix`, such that
+/// $$H_0 H_1 \dots H_{k - 1} = I - VTV^*.$$
+///
+/// On entry, the diagonal of `householder_factor` must contain the householder coefficients.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` is not a square matrix of dimension
+///   `min(matrix.nrows(), matrix.ncols())`.
+/// - Panics if `householder_factor` is not stored in column major order.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn make_householder_factor_unblocked<T: ComplexField>(
+    householder_factor: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    stack: DynStack<'_>,
+) {
+    let mut householder_factor = householder_factor;
+    let mut stack = stack;
+
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = m.min(n);
+
+    fancy_assert!(householder_factor.nrows() == size);
+    fancy_assert!(householder_factor.ncols() == size);
+    fancy_assert!(householder_factor.col_stride() == 1);
+
+    for i in (0..size).rev() {
+        let rs = m - i - 1;
+        let rt = size - i - 1;
+
+        if rt > 0 {
+            let factor = -*householder_factor.rb().get(i, i);
+
+            let mut tail_row = householder_factor.rb_mut().row(i).split_at(size - rt).1;
+
+            use triangular::BlockStructure::*;
+
+            let mut dst = tail_row.rb_mut().as_2d();
+            let lhs = matrix.col(i).split_at(m - rs).1.transpose().as_2d();
+
+            let rhs = matrix.submatrix(m - rs, n - rt, rs, rt);
+            triangular::matmul(
+                dst.rb_mut(),
+                Rectangular,
+                Conj::No,
+                lhs.split_at(0, rt).2,
+                Rectangular,
+                Conj::Yes,
+                rhs.split_at(rt, 0).1,
+                UnitTriangularLower,
+                Conj::No,
+                None,
+                factor,
+                Parallelism::None,
+            );
+            matmul(
+                dst.rb_mut(),
+                Conj::No,
+                lhs.split_at(0, rt).3,
+                Conj::Yes,
+                rhs.split_at(rt, 0).3,
+                Conj::No,
+                Some(T::one()),
+                factor,
+                Parallelism::None,
+            );
+
+            temp_mat_uninit! {
+                let (mut tmp, _) = unsafe { temp_mat_uninit::<T>(rt, 1, stack.rb_mut()) };
+            }
+
+            triangular::matmul(
+                tmp.rb_mut().transpose(),
+                Rectangular,
+                Conj::No,
+                householder_factor.rb().submatrix(i, size - rt, 1, rt),
+                Rectangular,
+                Conj::No,
+                householder_factor
+                    .rb()
+                    .submatrix(size - rt, size - rt, rt, rt),
+                TriangularUpper,
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+            householder_factor
+                .rb_mut()
+                .submatrix(i, size - rt, 1, rt)
+                .row(0)
+                .cwise()
+                .zip(tmp.transpose().row(0))
+                .for_each(|a, b| *a = *b);
+        }
+    }
+}
+
+/// Computes the size and alignment of required workspace for applying a sequence of householder
+/// reflections.
+pub fn apply_householder_sequence_on_the_left_req<T: 'static>(
+    householder_nrows: usize,
+    householder_ncols: usize,
+    rhs_ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    let bs = default_blocksize(householder_nrows, householder_ncols)
+        .min(householder_nrows.min(householder_ncols));
+    StackReq::try_all_of([
+        temp_mat_req::<T>(bs, bs)?,
+        StackReq::try_any_of([
+            make_householder_factor_unblocked_req::<T>(bs)?,
+            temp_mat_req::<T>(bs, rhs_ncols)?.try_and(temp_mat_req::<T>(bs, rhs_ncols)?)?,
+        ])?,
+    ])
+}
+
+/// Applies the sequence of householder reflections
+/// $Q = H_0 H_1 \dots H_{k - 1}$, whose essential parts are stored below the diagonal of
+/// `householder_basis`, to `matrix`, in blocks.
+///
+/// If `forward` is `true`, the matrix is multiplied by $Q$, otherwise it is multiplied by $Q^*$.
+pub fn apply_householder_sequence_on_the_left<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_basis: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    forward: bool,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let mut matrix = matrix;
+    let mut stack = stack;
+
+    let m = householder_basis.nrows();
+    let n = householder_basis.ncols();
+    let size = m.min(n);
+    let k_cols = matrix.ncols();
+
+    fancy_assert!(matrix.nrows() == m);
+    fancy_assert!(householder_factor.nrows() == size);
+
+    if size == 0 {
+        return;
+    }
+
+    let blocksize = default_blocksize(m, n).min(size);
+
+    // Q = H_0 × H_1 × ... × H_{size - 1}
+    // so Q is applied starting from the last block, and Q^* starting from the first one
+    let mut block_starts = (0..size).step_by(blocksize);
+    while let Some(k) = if forward {
+        block_starts.next_back()
+    } else {
+        block_starts.next()
+    } {
+        let bs = blocksize.min(size - k);
+        let basis = householder_basis.submatrix(k, k, m - k, bs);
+
+        temp_mat_uninit! {
+            let (t, mut stack) = unsafe { temp_mat_uninit::<T>(bs, bs, stack.rb_mut()) };
+        }
+        let mut t = t.transpose();
+        for i in 0..bs {
+            t[(i, i)] = householder_factor[k + i];
+        }
+        make_householder_factor_unblocked(t.rb_mut(), basis, stack.rb_mut());
+
+        apply_block_househodler_on_the_left(
+            matrix.rb_mut().submatrix(k, 0, m - k, k_cols),
+            basis,
+            t.rb(),
+            forward,
+            parallelism,
+            stack,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{c64, Mat};
+
+    /// Computes the householder reflection of `x`, and returns `(tau, beta, H x)`.
+    fn reflect(x: &[c64]) -> (c64, c64, Vec<c64>) {
+        let n = x.len();
+        let mut essential = Mat::with_dims(|i, _| x[i + 1], n - 1, 1);
+        let tail_squared_norm = x[1..].iter().map(|e| e.norm_sqr()).sum::<f64>();
+        let (tau, beta) =
+            make_householder_in_place(essential.as_mut().col(0), x[0], tail_squared_norm);
+
+        // H = I - tau v v^*, with v = [1, essential]
+        let v = (0..n)
+            .map(|i| {
+                if i == 0 {
+                    c64::one()
+                } else {
+                    essential[(i - 1, 0)]
+                }
+            })
+            .collect::<Vec<_>>();
+        let dot = (0..n).fold(c64::zero(), |acc, i| acc + v[i].conj() * x[i]);
+        let hx = (0..n).map(|i| x[i] - tau * v[i] * dot).collect();
+        (tau, beta, hx)
+    }
+
+    #[test]
+    fn test_householder_zero_head() {
+        let x = [c64::zero(), c64::new(3.0, 0.0), c64::new(0.0, 4.0)];
+        let (tau, beta, hx) = reflect(&x);
+
+        fancy_assert!(tau.is_finite());
+        fancy_assert!((beta.norm() - 5.0).abs() < 1e-12);
+        fancy_assert!((hx[0] - beta).norm() < 1e-12);
+        for e in &hx[1..] {
+            fancy_assert!(e.norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_householder_zero_column() {
+        let x = [c64::zero(); 3];
+        let (tau, beta, hx) = reflect(&x);
+
+        fancy_assert!(tau == c64::zero());
+        fancy_assert!(beta == c64::zero());
+        for e in &hx {
+            fancy_assert!(*e == c64::zero());
+        }
+    }
+}
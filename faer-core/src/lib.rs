@@ -17,7 +17,7 @@ use core::{
     fmt::Debug,
     marker::PhantomData,
     mem::{size_of, MaybeUninit},
-    ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub},
+    ops::{Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
     ptr::NonNull,
 };
 use dyn_stack::{SizeOverflow, StackReq};
@@ -34,8 +34,13 @@ use reborrow::*;
 
 extern crate alloc;
 
+pub mod blas;
+pub mod error;
+pub mod expr;
 pub mod inverse;
+pub mod mixed_precision;
 pub mod mul;
+pub mod norm;
 pub mod solve;
 pub mod zip;
 
@@ -181,9 +186,212 @@ impl Mul<I> for f64 {
 }
 
 /// Trait that describes a real number field.
-pub trait RealField: ComplexField<Real = Self> + PartialOrd {}
+pub trait RealField: ComplexField<Real = Self> + PartialOrd {
+    /// Returns the difference between `1.0` and the next larger representable number.
+    ///
+    /// The default implementation computes it by repeated halving, and should be overridden by
+    /// types that can provide it directly.
+    fn epsilon() -> Self {
+        let one = Self::one();
+        let half = (one + one).inv();
+        let mut epsilon = one;
+        while one + epsilon * half != one {
+            epsilon = epsilon * half;
+        }
+        epsilon
+    }
+
+    /// Returns the smallest positive normal number.
+    ///
+    /// The default implementation computes it by repeated halving, stopping at the first power
+    /// of two that loses precision, and should be overridden by types that can provide it
+    /// directly.
+    fn min_positive() -> Self {
+        let one = Self::one();
+        let half = (one + one).inv();
+        let one_plus_epsilon = one + Self::epsilon();
+        let mut min = one;
+        loop {
+            let next = min * half;
+            // a subnormal power of two can't be incremented by a relative epsilon
+            if next * one_plus_epsilon == next {
+                break min;
+            }
+            min = next;
+        }
+    }
+
+    /// Computes $\sqrt{a^2 + b^2}$ without undue overflow or underflow.
+    #[inline]
+    fn hypot(self, other: Self) -> Self {
+        let a = self.abs();
+        let b = other.abs();
+        let (max, min) = if a > b { (a, b) } else { (b, a) };
+        if max == Self::zero() {
+            Self::zero()
+        } else {
+            let ratio = min / max;
+            max * (Self::one() + ratio * ratio).sqrt()
+        }
+    }
+
+    /// Returns `true` if the input is neither infinite nor NaN.
+    #[inline]
+    fn is_finite(self) -> bool {
+        self * Self::zero() == Self::zero()
+    }
+
+    /// Returns the natural logarithm of the input.
+    ///
+    /// The default implementation reduces the input to the range $[1, 2)$ by powers of two and
+    /// sums the series of $2 \operatorname{atanh}\left(\frac{x - 1}{x + 1}\right)$, and should be
+    /// overridden by types that can provide it directly.
+    fn ln(self) -> Self {
+        let zero = Self::zero();
+        let one = Self::one();
+        let two = one + one;
+        let half = two.inv();
+
+        let inf = one / zero;
+        if self == zero {
+            return -inf;
+        }
+        if self < zero {
+            // 0 × inf is NaN
+            return zero * inf;
+        }
+        if !self.is_finite() {
+            // ln(inf) is inf, and ln(NaN) is NaN
+            return self;
+        }
+
+        // ln(x) = 2 atanh((x - 1) / (x + 1)) = 2 (z + z^3 / 3 + z^5 / 5 + ...)
+        let series = |x: Self| {
+            let z = (x - one) / (x + one);
+            let z2 = z * z;
+            let mut term = z;
+            let mut k = one;
+            let mut sum = zero;
+            loop {
+                let next = sum + term / k;
+                if next == sum {
+                    break;
+                }
+                sum = next;
+                term = term * z2;
+                k = k + two;
+            }
+            sum + sum
+        };
+
+        // x = mantissa × 2^exponent, with mantissa in [1, 2)
+        let mut mantissa = self;
+        let mut exponent = zero;
+        while mantissa >= two {
+            mantissa = mantissa * half;
+            exponent = exponent + one;
+        }
+        while mantissa < one {
+            mantissa = mantissa * two;
+            exponent = exponent - one;
+        }
+
+        series(mantissa) + exponent * series(two)
+    }
+
+    /// Returns the value closest to `value` that is representable by `Self`.
+    ///
+    /// The default implementation builds the mantissa of `value` from 16 bit chunks and scales it
+    /// by powers of two, so the result may be rounded twice, and should be overridden by types
+    /// that can provide it directly.
+    fn from_f64(value: f64) -> Self {
+        let zero = Self::zero();
+        let one = Self::one();
+        let two = one + one;
+        let half = two.inv();
+
+        let inf = one / zero;
+        if value.is_nan() {
+            // 0 × inf is NaN
+            return zero * inf;
+        }
+
+        let magnitude = if value.is_infinite() {
+            inf
+        } else {
+            // |value| = mantissa × 2^(exponent - 52), with a 53 bit integer mantissa
+            let bits = value.to_bits();
+            let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+            let fraction = bits & ((1u64 << 52) - 1);
+            let (mantissa, exponent) = if biased_exponent == 0 {
+                (fraction, -1022)
+            } else {
+                (fraction | (1u64 << 52), biased_exponent - 1023)
+            };
+
+            let chunk = |shift: u32| {
+                let bits = (mantissa >> shift) & 0xffff;
+                let mut x = zero;
+                for i in (0..16).rev() {
+                    x = x * two;
+                    if (bits >> i) & 1 == 1 {
+                        x = x + one;
+                    }
+                }
+                x
+            };
+            let mut chunk_scale = one;
+            for _ in 0..16 {
+                chunk_scale = chunk_scale * half;
+            }
+
+            // x = mantissa / 2^48, starting from the least significant chunk
+            let mut x = chunk(0);
+            x = x * chunk_scale + chunk(16);
+            x = x * chunk_scale + chunk(32);
+            x = x * chunk_scale + chunk(48);
+
+            let mut exponent = exponent - 4;
+            while exponent > 0 {
+                x = x * two;
+                exponent -= 1;
+            }
+            while exponent < 0 {
+                x = x * half;
+                exponent += 1;
+            }
+            x
+        };
 
-impl RealField for f32 {}
+        if value.is_sign_negative() {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+impl RealField for f32 {
+    #[inline(always)]
+    fn epsilon() -> Self {
+        f32::EPSILON
+    }
+
+    #[inline(always)]
+    fn min_positive() -> Self {
+        f32::MIN_POSITIVE
+    }
+
+    #[inline(always)]
+    fn ln(self) -> Self {
+        f32::ln(self)
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
 impl ComplexField for f32 {
     type Real = f32;
 
@@ -233,7 +441,27 @@ impl ComplexField for f32 {
     }
 }
 
-impl RealField for f64 {}
+impl RealField for f64 {
+    #[inline(always)]
+    fn epsilon() -> Self {
+        f64::EPSILON
+    }
+
+    #[inline(always)]
+    fn min_positive() -> Self {
+        f64::MIN_POSITIVE
+    }
+
+    #[inline(always)]
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
 impl ComplexField for f64 {
     type Real = f64;
 
@@ -4868,6 +5096,234 @@ impl<T> Sub<&Mat<T>> for &Mat<T>
     }
 }
 
+/// Computes `lhs * rhs` into a newly allocated matrix.
+#[track_caller]
+#[inline]
+fn mul_to_owned<T: ComplexField>(lhs: MatRef<'_, T>, rhs: MatRef<'_, T>) -> Mat<T> {
+    fancy_assert!(lhs.ncols() == rhs.nrows());
+    let mut dst = Mat::zeros(lhs.nrows(), rhs.ncols());
+    mul::matmul(
+        dst.as_mut(),
+        Conj::No,
+        lhs,
+        Conj::No,
+        rhs,
+        Conj::No,
+        None,
+        T::one(),
+        Parallelism::None,
+    );
+    dst
+}
+
+/// Computes `factor * src` into a newly allocated matrix.
+#[inline]
+fn scale_to_owned<T: ComplexField>(src: MatRef<'_, T>, factor: T) -> Mat<T> {
+    Mat::with_dims(|i, j| factor * src[(i, j)], src.nrows(), src.ncols())
+}
+
+impl<'a, 'b, T: ComplexField> Mul<MatRef<'b, T>> for MatRef<'a, T> {
+    type Output = Mat<T>;
+
+    /// Computes the matrix product, sequentially. For more control over the computation, use
+    /// [`mul::matmul`].
+    #[track_caller]
+    #[inline]
+    fn mul(self, rhs: MatRef<'b, T>) -> Self::Output {
+        mul_to_owned(self, rhs)
+    }
+}
+
+impl<'a, 'b, T: ComplexField> Mul<ColRef<'b, T>> for MatRef<'a, T> {
+    type Output = Mat<T>;
+
+    /// Computes the matrix-vector product as a matrix with one column, sequentially.
+    #[track_caller]
+    #[inline]
+    fn mul(self, rhs: ColRef<'b, T>) -> Self::Output {
+        mul_to_owned(self, rhs.as_2d())
+    }
+}
+
+impl<T: ComplexField> Mul<Mat<T>> for Mat<T> {
+    type Output = Mat<T>;
+
+    #[track_caller]
+    #[inline]
+    fn mul(self, rhs: Mat<T>) -> Self::Output {
+        mul_to_owned(self.as_ref(), rhs.as_ref())
+    }
+}
+
+impl<T: ComplexField> Mul<&Mat<T>> for &Mat<T> {
+    type Output = Mat<T>;
+
+    #[track_caller]
+    #[inline]
+    fn mul(self, rhs: &Mat<T>) -> Self::Output {
+        mul_to_owned(self.as_ref(), rhs.as_ref())
+    }
+}
+
+impl<'b, T: ComplexField> Mul<ColRef<'b, T>> for &Mat<T> {
+    type Output = Mat<T>;
+
+    #[track_caller]
+    #[inline]
+    fn mul(self, rhs: ColRef<'b, T>) -> Self::Output {
+        mul_to_owned(self.as_ref(), rhs.as_2d())
+    }
+}
+
+impl<'a, T: ComplexField> Mul<T> for MatRef<'a, T> {
+    type Output = Mat<T>;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Self::Output {
+        scale_to_owned(self, rhs)
+    }
+}
+
+impl<T: ComplexField> Mul<T> for Mat<T> {
+    type Output = Mat<T>;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut this = self;
+        this.as_mut().cwise().for_each(|x| *x = *x * rhs);
+        this
+    }
+}
+
+impl<T: ComplexField> Mul<T> for &Mat<T> {
+    type Output = Mat<T>;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Self::Output {
+        scale_to_owned(self.as_ref(), rhs)
+    }
+}
+
+// scalars on the left hand side can only be supported for concrete types
+macro_rules! impl_scalar_lhs_mul {
+    ($($ty: ty),*) => {
+        $(
+            impl<'a> Mul<MatRef<'a, $ty>> for $ty {
+                type Output = Mat<$ty>;
+
+                #[inline]
+                fn mul(self, rhs: MatRef<'a, $ty>) -> Self::Output {
+                    scale_to_owned(rhs, self)
+                }
+            }
+
+            impl Mul<Mat<$ty>> for $ty {
+                type Output = Mat<$ty>;
+
+                #[inline]
+                fn mul(self, rhs: Mat<$ty>) -> Self::Output {
+                    rhs * self
+                }
+            }
+
+            impl Mul<&Mat<$ty>> for $ty {
+                type Output = Mat<$ty>;
+
+                #[inline]
+                fn mul(self, rhs: &Mat<$ty>) -> Self::Output {
+                    scale_to_owned(rhs.as_ref(), self)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_lhs_mul!(f32, f64, c32, c64);
+
+impl<'a, T: ComplexField> Neg for MatRef<'a, T> {
+    type Output = Mat<T>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Mat::with_dims(|i, j| -self[(i, j)], self.nrows(), self.ncols())
+    }
+}
+
+impl<T: ComplexField> Neg for Mat<T> {
+    type Output = Mat<T>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        let mut this = self;
+        this.as_mut().cwise().for_each(|x| *x = -*x);
+        this
+    }
+}
+
+impl<T: ComplexField> Neg for &Mat<T> {
+    type Output = Mat<T>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        -self.as_ref()
+    }
+}
+
+impl<'a, 'b, T: ComplexField> AddAssign<MatRef<'b, T>> for MatMut<'a, T> {
+    #[track_caller]
+    #[inline]
+    fn add_assign(&mut self, rhs: MatRef<'b, T>) {
+        self.rb_mut()
+            .cwise()
+            .zip(rhs)
+            .for_each(|dst, src| *dst = *dst + *src);
+    }
+}
+
+impl<'a, 'b, T: ComplexField> SubAssign<MatRef<'b, T>> for MatMut<'a, T> {
+    #[track_caller]
+    #[inline]
+    fn sub_assign(&mut self, rhs: MatRef<'b, T>) {
+        self.rb_mut()
+            .cwise()
+            .zip(rhs)
+            .for_each(|dst, src| *dst = *dst - *src);
+    }
+}
+
+impl<'a, T: ComplexField> MulAssign<T> for MatMut<'a, T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: T) {
+        self.rb_mut().cwise().for_each(|x| *x = *x * rhs);
+    }
+}
+
+impl<'b, T: ComplexField> AddAssign<MatRef<'b, T>> for Mat<T> {
+    #[track_caller]
+    #[inline]
+    fn add_assign(&mut self, rhs: MatRef<'b, T>) {
+        let mut this = self.as_mut();
+        this += rhs;
+    }
+}
+
+impl<'b, T: ComplexField> SubAssign<MatRef<'b, T>> for Mat<T> {
+    #[track_caller]
+    #[inline]
+    fn sub_assign(&mut self, rhs: MatRef<'b, T>) {
+        let mut this = self.as_mut();
+        this -= rhs;
+    }
+}
+
+impl<T: ComplexField> MulAssign<T> for Mat<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: T) {
+        let mut this = self.as_mut();
+        this *= rhs;
+    }
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __transpose_impl {
@@ -5222,4 +5678,175 @@ mod tests {
             [4.0, 5.0],
         ];
     }
+
+    #[test]
+    fn mul_operators() {
+        let a = mat![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let b = mat![[1.0, -1.0, 2.0], [0.0, 3.0, 1.0]];
+        let target = mat![[1.0, 5.0, 4.0], [3.0, 9.0, 10.0], [5.0, 13.0, 16.0]];
+
+        let c = &a * &b;
+        fancy_assert!(c.as_ref() == target.as_ref());
+        let c = a.as_ref() * b.as_ref();
+        fancy_assert!(c.as_ref() == target.as_ref());
+        let c = a.clone() * b.clone();
+        fancy_assert!(c.as_ref() == target.as_ref());
+
+        let x = &a * b.as_ref().col(1);
+        fancy_assert!(x.as_ref() == mat![[5.0], [9.0], [13.0]].as_ref());
+        let x = a.as_ref() * b.as_ref().col(1);
+        fancy_assert!(x.as_ref() == mat![[5.0], [9.0], [13.0]].as_ref());
+
+        let scaled = mat![[2.0, 4.0], [6.0, 8.0], [10.0, 12.0]];
+        fancy_assert!((&a * 2.0).as_ref() == scaled.as_ref());
+        fancy_assert!((2.0f64 * &a).as_ref() == scaled.as_ref());
+        fancy_assert!((a.as_ref() * 2.0).as_ref() == scaled.as_ref());
+        fancy_assert!((2.0f64 * a.as_ref()).as_ref() == scaled.as_ref());
+        fancy_assert!((a.clone() * 2.0).as_ref() == scaled.as_ref());
+        fancy_assert!((2.0f64 * a.clone()).as_ref() == scaled.as_ref());
+
+        let z = mat![[c64::new(1.0, 2.0)]] * c64::new(0.0, 1.0);
+        fancy_assert!(z[(0, 0)] == c64::new(-2.0, 1.0));
+
+        let negated = mat![[-1.0, -2.0], [-3.0, -4.0], [-5.0, -6.0]];
+        fancy_assert!((-&a).as_ref() == negated.as_ref());
+        fancy_assert!((-a.as_ref()).as_ref() == negated.as_ref());
+        fancy_assert!((-a.clone()).as_ref() == negated.as_ref());
+    }
+
+    #[test]
+    fn assign_operators() {
+        let a = mat![[1.0, 2.0], [3.0, 4.0]];
+        let b = mat![[4.0, 3.0], [2.0, 1.0]];
+
+        let mut c = a.clone();
+        c += b.as_ref();
+        fancy_assert!(c.as_ref() == mat![[5.0, 5.0], [5.0, 5.0]].as_ref());
+        c -= a.as_ref();
+        fancy_assert!(c.as_ref() == b.as_ref());
+        c *= 2.0;
+        fancy_assert!(c.as_ref() == mat![[8.0, 6.0], [4.0, 2.0]].as_ref());
+
+        // views
+        let mut c = a.clone();
+        {
+            let mut view = c.as_mut().submatrix(0, 1, 2, 1);
+            view += b.as_ref().submatrix(0, 0, 2, 1);
+            view *= -1.0;
+            view -= a.as_ref().submatrix(0, 0, 2, 1);
+        }
+        fancy_assert!(c.as_ref() == mat![[1.0, -7.0], [3.0, -9.0]].as_ref());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_different_size() {
+        let _ = &mat![[1.0, 2.0]] * &mat![[1.0, 2.0]];
+    }
+
+    /// Real type relying on the default implementations of [`RealField`].
+    #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+    struct Real(f32);
+
+    macro_rules! impl_real_op {
+        ($trait: ident, $fn: ident, $op: tt) => {
+            impl $trait for Real {
+                type Output = Real;
+
+                fn $fn(self, rhs: Real) -> Real {
+                    Real(self.0 $op rhs.0)
+                }
+            }
+        };
+    }
+
+    impl_real_op!(Add, add, +);
+    impl_real_op!(Sub, sub, -);
+    impl_real_op!(Mul, mul, *);
+    impl_real_op!(Div, div, /);
+
+    impl Neg for Real {
+        type Output = Real;
+
+        fn neg(self) -> Real {
+            Real(-self.0)
+        }
+    }
+
+    impl ComplexField for Real {
+        type Real = Real;
+
+        fn from_real(real: Real) -> Self {
+            real
+        }
+        fn into_real_imag(self) -> (Real, Real) {
+            (self, Real(0.0))
+        }
+        fn zero() -> Self {
+            Real(0.0)
+        }
+        fn one() -> Self {
+            Real(1.0)
+        }
+        fn inv(self) -> Self {
+            Real(1.0 / self.0)
+        }
+        fn conj(self) -> Self {
+            self
+        }
+        fn sqrt(self) -> Self {
+            Real(self.0.sqrt())
+        }
+        fn score(self) -> Real {
+            Real(self.0.abs())
+        }
+        fn abs(self) -> Real {
+            Real(self.0.abs())
+        }
+    }
+
+    impl RealField for Real {}
+
+    #[test]
+    fn real_field_defaults() {
+        fancy_assert!(Real::epsilon() == Real(f32::EPSILON));
+        fancy_assert!(Real::min_positive() == Real(f32::MIN_POSITIVE));
+        fancy_assert!(Real(3.0).hypot(Real(-4.0)) == Real(5.0));
+        fancy_assert!(Real(3e30).hypot(Real(4e30)) == Real(5e30));
+        fancy_assert!(Real(0.0).hypot(Real(0.0)) == Real(0.0));
+        fancy_assert!(Real(1.0).is_finite());
+        fancy_assert!(!Real(f32::INFINITY).is_finite());
+        fancy_assert!(!Real(f32::NAN).is_finite());
+
+        for x in [1.0, 2.0, 0.5, 1.9999, 3.0, 10.0, 1e-3, 1e30, 1e-40, f32::MAX] {
+            let expected = x.ln();
+            let ln = Real(x).ln().0;
+            fancy_assert!((ln - expected).abs() <= 4.0 * f32::EPSILON * expected.abs().max(1.0));
+        }
+        fancy_assert!(Real(1.0).ln() == Real(0.0));
+        fancy_assert!(Real(0.0).ln() == Real(f32::NEG_INFINITY));
+        fancy_assert!(Real(f32::INFINITY).ln() == Real(f32::INFINITY));
+        fancy_assert!(Real(-1.0).ln().0.is_nan());
+        fancy_assert!(Real(f32::NAN).ln().0.is_nan());
+
+        for x in [
+            0.0,
+            -0.0,
+            1.0,
+            0.1,
+            -3.5,
+            core::f64::consts::PI,
+            1e30,
+            -1e-30,
+            1e-40,
+            f64::MAX,
+            1e-300,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ] {
+            let converted = Real::from_f64(x).0;
+            fancy_assert!(converted.to_bits() == (x as f32).to_bits());
+        }
+        fancy_assert!(Real::from_f64(f64::NAN).0.is_nan());
+    }
 }
@@ -0,0 +1,163 @@
+//! This module provides the building blocks shared by the mixed precision solvers, which factorize
+//! a matrix in a lower precision and refine the solution iteratively, with the residuals computed
+//! in the working precision.
+
+use crate::{c32, c64, ComplexField, MatRef, RealField};
+
+/// Trait for the types that have a lower precision counterpart, in which a matrix can be
+/// factorized faster.
+pub trait MixedPrecision: ComplexField {
+    /// Lower precision counterpart of `Self`.
+    type Lower: ComplexField;
+
+    /// Returns the value closest to `self` that is representable by `Self::Lower`, which may be
+    /// infinite if `self` is out of its range.
+    fn to_lower(self) -> Self::Lower;
+    /// Returns `value` converted to `Self`.
+    fn from_lower(value: Self::Lower) -> Self;
+}
+
+impl MixedPrecision for f64 {
+    type Lower = f32;
+
+    #[inline(always)]
+    fn to_lower(self) -> Self::Lower {
+        self as f32
+    }
+
+    #[inline(always)]
+    fn from_lower(value: Self::Lower) -> Self {
+        value as f64
+    }
+}
+
+impl MixedPrecision for c64 {
+    type Lower = c32;
+
+    #[inline(always)]
+    fn to_lower(self) -> Self::Lower {
+        c32::new(self.re as f32, self.im as f32)
+    }
+
+    #[inline(always)]
+    fn from_lower(value: Self::Lower) -> Self {
+        c64::new(value.re as f64, value.im as f64)
+    }
+}
+
+/// Parameters of the mixed precision solvers.
+#[derive(Copy, Clone)]
+#[non_exhaustive]
+pub struct MixedPrecisionParams<R> {
+    /// Maximum number of refinement iterations before falling back to a working precision
+    /// factorization. Defaults to `30`.
+    pub max_iterations: Option<usize>,
+    /// Tolerance on the normwise backward error of each column of the solution. Defaults to
+    /// $\sqrt{n} \varepsilon$, where $n$ is the dimension of the matrix, and $\varepsilon$ is the
+    /// machine epsilon of the working precision.
+    pub tolerance: Option<R>,
+}
+
+impl<R> Default for MixedPrecisionParams<R> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_iterations: None,
+            tolerance: None,
+        }
+    }
+}
+
+impl<R: RealField> MixedPrecisionParams<R> {
+    /// Returns the maximum number of iterations and the tolerance for a matrix of dimension
+    /// `dim`, replacing the unset parameters by their default values.
+    pub fn normalize(self, dim: usize) -> (usize, R) {
+        let max_iterations = self.max_iterations.unwrap_or(30);
+        let tolerance = self.tolerance.unwrap_or_else(|| {
+            let mut n = R::zero();
+            for _ in 0..dim {
+                n = n + R::one();
+            }
+            n.sqrt() * R::epsilon()
+        });
+        (max_iterations, tolerance)
+    }
+}
+
+/// Reason why a mixed precision solver fell back to a working precision factorization.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FallbackReason {
+    /// The matrix has elements that can't be represented in the lower precision.
+    NotRepresentable,
+    /// The lower precision factorization broke down.
+    FactorizationBreakdown,
+    /// The iterative refinement didn't converge within the maximum number of iterations.
+    NoConvergence,
+}
+
+/// Information about the convergence of a mixed precision solve.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MixedPrecisionInfo {
+    /// Number of refinement iterations that were performed with the lower precision
+    /// factorization.
+    pub iterations: usize,
+    /// Reason why the system was solved using a working precision factorization instead, or
+    /// `None` if the iterative refinement converged.
+    pub fallback_reason: Option<FallbackReason>,
+}
+
+/// Returns `true` if the normwise backward error of each column of `x` is below the tolerance,
+/// given the residual $r = b - Ax$ and `threshold`, which is the tolerance multiplied by the
+/// infinity norm of $A$.
+pub fn has_converged<T: ComplexField>(
+    residual: MatRef<'_, T>,
+    x: MatRef<'_, T>,
+    threshold: T::Real,
+) -> bool {
+    let zero = T::Real::zero();
+    for j in 0..x.ncols() {
+        let mut r_norm = zero;
+        let mut x_norm = zero;
+        for i in 0..x.nrows() {
+            let r = residual[(i, j)].abs();
+            // propagate NaN values
+            if r > r_norm || !r.is_finite() {
+                r_norm = r;
+            }
+            let x = x[(i, j)].abs();
+            if x > x_norm {
+                x_norm = x;
+            }
+        }
+        if r_norm > threshold * x_norm || !r_norm.is_finite() {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mat;
+    use assert2::assert as fancy_assert;
+
+    #[test]
+    fn test_conversions() {
+        fancy_assert!(1.5f64.to_lower() == 1.5f32);
+        fancy_assert!(1e300f64.to_lower() == f32::INFINITY);
+        fancy_assert!(f64::from_lower(0.25f32) == 0.25);
+        fancy_assert!(c64::new(1.0, -2.0).to_lower() == c32::new(1.0, -2.0));
+        fancy_assert!(c64::from_lower(c32::new(1.0, -2.0)) == c64::new(1.0, -2.0));
+    }
+
+    #[test]
+    fn test_has_converged() {
+        let x = Mat::with_dims(|i, _| (i + 1) as f64, 3, 2);
+        let mut r = Mat::with_dims(|_, _| 1e-10, 3, 2);
+        fancy_assert!(has_converged(r.as_ref(), x.as_ref(), 1e-9));
+        fancy_assert!(!has_converged(r.as_ref(), x.as_ref(), 1e-11));
+        r[(1, 1)] = f64::NAN;
+        fancy_assert!(!has_converged(r.as_ref(), x.as_ref(), 1e-9));
+    }
+}
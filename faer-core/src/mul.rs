@@ -1523,6 +1523,261 @@ pub mod triangular {
     }
 }
 
+/// Symmetric and hermitian matrix multiplication module, where the product is known to be
+/// symmetric or hermitian, so that only one triangular half of it is computed.
+pub mod symmetric {
+    use super::*;
+    use crate::mul::triangular::BlockStructure;
+
+    #[inline]
+    fn flip(conj: Conj) -> Conj {
+        match conj {
+            Conj::No => Conj::Yes,
+            Conj::Yes => Conj::No,
+        }
+    }
+
+    #[track_caller]
+    fn assert_triangular(acc_structure: BlockStructure) {
+        fancy_assert!(matches!(
+            acc_structure,
+            BlockStructure::TriangularLower | BlockStructure::TriangularUpper
+        ));
+    }
+
+    /// Sets the imaginary parts of the diagonal elements of `acc` to zero.
+    fn make_diagonal_real<T: ComplexField>(acc: MatMut<'_, T>) {
+        let mut acc = acc;
+        for j in 0..acc.nrows() {
+            let d = acc[(j, j)];
+            acc[(j, j)] = T::from_real(d.real());
+        }
+    }
+
+    /// Computes the symmetric rank k update `[alpha * acc] + beta * Op(lhs) * Op(lhs)^T` and
+    /// stores the result in `acc`.
+    ///
+    /// Only the triangular half of `acc` given by `acc_structure` is accessed, which must be
+    /// either `BlockStructure::TriangularLower` or `BlockStructure::TriangularUpper`. This
+    /// requires about half as many operations as computing the full product.
+    ///
+    /// If `alpha` is not provided, the preexisting values in `acc` are not read.
+    ///
+    /// `Op` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+    /// `Conj::Yes`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `acc` is not a square matrix, or if `acc.nrows() != lhs.nrows()`.
+    /// - Panics if `acc_structure` is neither `BlockStructure::TriangularLower` nor
+    ///   `BlockStructure::TriangularUpper`.
+    #[track_caller]
+    pub fn syrk<T: ComplexField>(
+        acc: MatMut<'_, T>,
+        acc_structure: BlockStructure,
+        lhs: MatRef<'_, T>,
+        conj_lhs: Conj,
+        alpha: Option<T>,
+        beta: T,
+        parallelism: Parallelism,
+    ) {
+        assert_triangular(acc_structure);
+        triangular::matmul(
+            acc,
+            acc_structure,
+            Conj::No,
+            lhs,
+            BlockStructure::Rectangular,
+            conj_lhs,
+            lhs.transpose(),
+            BlockStructure::Rectangular,
+            conj_lhs,
+            alpha,
+            beta,
+            parallelism,
+        );
+    }
+
+    /// Computes the hermitian rank k update `[alpha * acc] + beta * Op(lhs) * Op(lhs)^*`, where
+    /// `alpha` and `beta` are real, and stores the result in `acc`.
+    ///
+    /// Only the triangular half of `acc` given by `acc_structure` is accessed, which must be
+    /// either `BlockStructure::TriangularLower` or `BlockStructure::TriangularUpper`. This
+    /// requires about half as many operations as computing the full product. The imaginary parts
+    /// of the diagonal elements of the result are set to zero.
+    ///
+    /// If `alpha` is not provided, the preexisting values in `acc` are not read.
+    ///
+    /// `Op` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+    /// `Conj::Yes`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `acc` is not a square matrix, or if `acc.nrows() != lhs.nrows()`.
+    /// - Panics if `acc_structure` is neither `BlockStructure::TriangularLower` nor
+    ///   `BlockStructure::TriangularUpper`.
+    #[track_caller]
+    pub fn herk<T: ComplexField>(
+        acc: MatMut<'_, T>,
+        acc_structure: BlockStructure,
+        lhs: MatRef<'_, T>,
+        conj_lhs: Conj,
+        alpha: Option<T::Real>,
+        beta: T::Real,
+        parallelism: Parallelism,
+    ) {
+        assert_triangular(acc_structure);
+        let mut acc = acc;
+        triangular::matmul(
+            acc.rb_mut(),
+            acc_structure,
+            Conj::No,
+            lhs,
+            BlockStructure::Rectangular,
+            conj_lhs,
+            lhs.transpose(),
+            BlockStructure::Rectangular,
+            flip(conj_lhs),
+            alpha.map(T::from_real),
+            T::from_real(beta),
+            parallelism,
+        );
+        make_diagonal_real(acc);
+    }
+
+    /// Computes the symmetric rank 2k update
+    /// `[alpha * acc] + beta * Op_lhs(lhs) * Op_rhs(rhs)^T + beta * Op_rhs(rhs) * Op_lhs(lhs)^T`
+    /// and stores the result in `acc`.
+    ///
+    /// Only the triangular half of `acc` given by `acc_structure` is accessed, which must be
+    /// either `BlockStructure::TriangularLower` or `BlockStructure::TriangularUpper`.
+    ///
+    /// If `alpha` is not provided, the preexisting values in `acc` are not read.
+    ///
+    /// `Op_lhs` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+    /// `Conj::Yes`.  
+    /// `Op_rhs` is the identity if `conj_rhs` is `Conj::No`, and the conjugation operation if it is
+    /// `Conj::Yes`.  
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `acc` is not a square matrix, or if `lhs` and `rhs` don't both have
+    ///   `acc.nrows()` rows and the same number of columns.
+    /// - Panics if `acc_structure` is neither `BlockStructure::TriangularLower` nor
+    ///   `BlockStructure::TriangularUpper`.
+    #[track_caller]
+    pub fn syr2k<T: ComplexField>(
+        acc: MatMut<'_, T>,
+        acc_structure: BlockStructure,
+        lhs: MatRef<'_, T>,
+        conj_lhs: Conj,
+        rhs: MatRef<'_, T>,
+        conj_rhs: Conj,
+        alpha: Option<T>,
+        beta: T,
+        parallelism: Parallelism,
+    ) {
+        assert_triangular(acc_structure);
+        fancy_assert!((lhs.nrows(), lhs.ncols()) == (rhs.nrows(), rhs.ncols()));
+        let mut acc = acc;
+        triangular::matmul(
+            acc.rb_mut(),
+            acc_structure,
+            Conj::No,
+            lhs,
+            BlockStructure::Rectangular,
+            conj_lhs,
+            rhs.transpose(),
+            BlockStructure::Rectangular,
+            conj_rhs,
+            alpha,
+            beta,
+            parallelism,
+        );
+        triangular::matmul(
+            acc,
+            acc_structure,
+            Conj::No,
+            rhs,
+            BlockStructure::Rectangular,
+            conj_rhs,
+            lhs.transpose(),
+            BlockStructure::Rectangular,
+            conj_lhs,
+            Some(T::one()),
+            beta,
+            parallelism,
+        );
+    }
+
+    /// Computes the hermitian rank 2k update
+    /// `[alpha * acc] + beta * Op_lhs(lhs) * Op_rhs(rhs)^* + conj(beta) * Op_rhs(rhs) *
+    /// Op_lhs(lhs)^*`, where `alpha` is real, and stores the result in `acc`.
+    ///
+    /// Only the triangular half of `acc` given by `acc_structure` is accessed, which must be
+    /// either `BlockStructure::TriangularLower` or `BlockStructure::TriangularUpper`. The
+    /// imaginary parts of the diagonal elements of the result are set to zero.
+    ///
+    /// If `alpha` is not provided, the preexisting values in `acc` are not read.
+    ///
+    /// `Op_lhs` is the identity if `conj_lhs` is `Conj::No`, and the conjugation operation if it is
+    /// `Conj::Yes`.  
+    /// `Op_rhs` is the identity if `conj_rhs` is `Conj::No`, and the conjugation operation if it is
+    /// `Conj::Yes`.  
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `acc` is not a square matrix, or if `lhs` and `rhs` don't both have
+    ///   `acc.nrows()` rows and the same number of columns.
+    /// - Panics if `acc_structure` is neither `BlockStructure::TriangularLower` nor
+    ///   `BlockStructure::TriangularUpper`.
+    #[track_caller]
+    pub fn her2k<T: ComplexField>(
+        acc: MatMut<'_, T>,
+        acc_structure: BlockStructure,
+        lhs: MatRef<'_, T>,
+        conj_lhs: Conj,
+        rhs: MatRef<'_, T>,
+        conj_rhs: Conj,
+        alpha: Option<T::Real>,
+        beta: T,
+        parallelism: Parallelism,
+    ) {
+        assert_triangular(acc_structure);
+        fancy_assert!((lhs.nrows(), lhs.ncols()) == (rhs.nrows(), rhs.ncols()));
+        let mut acc = acc;
+        triangular::matmul(
+            acc.rb_mut(),
+            acc_structure,
+            Conj::No,
+            lhs,
+            BlockStructure::Rectangular,
+            conj_lhs,
+            rhs.transpose(),
+            BlockStructure::Rectangular,
+            flip(conj_rhs),
+            alpha.map(T::from_real),
+            beta,
+            parallelism,
+        );
+        triangular::matmul(
+            acc.rb_mut(),
+            acc_structure,
+            Conj::No,
+            rhs,
+            BlockStructure::Rectangular,
+            conj_rhs,
+            lhs.transpose(),
+            BlockStructure::Rectangular,
+            flip(conj_lhs),
+            Some(T::one()),
+            beta.conj(),
+            parallelism,
+        );
+        make_diagonal_real(acc);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_approx_eq::assert_approx_eq;
@@ -1755,4 +2010,138 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn symmetric() {
+        use crate::c64;
+        use symmetric::{her2k, herk, syr2k, syrk};
+        use BlockStructure::*;
+
+        let random_mat =
+            |m: usize, n: usize| Mat::with_dims(|_, _| c64::new(random(), random()), m, n);
+
+        for (n, k) in [(0, 0), (1, 1), (5, 3), (20, 40), (70, 10)] {
+            let a = random_mat(n, k);
+            let b = random_mat(n, k);
+            let acc = random_mat(n, n);
+            let beta = c64::new(random(), random());
+            let beta_real: f64 = random();
+
+            for structure in [TriangularLower, TriangularUpper] {
+                for alpha in [None, Some(2.5)] {
+                    for conj_a in [Conj::No, Conj::Yes] {
+                        for conj_b in [Conj::No, Conj::Yes] {
+                            let flip = |conj| match conj {
+                                Conj::No => Conj::Yes,
+                                Conj::Yes => Conj::No,
+                            };
+                            let full = |lhs: &Mat<c64>,
+                                        conj_lhs,
+                                        rhs: &Mat<c64>,
+                                        conj_rhs,
+                                        beta|
+                             -> Mat<c64> {
+                                let mut dst = Mat::zeros(n, n);
+                                super::matmul(
+                                    dst.as_mut(),
+                                    Conj::No,
+                                    lhs.as_ref(),
+                                    conj_lhs,
+                                    rhs.as_ref().transpose(),
+                                    conj_rhs,
+                                    None,
+                                    beta,
+                                    Parallelism::None,
+                                );
+                                dst
+                            };
+
+                            let ab_t = full(&a, conj_a, &b, conj_b, beta);
+                            let ba_t = full(&b, conj_b, &a, conj_a, beta);
+                            let ab_h = full(&a, conj_a, &b, flip(conj_b), beta);
+                            let ba_h = full(&b, conj_b, &a, flip(conj_a), beta.conj());
+                            let aa_t = full(&a, conj_a, &a, conj_a, beta);
+                            let aa_h = full(&a, conj_a, &a, flip(conj_a), c64::new(beta_real, 0.0));
+
+                            let check = |result: &Mat<c64>,
+                                         target: &dyn Fn(usize, usize) -> c64,
+                                         real_diag: bool| {
+                                for j in 0..n {
+                                    for i in 0..n {
+                                        let in_triangle =
+                                            if structure.is_lower() { i >= j } else { i <= j };
+                                        let mut expected = if in_triangle {
+                                            let base = match alpha {
+                                                Some(alpha) => acc[(i, j)] * alpha,
+                                                None => c64::zero(),
+                                            };
+                                            base + target(i, j)
+                                        } else {
+                                            acc[(i, j)]
+                                        };
+                                        if real_diag && i == j {
+                                            expected.im = 0.0;
+                                        }
+                                        assert!((result[(i, j)] - expected).abs() < 1e-10);
+                                    }
+                                }
+                            };
+
+                            let mut result = acc.clone();
+                            syrk(
+                                result.as_mut(),
+                                structure,
+                                a.as_ref(),
+                                conj_a,
+                                alpha.map(|alpha| c64::new(alpha, 0.0)),
+                                beta,
+                                Parallelism::None,
+                            );
+                            check(&result, &|i, j| aa_t[(i, j)], false);
+
+                            let mut result = acc.clone();
+                            herk(
+                                result.as_mut(),
+                                structure,
+                                a.as_ref(),
+                                conj_a,
+                                alpha,
+                                beta_real,
+                                Parallelism::None,
+                            );
+                            check(&result, &|i, j| aa_h[(i, j)], true);
+
+                            let mut result = acc.clone();
+                            syr2k(
+                                result.as_mut(),
+                                structure,
+                                a.as_ref(),
+                                conj_a,
+                                b.as_ref(),
+                                conj_b,
+                                alpha.map(|alpha| c64::new(alpha, 0.0)),
+                                beta,
+                                Parallelism::None,
+                            );
+                            check(&result, &|i, j| ab_t[(i, j)] + ba_t[(i, j)], false);
+
+                            let mut result = acc.clone();
+                            her2k(
+                                result.as_mut(),
+                                structure,
+                                a.as_ref(),
+                                conj_a,
+                                b.as_ref(),
+                                conj_b,
+                                alpha,
+                                beta,
+                                Parallelism::None,
+                            );
+                            check(&result, &|i, j| ab_h[(i, j)] + ba_h[(i, j)], true);
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,835 @@
+//! This module provides functions for computing the norms of matrices and vectors.
+//!
+//! All the functions propagate NaN values, and the functions computing euclidean and Frobenius
+//! norms avoid unnecessary overflow and underflow by accumulating a scaled sum of squares, as in
+//! LAPACK's `xLASSQ`.
+
+use crate::{
+    join_raw, mul::matmul, temp_mat_req, temp_mat_uninit, ColMut, ColRef, ComplexField, Conj,
+    MatRef, Parallelism, RealField, RowRef,
+};
+use assert2::assert as fancy_assert;
+use core::cmp::Ordering;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Matrices with fewer elements than this are processed sequentially.
+const PARALLEL_THRESHOLD: usize = 128 * 128;
+
+/// Number of rows whose sums are accumulated simultaneously when computing row sums of a column
+/// major matrix.
+const ROW_CHUNK: usize = 32;
+
+/// Returns the maximum of `a` and `b`, or NaN if either of them is NaN.
+#[inline(always)]
+fn max_nan<T: RealField>(a: T, b: T) -> T {
+    match a.partial_cmp(&b) {
+        Some(Ordering::Less) => b,
+        Some(_) => a,
+        None => {
+            if a.partial_cmp(&a).is_none() {
+                a
+            } else {
+                b
+            }
+        }
+    }
+}
+
+/// Merges two scaled sums of squares, such that the result represents the sum of the values
+/// represented by the operands.
+#[inline(always)]
+fn merge_sum_of_squares<T: RealField>(lhs: (T, T), rhs: (T, T)) -> (T, T) {
+    let (lhs_scale, lhs_ssq) = lhs;
+    let (rhs_scale, rhs_ssq) = rhs;
+
+    match lhs_scale.partial_cmp(&rhs_scale) {
+        None => {
+            if lhs_scale.partial_cmp(&lhs_scale).is_none() {
+                lhs
+            } else {
+                rhs
+            }
+        }
+        Some(Ordering::Less) => {
+            let ratio = lhs_scale / rhs_scale;
+            (rhs_scale, rhs_ssq + lhs_ssq * (ratio * ratio))
+        }
+        Some(_) => {
+            if lhs_scale == T::zero() || !lhs_scale.is_finite() {
+                lhs
+            } else {
+                let ratio = rhs_scale / lhs_scale;
+                (lhs_scale, lhs_ssq + rhs_ssq * (ratio * ratio))
+            }
+        }
+    }
+}
+
+/// Calls `f` on each element of the `j`-th column of `matrix`.
+#[inline(always)]
+fn for_each_in_col<T: ComplexField>(matrix: MatRef<'_, T>, j: usize, mut f: impl FnMut(T)) {
+    let m = matrix.nrows();
+    if m == 0 {
+        return;
+    }
+    if matrix.row_stride() == 1 {
+        // SAFETY: the column is contiguous, and contains `m` elements
+        let col = unsafe { core::slice::from_raw_parts(matrix.ptr_at(0, j), m) };
+        for &x in col {
+            f(x);
+        }
+    } else {
+        for i in 0..m {
+            f(unsafe { *matrix.get_unchecked(i, j) });
+        }
+    }
+}
+
+/// Returns a view over the same elements as `matrix` that is preferably column major, for
+/// reductions that don't depend on the order of the elements.
+#[inline(always)]
+fn col_major<T>(matrix: MatRef<'_, T>) -> MatRef<'_, T> {
+    if matrix.row_stride().unsigned_abs() > matrix.col_stride().unsigned_abs() {
+        matrix.transpose()
+    } else {
+        matrix
+    }
+}
+
+/// Splits `matrix` into halves recursively, as long as it is large enough and `parallelism`
+/// allows it, then applies `kernel` to each part and merges the results with `combine`.
+///
+/// The matrix is only split along its rows if `split_rows` is `true`, and along its columns if
+/// `split_cols` is `true`.
+fn reduce<T: ComplexField, R: Send>(
+    matrix: MatRef<'_, T>,
+    parallelism: Parallelism,
+    split_rows: bool,
+    split_cols: bool,
+    kernel: &(impl Sync + Fn(MatRef<'_, T>) -> R),
+    combine: &(impl Sync + Fn(R, R) -> R),
+) -> R {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+
+    let along_cols = split_cols && n >= 2 && (n >= m || !split_rows);
+    let along_rows = split_rows && m >= 2 && !along_cols;
+
+    if parallelism == Parallelism::None
+        || m.saturating_mul(n) < PARALLEL_THRESHOLD
+        || !(along_cols || along_rows)
+    {
+        return pulp::Arch::new().dispatch(
+            #[inline(always)]
+            || kernel(matrix),
+        );
+    }
+
+    let (lhs, rhs) = if along_cols {
+        matrix.split_at_col(n / 2)
+    } else {
+        matrix.split_at_row(m / 2)
+    };
+
+    let mut lhs_result = None;
+    let mut rhs_result = None;
+    join_raw(
+        |parallelism| {
+            lhs_result = Some(reduce(
+                lhs,
+                parallelism,
+                split_rows,
+                split_cols,
+                kernel,
+                combine,
+            ));
+        },
+        |parallelism| {
+            rhs_result = Some(reduce(
+                rhs,
+                parallelism,
+                split_rows,
+                split_cols,
+                kernel,
+                combine,
+            ));
+        },
+        parallelism,
+    );
+    combine(lhs_result.unwrap(), rhs_result.unwrap())
+}
+
+fn max_abs<T: ComplexField>(matrix: MatRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    reduce(
+        col_major(matrix),
+        parallelism,
+        true,
+        true,
+        &|matrix| {
+            let mut max = T::Real::zero();
+            for j in 0..matrix.ncols() {
+                for_each_in_col(matrix, j, |x| max = max_nan(max, x.abs()));
+            }
+            max
+        },
+        &|lhs, rhs| max_nan(lhs, rhs),
+    )
+}
+
+fn sum_abs<T: ComplexField>(matrix: MatRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    reduce(
+        col_major(matrix),
+        parallelism,
+        true,
+        true,
+        &|matrix| {
+            let mut sum = T::Real::zero();
+            for j in 0..matrix.ncols() {
+                for_each_in_col(matrix, j, |x| sum = sum + x.abs());
+            }
+            sum
+        },
+        &|lhs, rhs| lhs + rhs,
+    )
+}
+
+/// Computes the largest sum of the absolute values of the elements of a column.
+fn max_col_sum<T: ComplexField>(matrix: MatRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    let zero = T::Real::zero();
+    if matrix.row_stride().unsigned_abs() <= matrix.col_stride().unsigned_abs() {
+        reduce(
+            matrix,
+            parallelism,
+            false,
+            true,
+            &|matrix| {
+                let mut max = zero;
+                for j in 0..matrix.ncols() {
+                    let mut sum = zero;
+                    for_each_in_col(matrix, j, |x| sum = sum + x.abs());
+                    max = max_nan(max, sum);
+                }
+                max
+            },
+            &|lhs, rhs| max_nan(lhs, rhs),
+        )
+    } else {
+        // the column sums of a row major matrix are the row sums of its column major transpose,
+        // which are accumulated a few rows at a time
+        reduce(
+            matrix.transpose(),
+            parallelism,
+            true,
+            false,
+            &|matrix| {
+                let m = matrix.nrows();
+                let n = matrix.ncols();
+                let mut max = zero;
+                let mut i = 0;
+                while i < m {
+                    let len = ROW_CHUNK.min(m - i);
+                    let chunk = matrix.submatrix(i, 0, len, n);
+                    let mut sums = [zero; ROW_CHUNK];
+                    for j in 0..n {
+                        let mut k = 0;
+                        for_each_in_col(chunk, j, |x| {
+                            sums[k] = sums[k] + x.abs();
+                            k += 1;
+                        });
+                    }
+                    for &sum in &sums[..len] {
+                        max = max_nan(max, sum);
+                    }
+                    i += len;
+                }
+                max
+            },
+            &|lhs, rhs| max_nan(lhs, rhs),
+        )
+    }
+}
+
+/// Computes the scaled sum of squares of the absolute values of the elements of `matrix`.
+///
+/// The result is a pair $(s, q)$ such that $s^2 q = \sum_{ij} |a_{ij}|^2$, where $s$ is the
+/// largest absolute value of the real and imaginary parts of the elements of the matrix. This
+/// representation can't overflow nor underflow unless the elements themselves do, and can be
+/// used to combine the squared norms of several matrices before taking the square root.
+pub fn scaled_sum_of_squares<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    parallelism: Parallelism,
+) -> (T::Real, T::Real) {
+    let zero = T::Real::zero();
+    reduce(
+        col_major(matrix),
+        parallelism,
+        true,
+        true,
+        &|matrix| {
+            let mut scale = zero;
+            for j in 0..matrix.ncols() {
+                for_each_in_col(matrix, j, |x| {
+                    let (re, im) = x.into_real_imag();
+                    scale = max_nan(scale, max_nan(re.abs(), im.abs()));
+                });
+            }
+            if scale == zero || scale.partial_cmp(&scale).is_none() {
+                return (scale, zero);
+            }
+            if !scale.is_finite() {
+                return (scale, T::Real::one());
+            }
+
+            // multiplying by the inverse is faster, but it overflows for subnormal scales
+            let mut ssq = zero;
+            if scale >= T::Real::min_positive() {
+                let inv = scale.inv();
+                for j in 0..matrix.ncols() {
+                    for_each_in_col(matrix, j, |x| {
+                        let (re, im) = x.into_real_imag();
+                        let (re, im) = (re * inv, im * inv);
+                        ssq = ssq + (re * re + im * im);
+                    });
+                }
+            } else {
+                for j in 0..matrix.ncols() {
+                    for_each_in_col(matrix, j, |x| {
+                        let (re, im) = x.into_real_imag();
+                        let (re, im) = (re / scale, im / scale);
+                        ssq = ssq + (re * re + im * im);
+                    });
+                }
+            }
+            (scale, ssq)
+        },
+        &|lhs, rhs| merge_sum_of_squares(lhs, rhs),
+    )
+}
+
+/// Computes the Frobenius norm of `matrix`, i.e., the square root of the sum of the squared
+/// absolute values of its elements.
+pub fn norm_frobenius<T: ComplexField>(matrix: MatRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    let (scale, ssq) = scaled_sum_of_squares(matrix, parallelism);
+    scale * ssq.sqrt()
+}
+
+/// Computes the 1-norm of `matrix`, i.e., the largest sum of the absolute values of the elements
+/// of one of its columns.
+pub fn norm_one<T: ComplexField>(matrix: MatRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    max_col_sum(matrix, parallelism)
+}
+
+/// Computes the infinity norm of `matrix`, i.e., the largest sum of the absolute values of the
+/// elements of one of its rows.
+pub fn norm_inf<T: ComplexField>(matrix: MatRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    max_col_sum(matrix.transpose(), parallelism)
+}
+
+/// Computes the max norm of `matrix`, i.e., the largest absolute value of its elements.
+///
+/// This is not a consistent matrix norm.
+pub fn norm_max<T: ComplexField>(matrix: MatRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    max_abs(matrix, parallelism)
+}
+
+/// Computes the 1-norm of `col`, i.e., the sum of the absolute values of its elements.
+pub fn col_norm_l1<T: ComplexField>(col: ColRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    sum_abs(col.as_2d(), parallelism)
+}
+
+/// Computes the euclidean norm of `col`.
+pub fn col_norm_l2<T: ComplexField>(col: ColRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    norm_frobenius(col.as_2d(), parallelism)
+}
+
+/// Computes the infinity norm of `col`, i.e., the largest absolute value of its elements.
+pub fn col_norm_inf<T: ComplexField>(col: ColRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    max_abs(col.as_2d(), parallelism)
+}
+
+/// Computes the 1-norm of `row`, i.e., the sum of the absolute values of its elements.
+pub fn row_norm_l1<T: ComplexField>(row: RowRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    sum_abs(row.as_2d(), parallelism)
+}
+
+/// Computes the euclidean norm of `row`.
+pub fn row_norm_l2<T: ComplexField>(row: RowRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    norm_frobenius(row.as_2d(), parallelism)
+}
+
+/// Computes the infinity norm of `row`, i.e., the largest absolute value of its elements.
+pub fn row_norm_inf<T: ComplexField>(row: RowRef<'_, T>, parallelism: Parallelism) -> T::Real {
+    max_abs(row.as_2d(), parallelism)
+}
+
+/// Parameters of [`norm_two_estimate`].
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct NormTwoEstimateParams {
+    /// Relative change of the estimate below which the iteration stops. Defaults to `1e-6`.
+    pub tolerance: Option<f64>,
+    /// Maximum number of iterations. Defaults to `100`.
+    pub max_iterations: Option<usize>,
+}
+
+/// Computes the size and alignment of required workspace for estimating the 2-norm of a matrix
+/// with dimensions $m\times n$.
+pub fn norm_two_estimate_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([temp_mat_req::<T>(ncols, 1)?, temp_mat_req::<T>(nrows, 1)?])
+}
+
+/// Estimates the 2-norm of `matrix`, i.e., its largest singular value, using the power iteration
+/// on $A^*A$.
+///
+/// The starting vector is made of the 1-norms of the columns of the matrix. Each iteration costs
+/// two matrix-vector products, and the estimate, which is always a lower bound of the 2-norm,
+/// increases monotonically until its relative change is below the tolerance provided in
+/// `params`, or the maximum number of iterations is reached.
+///
+/// # Panics
+///
+/// - Panics if the provided memory in `stack` is insufficient.
+pub fn norm_two_estimate<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: NormTwoEstimateParams,
+) -> T::Real {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let zero = T::Real::zero();
+
+    let tolerance = T::Real::from_f64(params.tolerance.unwrap_or(1e-6));
+    let max_iterations = params.max_iterations.unwrap_or(100);
+
+    temp_mat_uninit! {
+        let (mut x, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut y, _) = unsafe { temp_mat_uninit::<T>(m, 1, stack) };
+    }
+
+    for j in 0..n {
+        x[(j, 0)] = T::from_real(sum_abs(matrix.col(j).as_2d(), Parallelism::None));
+    }
+
+    let mut norm_x = norm_frobenius(x.rb(), parallelism);
+    if norm_x == zero || norm_x.partial_cmp(&norm_x).is_none() {
+        return norm_x;
+    }
+
+    let mut estimate = zero;
+    for _ in 0..max_iterations {
+        // x <- x / |x|
+        let inv = norm_x.inv();
+        x.rb_mut().cwise().for_each(|e| *e = e.scale(inv));
+
+        // y <- A x
+        matmul(
+            y.rb_mut(),
+            Conj::No,
+            matrix,
+            Conj::No,
+            x.rb(),
+            Conj::No,
+            None,
+            T::one(),
+            parallelism,
+        );
+        let previous = estimate;
+        estimate = norm_frobenius(y.rb(), parallelism);
+        if estimate == zero || estimate.partial_cmp(&estimate).is_none() {
+            return estimate;
+        }
+
+        let delta = estimate - previous;
+        if delta.abs() <= tolerance * estimate {
+            break;
+        }
+
+        // x <- A^* y
+        matmul(
+            x.rb_mut(),
+            Conj::No,
+            matrix.transpose(),
+            Conj::Yes,
+            y.rb(),
+            Conj::No,
+            None,
+            T::one(),
+            parallelism,
+        );
+        norm_x = norm_frobenius(x.rb(), parallelism);
+        if norm_x == zero {
+            break;
+        }
+    }
+
+    estimate
+}
+
+/// Maximum number of iterations of [`norm_one_estimate`].
+const NORM_ONE_ESTIMATE_MAX_ITERATIONS: usize = 5;
+
+/// Replaces each element of `x` by its sign, and returns `true` if the signs are unchanged from
+/// the ones stored in `sign`, which is then updated.
+fn update_sign<T: ComplexField>(x: ColMut<'_, T>, sign: ColMut<'_, T>) -> bool {
+    let mut x = x;
+    let mut sign = sign;
+    let mut unchanged = true;
+    for i in 0..x.nrows() {
+        let abs = x[i].abs();
+        let s = if abs == T::Real::zero() {
+            T::one()
+        } else {
+            x[i].scale(abs.inv())
+        };
+        if (s - sign[i]).abs() != T::Real::zero() {
+            unchanged = false;
+        }
+        sign[i] = s;
+        x[i] = s;
+    }
+    unchanged
+}
+
+fn index_of_max_abs<T: ComplexField>(x: ColRef<'_, T>) -> usize {
+    let mut max = T::Real::zero();
+    let mut idx = 0;
+    for (i, &e) in x.into_iter().enumerate() {
+        let abs = e.abs();
+        if abs > max {
+            max = abs;
+            idx = i;
+        }
+    }
+    idx
+}
+
+/// Estimates the 1-norm of a square matrix $B$ with Hager's method, as refined by Higham, given
+/// two functions that respectively overwrite their argument $x$ with $Bx$ and $B^*x$, using the
+/// provided workspace.
+///
+/// This is typically used to estimate the 1-norm of the inverse of a matrix from its
+/// decomposition, without forming the inverse. The estimate is a lower bound that is exact in
+/// most cases, and it costs a few applications of $B$ and $B^*$.
+///
+/// `x` and `sign` are used as workspace, and their length must be equal to the dimension of $B$.
+/// `stack` is passed to `apply` and `apply_adjoint`.
+///
+/// # Panics
+///
+/// - Panics if `x` and `sign` don't have the same length.
+#[track_caller]
+pub fn norm_one_estimate<T: ComplexField>(
+    x: ColMut<'_, T>,
+    sign: ColMut<'_, T>,
+    mut apply: impl FnMut(ColMut<'_, T>, DynStack<'_>),
+    mut apply_adjoint: impl FnMut(ColMut<'_, T>, DynStack<'_>),
+    stack: DynStack<'_>,
+) -> T::Real {
+    let n = x.nrows();
+    fancy_assert!(sign.nrows() == n);
+    if n == 0 {
+        return T::Real::zero();
+    }
+
+    let mut x = x;
+    let mut sign = sign;
+    let mut stack = stack;
+
+    let one = T::Real::one();
+    let n_real = T::Real::from_f64(n as f64);
+
+    // x = B [1/n, ..., 1/n]
+    let inv_n = T::from_real(n_real.inv());
+    x.rb_mut().cwise().for_each(|e| *e = inv_n);
+    apply(x.rb_mut(), stack.rb_mut());
+
+    let mut norm = sum_abs(x.rb().as_2d(), Parallelism::None);
+    if n > 1 {
+        sign.rb_mut().cwise().for_each(|e| *e = T::zero());
+        update_sign(x.rb_mut(), sign.rb_mut());
+        apply_adjoint(x.rb_mut(), stack.rb_mut());
+        let mut j = index_of_max_abs(x.rb());
+
+        for _ in 1..NORM_ONE_ESTIMATE_MAX_ITERATIONS {
+            // x = B e_j
+            x.rb_mut().cwise().for_each(|e| *e = T::zero());
+            x[j] = T::one();
+            apply(x.rb_mut(), stack.rb_mut());
+
+            let prev_norm = norm;
+            norm = sum_abs(x.rb().as_2d(), Parallelism::None);
+            if norm <= prev_norm {
+                norm = prev_norm;
+                break;
+            }
+            if update_sign(x.rb_mut(), sign.rb_mut()) {
+                break;
+            }
+
+            apply_adjoint(x.rb_mut(), stack.rb_mut());
+            let prev_j = j;
+            j = index_of_max_abs(x.rb());
+            if x[prev_j].abs() >= x[j].abs() {
+                break;
+            }
+        }
+
+        // alternative estimate, which guards against the cases where the iteration above
+        // performs poorly: x_i = (-1)^i (1 + i / (n - 1))
+        let denom = (n_real - one).inv();
+        let mut alternating = one;
+        for i in 0..n {
+            let i_real = T::Real::from_f64(i as f64);
+            x[i] = T::from_real(alternating * (one + i_real * denom));
+            alternating = -alternating;
+        }
+        apply(x.rb_mut(), stack.rb_mut());
+        let two = one + one;
+        let three = two + one;
+        let alternative_norm = two * sum_abs(x.rb().as_2d(), Parallelism::None) / (three * n_real);
+        if alternative_norm > norm {
+            norm = alternative_norm;
+        }
+    }
+
+    norm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{c64, Mat};
+    use assert_approx_eq::assert_approx_eq;
+    use dyn_stack::GlobalMemBuffer;
+    use rand::random;
+
+    fn naive_norms(a: MatRef<'_, c64>) -> (f64, f64, f64, f64) {
+        let m = a.nrows();
+        let n = a.ncols();
+        let fro = (0..n)
+            .flat_map(|j| (0..m).map(move |i| a[(i, j)].norm_sqr()))
+            .sum::<f64>()
+            .sqrt();
+        let one = (0..n)
+            .map(|j| (0..m).map(|i| a[(i, j)].norm()).sum::<f64>())
+            .fold(0.0, f64::max);
+        let inf = (0..m)
+            .map(|i| (0..n).map(|j| a[(i, j)].norm()).sum::<f64>())
+            .fold(0.0, f64::max);
+        let max = (0..n)
+            .flat_map(|j| (0..m).map(move |i| a[(i, j)].norm()))
+            .fold(0.0, f64::max);
+        (fro, one, inf, max)
+    }
+
+    #[test]
+    fn test_matrix_norms() {
+        for (m, n) in [
+            (0, 0),
+            (0, 4),
+            (1, 1),
+            (3, 5),
+            (40, 33),
+            (200, 1),
+            (1, 200),
+            (300, 300),
+        ] {
+            let a = Mat::with_dims(|_, _| c64::new(random(), random()), m, n);
+            for parallelism in [Parallelism::None, Parallelism::Rayon(0)] {
+                // also check row major views
+                for a in [a.as_ref(), a.as_ref().transpose()] {
+                    let (fro, one, inf, max) = naive_norms(a);
+                    assert_approx_eq!(norm_frobenius(a, parallelism), fro, 1e-10 * (1.0 + fro));
+                    assert_approx_eq!(norm_one(a, parallelism), one, 1e-10 * (1.0 + one));
+                    assert_approx_eq!(norm_inf(a, parallelism), inf, 1e-10 * (1.0 + inf));
+                    assert_approx_eq!(norm_max(a, parallelism), max);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_vector_norms() {
+        for n in [0, 1, 7, 100, 20000] {
+            let a = Mat::with_dims(|_, _| c64::new(random(), random()), n, 1);
+            let l1 = (0..n).map(|i| a[(i, 0)].norm()).sum::<f64>();
+            let l2 = (0..n).map(|i| a[(i, 0)].norm_sqr()).sum::<f64>().sqrt();
+            let inf = (0..n).map(|i| a[(i, 0)].norm()).fold(0.0, f64::max);
+
+            for parallelism in [Parallelism::None, Parallelism::Rayon(0)] {
+                let col = a.as_ref().col(0);
+                let row = a.as_ref().transpose().row(0);
+                assert_approx_eq!(col_norm_l1(col, parallelism), l1, 1e-10 * (1.0 + l1));
+                assert_approx_eq!(col_norm_l2(col, parallelism), l2, 1e-10 * (1.0 + l2));
+                assert_approx_eq!(col_norm_inf(col, parallelism), inf);
+                assert_approx_eq!(row_norm_l1(row, parallelism), l1, 1e-10 * (1.0 + l1));
+                assert_approx_eq!(row_norm_l2(row, parallelism), l2, 1e-10 * (1.0 + l2));
+                assert_approx_eq!(row_norm_inf(row, parallelism), inf);
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_overflow() {
+        for (value, expected) in [(1e300f64, 2e300), (1e-300, 2e-300), (1e-320, 2e-320)] {
+            let a = Mat::with_dims(|_, _| value, 4, 1);
+            let norm = norm_frobenius(a.as_ref(), Parallelism::None);
+            assert_approx_eq!(norm / expected, 1.0);
+
+            let (scale, ssq) = scaled_sum_of_squares(a.as_ref(), Parallelism::None);
+            assert!(scale == value);
+            assert_approx_eq!(ssq, 4.0);
+        }
+    }
+
+    #[test]
+    fn test_nan() {
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), 10, 10);
+        a[(3, 7)] = f64::NAN;
+        assert!(norm_frobenius(a.as_ref(), Parallelism::None).is_nan());
+        assert!(norm_one(a.as_ref(), Parallelism::None).is_nan());
+        assert!(norm_inf(a.as_ref(), Parallelism::None).is_nan());
+        assert!(norm_max(a.as_ref(), Parallelism::None).is_nan());
+
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), 10, 10);
+        a[(3, 7)] = f64::INFINITY;
+        a[(5, 2)] = f64::NEG_INFINITY;
+        assert!(norm_frobenius(a.as_ref(), Parallelism::None) == f64::INFINITY);
+        assert!(col_norm_l2(a.as_ref().col(7), Parallelism::None) == f64::INFINITY);
+        assert!(row_norm_l2(a.as_ref().row(3), Parallelism::None) == f64::INFINITY);
+        assert!(norm_one(a.as_ref(), Parallelism::None) == f64::INFINITY);
+
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), 1000, 1000);
+        a[(0, 0)] = f64::INFINITY;
+        a[(999, 999)] = f64::INFINITY;
+        assert!(norm_frobenius(a.as_ref(), Parallelism::Rayon(4)) == f64::INFINITY);
+    }
+
+    #[test]
+    fn test_norm_two_estimate() {
+        for (m, n) in [(1, 1), (4, 4), (10, 6), (6, 10), (100, 80)] {
+            // rank one matrix u v^*, whose 2-norm is |u| |v|
+            let u = Mat::with_dims(|_, _| c64::new(random(), random()), m, 1);
+            let v = Mat::with_dims(|_, _| c64::new(random(), random()), n, 1);
+            let mut a = Mat::zeros(m, n);
+            matmul(
+                a.as_mut(),
+                Conj::No,
+                u.as_ref(),
+                Conj::No,
+                v.as_ref().transpose(),
+                Conj::Yes,
+                None,
+                c64::one(),
+                Parallelism::None,
+            );
+            let expected = norm_frobenius(u.as_ref(), Parallelism::None)
+                * norm_frobenius(v.as_ref(), Parallelism::None);
+
+            let estimate = norm_two_estimate(
+                a.as_ref(),
+                Parallelism::None,
+                DynStack::new(&mut GlobalMemBuffer::new(
+                    norm_two_estimate_req::<c64>(m, n).unwrap(),
+                )),
+                Default::default(),
+            );
+            assert_approx_eq!(estimate, expected, 1e-8 * expected);
+
+            // general matrix, the estimate is bounded by the Frobenius norm and the 2-norm
+            // bounds
+            let a = Mat::with_dims(|_, _| c64::new(random(), random()), m, n);
+            let estimate = norm_two_estimate(
+                a.as_ref(),
+                Parallelism::None,
+                DynStack::new(&mut GlobalMemBuffer::new(
+                    norm_two_estimate_req::<c64>(m, n).unwrap(),
+                )),
+                Default::default(),
+            );
+            let fro = norm_frobenius(a.as_ref(), Parallelism::None);
+            let one = norm_one(a.as_ref(), Parallelism::None);
+            let inf = norm_inf(a.as_ref(), Parallelism::None);
+            assert!(estimate <= fro * (1.0 + 1e-12));
+            assert!(estimate >= fro / (m.min(n) as f64).sqrt() * (1.0 - 1e-12));
+            assert!(estimate <= (one * inf).sqrt() * (1.0 + 1e-12));
+        }
+
+        let a = Mat::<f64>::zeros(3, 4);
+        let estimate = norm_two_estimate(
+            a.as_ref(),
+            Parallelism::None,
+            DynStack::new(&mut GlobalMemBuffer::new(
+                norm_two_estimate_req::<f64>(3, 4).unwrap(),
+            )),
+            Default::default(),
+        );
+        assert!(estimate == 0.0);
+    }
+
+    fn mul_in_place(a: MatRef<'_, c64>, conj_a: Conj, x: ColMut<'_, c64>) {
+        let mut x = x;
+        let mut ax = Mat::zeros(a.nrows(), 1);
+        matmul(
+            ax.as_mut(),
+            Conj::No,
+            a,
+            conj_a,
+            x.rb().as_2d(),
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        for i in 0..x.nrows() {
+            x[i] = ax[(i, 0)];
+        }
+    }
+
+    #[test]
+    fn test_norm_one_estimate() {
+        for n in [0, 1, 2, 4, 10, 50] {
+            let a = Mat::with_dims(|_, _| c64::new(random(), random()), n, n);
+            let exact = norm_one(a.as_ref(), Parallelism::None);
+
+            let mut x = Mat::zeros(n, 1);
+            let mut sign = Mat::zeros(n, 1);
+            let estimate = norm_one_estimate(
+                x.as_mut().col(0),
+                sign.as_mut().col(0),
+                |x, _| mul_in_place(a.as_ref(), Conj::No, x),
+                |x, _| mul_in_place(a.as_ref().transpose(), Conj::Yes, x),
+                DynStack::new(&mut []),
+            );
+
+            // the estimate is a lower bound, that is exact for most small matrices
+            assert!(estimate <= exact * (1.0 + 1e-12));
+            assert!(estimate >= exact / 3.0);
+        }
+
+        // the estimate is exact for diagonal matrices
+        let n = 8;
+        let d = (0..n).map(|i| (i as f64) - 2.5).collect::<Vec<_>>();
+        let mut x = Mat::zeros(n, 1);
+        let mut sign = Mat::zeros(n, 1);
+        let scale = |x: ColMut<'_, f64>, _: DynStack<'_>| {
+            let mut x = x;
+            for i in 0..n {
+                x[i] *= d[i];
+            }
+        };
+        let estimate = norm_one_estimate(
+            x.as_mut().col(0),
+            sign.as_mut().col(0),
+            scale,
+            scale,
+            DynStack::new(&mut []),
+        );
+        assert_approx_eq!(estimate, 4.5);
+    }
+}
@@ -1,7 +1,7 @@
 use assert2::{assert as fancy_assert, debug_assert as fancy_debug_assert};
 use reborrow::*;
 
-use crate::{MatMut, MatRef};
+use crate::{ColMut, MatMut, MatRef};
 
 #[track_caller]
 #[inline]
@@ -168,6 +168,165 @@ impl<'short, 'a> ReborrowMut<'short> for PermutationIndicesMut<'a> {
     }
 }
 
+/// Owned permutation of the indices `0..n`, storing both the forward and the inverse index
+/// arrays.
+///
+/// The permutation is interpreted the same way as [`PermutationIndicesRef`] in [`permute_rows`]:
+/// permuting the rows of a matrix $A$ by $P$ produces the matrix whose $i$-th row is the
+/// `forward[i]`-th row of $A$.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Permutation {
+    forward: Box<[usize]>,
+    inverse: Box<[usize]>,
+}
+
+impl Permutation {
+    /// Creates a permutation from its forward index array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `forward` isn't a permutation of `0..forward.len()`.
+    #[track_caller]
+    pub fn new(forward: Vec<usize>) -> Self {
+        let n = forward.len();
+        let mut inverse = vec![n; n];
+        for (i, &p) in forward.iter().enumerate() {
+            fancy_assert!(p < n, "permutation index out of bounds");
+            fancy_assert!(inverse[p] == n, "permutation index is repeated");
+            inverse[p] = i;
+        }
+        Self {
+            forward: forward.into_boxed_slice(),
+            inverse: inverse.into_boxed_slice(),
+        }
+    }
+
+    /// Creates a permutation from its forward and inverse index arrays, without checking the
+    /// validity of the inputs.
+    ///
+    /// # Safety
+    ///
+    /// `forward` and `inverse` must have the same length, be valid permutations, and be inverse
+    /// permutations of each other.
+    #[inline]
+    pub unsafe fn new_unchecked(forward: Box<[usize]>, inverse: Box<[usize]>) -> Self {
+        fancy_debug_assert!(forward.len() == inverse.len());
+        Self { forward, inverse }
+    }
+
+    /// Returns the identity permutation of length `n`.
+    pub fn identity(n: usize) -> Self {
+        let forward: Box<[usize]> = (0..n).collect();
+        Self {
+            inverse: forward.clone(),
+            forward,
+        }
+    }
+
+    /// Creates a permutation of length `n` from a sequence of transpositions, in the format
+    /// produced by the pivoted decompositions, e.g.,
+    /// `faer_qr::col_pivoting::compute::qr_in_place`: for each `k`, the `k`-th index is swapped
+    /// with the `transpositions[k]`-th index, in increasing order of `k`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `transpositions.len() > n`.
+    /// - Panics if any of the transposition indices is out of bounds.
+    #[track_caller]
+    pub fn from_transpositions(n: usize, transpositions: &[usize]) -> Self {
+        fancy_assert!(transpositions.len() <= n);
+        let mut forward: Box<[usize]> = (0..n).collect();
+        for (k, &t) in transpositions.iter().enumerate() {
+            fancy_assert!(t < n, "transposition index out of bounds");
+            forward.swap(k, t);
+        }
+        let mut inverse = forward.clone();
+        for (i, &p) in forward.iter().enumerate() {
+            inverse[p] = i;
+        }
+        Self { forward, inverse }
+    }
+
+    /// Returns the length of the permutation.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    /// Returns `true` if the permutation has length zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+
+    /// Returns a view over the permutation.
+    #[inline]
+    pub fn as_ref(&self) -> PermutationIndicesRef<'_> {
+        PermutationIndicesRef {
+            forward: &self.forward,
+            inverse: &self.inverse,
+        }
+    }
+
+    /// Returns the forward and inverse index arrays.
+    #[inline]
+    pub fn into_arrays(self) -> (Box<[usize]>, Box<[usize]>) {
+        (self.forward, self.inverse)
+    }
+
+    /// Returns the inverse permutation.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        Self {
+            forward: self.inverse,
+            inverse: self.forward,
+        }
+    }
+
+    /// Returns the composition `self * rhs`, i.e., the permutation that is equivalent to
+    /// permuting by `rhs`, then by `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `rhs` don't have the same length.
+    #[track_caller]
+    pub fn compose(&self, rhs: &Self) -> Self {
+        fancy_assert!(self.len() == rhs.len());
+        let forward: Box<[usize]> = self.forward.iter().map(|&i| rhs.forward[i]).collect();
+        let inverse: Box<[usize]> = rhs.inverse.iter().map(|&i| self.inverse[i]).collect();
+        Self { forward, inverse }
+    }
+
+    /// Returns `true` if the permutation can be written as an even number of transpositions.
+    pub fn is_even(&self) -> bool {
+        let n = self.len();
+        let mut visited = vec![false; n];
+        let mut n_cycles = 0;
+        for i in 0..n {
+            if visited[i] {
+                continue;
+            }
+            n_cycles += 1;
+            let mut j = i;
+            while !visited[j] {
+                visited[j] = true;
+                j = self.forward[j];
+            }
+        }
+        (n - n_cycles) & 1 == 0
+    }
+
+    /// Returns the sign of the permutation, which is `1` if it is even, and `-1` otherwise.
+    #[inline]
+    pub fn sign(&self) -> i32 {
+        if self.is_even() {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
 /// Computes a symmetric permutation of the source matrix using the given permutation, and stores
 /// the result in the destination matrix.
 ///
@@ -284,3 +443,147 @@ pub fn permute_rows<T: Copy>(
 
     unsafe { permute_rows_unchecked(dst, src, perm_indices) };
 }
+
+/// Permutes the rows of the matrix in place, so that the $i$-th row of the result is the
+/// `perm_indices.into_arrays().0[i]`-th row of the input, without allocating a copy of the matrix.
+///
+/// # Panics
+///
+/// Panics if the length of the permutation isn't equal to the number of rows of the matrix.
+#[track_caller]
+pub fn permute_rows_in_place<T>(mat: MatMut<'_, T>, perm_indices: PermutationIndicesRef<'_>) {
+    let mut mat = mat;
+    let m = mat.nrows();
+    fancy_assert!(
+        perm_indices.len() == m,
+        "permutation must have the same length as the number of rows of the matrix"
+    );
+
+    let perm = perm_indices.into_arrays().0;
+    let mut visited = vec![false; m];
+    for i in 0..m {
+        if visited[i] {
+            continue;
+        }
+        // follow the cycle starting at i, moving each row to its destination with a swap
+        visited[i] = true;
+        let mut j = i;
+        loop {
+            let k = perm[j];
+            if k == i {
+                break;
+            }
+            swap_rows(mat.rb_mut(), j, k);
+            visited[k] = true;
+            j = k;
+        }
+    }
+}
+
+/// Permutes the columns of the matrix in place, so that the $j$-th column of the result is the
+/// `perm_indices.into_arrays().0[j]`-th column of the input, without allocating a copy of the
+/// matrix.
+///
+/// # Panics
+///
+/// Panics if the length of the permutation isn't equal to the number of columns of the matrix.
+#[track_caller]
+#[inline]
+pub fn permute_cols_in_place<T>(mat: MatMut<'_, T>, perm_indices: PermutationIndicesRef<'_>) {
+    permute_rows_in_place(mat.transpose(), perm_indices)
+}
+
+/// Permutes the elements of the column vector in place, so that the $i$-th element of the result
+/// is the `perm_indices.into_arrays().0[i]`-th element of the input.
+///
+/// # Panics
+///
+/// Panics if the length of the permutation isn't equal to the number of rows of the vector.
+#[track_caller]
+#[inline]
+pub fn permute_col_in_place<T>(col: ColMut<'_, T>, perm_indices: PermutationIndicesRef<'_>) {
+    permute_rows_in_place(col.as_2d(), perm_indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mat;
+
+    #[test]
+    fn test_owned_permutation() {
+        let p = Permutation::new(vec![2, 0, 3, 1]);
+        fancy_assert!(p.as_ref().into_arrays().1 == &[1, 3, 0, 2]);
+        fancy_assert!(p.compose(&p.clone().inverse()) == Permutation::identity(4));
+        fancy_assert!(p.clone().inverse().compose(&p) == Permutation::identity(4));
+        // single 4-cycle
+        fancy_assert!(p.sign() == -1);
+        fancy_assert!(Permutation::identity(5).sign() == 1);
+        fancy_assert!(Permutation::new(vec![1, 0, 3, 2]).is_even());
+
+        let q = Permutation::new(vec![3, 1, 0, 2]);
+        let pq = p.compose(&q);
+        fancy_assert!(pq.sign() == p.sign() * q.sign());
+
+        // permuting by pq is the same as permuting by q, then by p
+        let a = Mat::with_dims(|i, j| (10 * i + j) as f64, 4, 3);
+        let mut lhs = a.clone();
+        permute_rows_in_place(lhs.as_mut(), pq.as_ref());
+        let mut rhs = a.clone();
+        permute_rows_in_place(rhs.as_mut(), q.as_ref());
+        permute_rows_in_place(rhs.as_mut(), p.as_ref());
+        fancy_assert!(lhs.as_ref() == rhs.as_ref());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_permutation() {
+        Permutation::new(vec![0, 2, 2]);
+    }
+
+    #[test]
+    fn test_from_transpositions() {
+        let n = 6;
+        let transpositions = [3, 1, 5, 5, 4];
+        let p = Permutation::from_transpositions(n, &transpositions);
+
+        let a = Mat::with_dims(|i, j| (i + 7 * j) as f64, 3, n);
+        let mut swapped = a.clone();
+        for (k, &t) in transpositions.iter().enumerate() {
+            swap_cols(swapped.as_mut(), k, t);
+        }
+        fancy_assert!(p.sign() == -1);
+
+        let mut permuted = Mat::zeros(3, n);
+        permute_cols(permuted.as_mut(), a.as_ref(), p.as_ref());
+        fancy_assert!(permuted.as_ref() == swapped.as_ref());
+
+        let mut in_place = a.clone();
+        permute_cols_in_place(in_place.as_mut(), p.as_ref());
+        fancy_assert!(in_place.as_ref() == swapped.as_ref());
+
+        permute_cols_in_place(in_place.as_mut(), p.as_ref().inverse());
+        fancy_assert!(in_place.as_ref() == a.as_ref());
+    }
+
+    #[test]
+    fn test_permute_in_place() {
+        let n = 37;
+        let forward: Vec<usize> = (0..n).map(|i| (i * 11 + 5) % n).collect();
+        let p = Permutation::new(forward);
+        let a = Mat::with_dims(|i, j| (i + 100 * j) as f64, n, 4);
+
+        let mut expected = Mat::zeros(n, 4);
+        permute_rows(expected.as_mut(), a.as_ref(), p.as_ref());
+
+        let mut in_place = a.clone();
+        permute_rows_in_place(in_place.as_mut(), p.as_ref());
+        fancy_assert!(in_place.as_ref() == expected.as_ref());
+
+        let mut col = a.clone();
+        permute_col_in_place(col.as_mut().col(2), p.as_ref());
+        for i in 0..n {
+            fancy_assert!(col[(i, 2)] == expected[(i, 2)]);
+        }
+    }
+}
@@ -0,0 +1,442 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    householder::{
+        apply_block_househodler_on_the_left, apply_househodler_on_the_left, default_blocksize,
+        make_householder_factor_unblocked, make_householder_factor_unblocked_req,
+        make_householder_in_place,
+    },
+    mul::matmul,
+    temp_mat_req, temp_mat_uninit, ColMut, ComplexField, Conj, MatMut, Parallelism,
+};
+use reborrow::*;
+
+fn disable_blocking(n: usize) -> bool {
+    n < 48
+}
+
+/// Computes the size and alignment of required workspace for reducing a square matrix to upper
+/// Hessenberg form.
+pub fn make_hessenberg_in_place_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    let size = dim.saturating_sub(1);
+    let bs = default_blocksize(size, size).min(size);
+    StackReq::try_any_of([
+        StackReq::try_all_of([temp_mat_req::<T>(dim, 1)?, temp_mat_req::<T>(dim, 1)?])?,
+        StackReq::try_all_of([
+            temp_mat_req::<T>(size, bs)?,
+            temp_mat_req::<T>(size, 1)?,
+            temp_mat_req::<T>(size, 1)?,
+            StackReq::try_any_of([
+                temp_mat_req::<T>(1, 1)?,
+                StackReq::try_all_of([
+                    temp_mat_req::<T>(size, bs)?,
+                    temp_mat_req::<T>(bs, bs)?,
+                    StackReq::try_any_of([
+                        make_householder_factor_unblocked_req::<T>(bs)?,
+                        temp_mat_req::<T>(bs, dim)?.try_and(temp_mat_req::<T>(bs, dim)?)?,
+                    ])?,
+                ])?,
+            ])?,
+        ])?,
+    ])
+}
+
+fn make_hessenberg_in_place_unblocked<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_factor: ColMut<'_, T>,
+    start: usize,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = matrix.nrows();
+
+    let mut matrix = matrix;
+    let mut householder_factor = householder_factor;
+    let mut stack = stack;
+
+    for k in start..n.saturating_sub(1) {
+        let m = n - k - 1;
+        let (_, top_right, bottom_left, bottom_right) = matrix.rb_mut().split_at(k + 1, k + 1);
+        let (mut head, mut tail) = bottom_left.col(k).split_at(1);
+
+        let mut tail_squared_norm = T::Real::zero();
+        for &elem in tail.rb() {
+            tail_squared_norm = tail_squared_norm + (elem * elem.conj()).real();
+        }
+
+        // the column is already in hessenberg form
+        if tail_squared_norm == T::Real::zero() {
+            householder_factor[k] = T::zero();
+            continue;
+        }
+
+        let (tau, beta) = make_householder_in_place(tail.rb_mut(), head[0], tail_squared_norm);
+        head[0] = beta;
+        householder_factor[k] = tau;
+
+        // A22 <- H A22
+        let mut bottom_right = bottom_right;
+        apply_househodler_on_the_left(bottom_right.rb_mut(), tail.rb(), tau, stack.rb_mut());
+
+        temp_mat_uninit! {
+            let (mut v, stack) = unsafe { temp_mat_uninit::<T>(m, 1, stack.rb_mut()) };
+            let (mut w, _) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        }
+
+        // v = [1; essential]
+        v[(0, 0)] = T::one();
+        for i in 1..m {
+            v[(i, 0)] = tail[i - 1];
+        }
+
+        // [A02; A22] <- [A02; A22] H = [A02; A22] - tau ([A02; A22] v) v^*
+        let (mut w_top, mut w_bot) = w.rb_mut().split_at_row(k + 1);
+        matmul(
+            w_top.rb_mut(),
+            Conj::No,
+            top_right.rb(),
+            Conj::No,
+            v.rb(),
+            Conj::No,
+            None,
+            T::one(),
+            parallelism,
+        );
+        matmul(
+            w_bot.rb_mut(),
+            Conj::No,
+            bottom_right.rb(),
+            Conj::No,
+            v.rb(),
+            Conj::No,
+            None,
+            T::one(),
+            parallelism,
+        );
+        matmul(
+            top_right,
+            Conj::No,
+            w_top.rb(),
+            Conj::No,
+            v.rb().transpose(),
+            Conj::Yes,
+            Some(T::one()),
+            -tau,
+            parallelism,
+        );
+        matmul(
+            bottom_right,
+            Conj::No,
+            w_bot.rb(),
+            Conj::No,
+            v.rb().transpose(),
+            Conj::Yes,
+            Some(T::one()),
+            -tau,
+            parallelism,
+        );
+    }
+}
+
+/// Computes the householder reflections `start..start + bs` of the Hessenberg reduction of
+/// `matrix`, and applies them on both sides of the matrix as block reflections.
+///
+/// The matrix is left untouched while the reflections of the panel are computed. Each column of
+/// the partially reduced matrix is instead obtained on demand by applying the reflections
+/// computed so far to a matrix-vector product with the original matrix, and the reflections are
+/// accumulated in a temporary basis.
+fn make_hessenberg_panel<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_factor: ColMut<'_, T>,
+    start: usize,
+    bs: usize,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = matrix.nrows();
+    let k = start;
+    let m = n - k - 1;
+
+    let mut matrix = matrix;
+    let mut householder_factor = householder_factor.subrows(k, bs);
+    let mut stack = stack;
+
+    temp_mat_uninit! {
+        let (mut basis, stack) = unsafe { temp_mat_uninit::<T>(m, bs, stack.rb_mut()) };
+        let (v, stack) = unsafe { temp_mat_uninit::<T>(m, 1, stack) };
+        let (col, mut stack) = unsafe { temp_mat_uninit::<T>(m, 1, stack) };
+    }
+    let mut v = v.col(0);
+    let mut col = col.col(0);
+
+    for j in 0..bs {
+        // rows k + 1.. of the column k + j of the partially reduced matrix, computed as
+        // L A L^* e_{k + j}, where L is the product of the reflections computed so far
+        if j == 0 {
+            col.rb_mut()
+                .cwise()
+                .zip(matrix.rb().col(k).subrows(k + 1, m))
+                .for_each(|dst, src| *dst = *src);
+        } else {
+            v.rb_mut().cwise().for_each(|e| *e = T::zero());
+            v[j - 1] = T::one();
+            for i in (0..j).rev() {
+                apply_househodler_on_the_left(
+                    v.rb_mut().subrows(i, m - i).as_2d(),
+                    basis.rb().col(i).subrows(i + 1, m - i - 1),
+                    householder_factor[i],
+                    stack.rb_mut(),
+                );
+            }
+            matmul(
+                col.rb_mut().as_2d(),
+                Conj::No,
+                matrix.rb().submatrix(k + 1, k + 1, m, m),
+                Conj::No,
+                v.rb().as_2d(),
+                Conj::No,
+                None,
+                T::one(),
+                parallelism,
+            );
+            for i in 0..j {
+                apply_househodler_on_the_left(
+                    col.rb_mut().subrows(i, m - i).as_2d(),
+                    basis.rb().col(i).subrows(i + 1, m - i - 1),
+                    householder_factor[i],
+                    stack.rb_mut(),
+                );
+            }
+        }
+
+        let (mut head, mut tail) = col.rb_mut().subrows(j, m - j).split_at(1);
+
+        let mut tail_squared_norm = T::Real::zero();
+        for &elem in tail.rb() {
+            tail_squared_norm = tail_squared_norm + (elem * elem.conj()).real();
+        }
+
+        if tail_squared_norm == T::Real::zero() {
+            householder_factor[j] = T::zero();
+        } else {
+            let (tau, beta) = make_householder_in_place(tail.rb_mut(), head[0], tail_squared_norm);
+            head[0] = beta;
+            householder_factor[j] = tau;
+        }
+
+        // the elements above the diagonal of the basis are ignored by the block reflections, so
+        // they can hold the already reduced part of the column
+        basis
+            .rb_mut()
+            .col(j)
+            .cwise()
+            .zip(col.rb())
+            .for_each(|dst, src| *dst = *src);
+    }
+
+    {
+        temp_mat_uninit! {
+            let (mut conj_basis, stack) = unsafe { temp_mat_uninit::<T>(m, bs, stack.rb_mut()) };
+            let (t, mut stack) = unsafe { temp_mat_uninit::<T>(bs, bs, stack) };
+        }
+        let mut t = t.transpose();
+
+        // A22 <- Q^* A22, where Q = I - V T V^*
+        for i in 0..bs {
+            t[(i, i)] = householder_factor[i];
+        }
+        make_householder_factor_unblocked(t.rb_mut(), basis.rb(), stack.rb_mut());
+        apply_block_househodler_on_the_left(
+            matrix.rb_mut().submatrix(k + 1, k + 1, m, m),
+            basis.rb(),
+            t.rb(),
+            false,
+            parallelism,
+            stack.rb_mut(),
+        );
+
+        // [A02; A22] <- [A02; A22] Q, computed as the transpose of conj(Q)^* [A02; A22]^T, where
+        // conj(Q) = I - conj(V) conj(T) conj(V)^*
+        conj_basis
+            .rb_mut()
+            .cwise()
+            .zip(basis.rb())
+            .for_each(|dst, src| *dst = (*src).conj());
+        for i in 0..bs {
+            t[(i, i)] = householder_factor[i];
+        }
+        make_householder_factor_unblocked(t.rb_mut(), conj_basis.rb(), stack.rb_mut());
+        apply_block_househodler_on_the_left(
+            matrix.rb_mut().submatrix(0, k + 1, n, m).transpose(),
+            conj_basis.rb(),
+            t.rb(),
+            false,
+            parallelism,
+            stack.rb_mut(),
+        );
+    }
+
+    for j in 0..bs {
+        matrix
+            .rb_mut()
+            .col(k + j)
+            .subrows(k + 1, m)
+            .cwise()
+            .zip(basis.rb().col(j))
+            .for_each(|dst, src| *dst = *src);
+    }
+}
+
+/// Reduces a square matrix $A$ to upper Hessenberg form $H$, such that
+/// $$A = QHQ^*,$$
+/// where $Q$ is a unitary matrix, stored as a sequence of householder reflections.
+///
+/// On exit, the upper triangular part and the first subdiagonal of `matrix` contain $H$. The
+/// essential parts of the householder reflections are stored below the first subdiagonal, and
+/// their coefficients are stored in `householder_factor`, so that the $k$-th reflection acts on
+/// the rows and columns with indices greater than $k$, and $Q = H_0 H_1 \dots H_{n - 2}$.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if `householder_factor` doesn't have $\max(n - 1, 0)$ rows, where $n$ is the
+///   dimension of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn make_hessenberg_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_factor: ColMut<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(matrix.nrows() == matrix.ncols());
+    let n = matrix.nrows();
+    fancy_assert!(householder_factor.nrows() == n.saturating_sub(1));
+
+    let mut matrix = matrix;
+    let mut householder_factor = householder_factor;
+    let mut stack = stack;
+
+    let mut k = 0;
+    while k + 1 < n && !disable_blocking(n - k) {
+        let size = n - k - 1;
+        let bs = default_blocksize(size, size).min(size);
+        make_hessenberg_panel(
+            matrix.rb_mut(),
+            householder_factor.rb_mut(),
+            k,
+            bs,
+            parallelism,
+            stack.rb_mut(),
+        );
+        k += bs;
+    }
+
+    make_hessenberg_in_place_unblocked(matrix, householder_factor, k, parallelism, stack);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{
+        c64,
+        householder::{
+            apply_householder_sequence_on_the_left, apply_householder_sequence_on_the_left_req,
+        },
+        Mat,
+    };
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn test_hessenberg<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for n in [0, 1, 2, 3, 4, 10, 33, 64, 150] {
+            let a = Mat::with_dims(|_, _| gen(), n, n);
+
+            let mut hess = a.clone();
+            let mut householder = Mat::zeros(n.saturating_sub(1), 1);
+            make_hessenberg_in_place(
+                hess.as_mut(),
+                householder.as_mut().col(0),
+                Parallelism::Rayon(0),
+                make_stack!(make_hessenberg_in_place_req::<T>(n, Parallelism::Rayon(0)).unwrap()),
+            );
+
+            let mut h = Mat::zeros(n, n);
+            for j in 0..n {
+                for i in 0..n.min(j + 2) {
+                    h[(i, j)] = hess[(i, j)];
+                }
+            }
+
+            // Q
+            let mut q = Mat::zeros(n, n);
+            for i in 0..n {
+                q[(i, i)] = T::one();
+            }
+            if n > 0 {
+                apply_householder_sequence_on_the_left(
+                    q.as_mut().submatrix(1, 0, n - 1, n),
+                    hess.as_ref().submatrix(1, 0, n - 1, n - 1),
+                    householder.as_ref().col(0),
+                    true,
+                    Parallelism::Rayon(0),
+                    make_stack!(
+                        apply_householder_sequence_on_the_left_req::<T>(n - 1, n - 1, n).unwrap()
+                    ),
+                );
+            }
+
+            let mut qh = Mat::zeros(n, n);
+            let mut reconstructed = Mat::zeros(n, n);
+            matmul(
+                qh.as_mut(),
+                Conj::No,
+                q.as_ref(),
+                Conj::No,
+                h.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+            matmul(
+                reconstructed.as_mut(),
+                Conj::No,
+                qh.as_ref(),
+                Conj::No,
+                q.as_ref().transpose(),
+                Conj::Yes,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+
+            for j in 0..n {
+                for i in 0..n {
+                    fancy_assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hessenberg_f64() {
+        test_hessenberg(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_hessenberg_c64() {
+        test_hessenberg(|| c64::new(random(), random()), 1e-10);
+    }
+}
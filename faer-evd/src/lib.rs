@@ -0,0 +1,965 @@
+//! The eigenvalue decomposition of a hermitian matrix $A$ is a decomposition of the form
+//! $$A = U S U^*,$$
+//! where $U$ is a unitary matrix whose columns are the eigenvectors of $A$, and $S$ is a real
+//! diagonal matrix containing its eigenvalues.
+//!
+//! The decomposition is computed by first reducing $A$ to tridiagonal form using householder
+//! reflections, then computing the eigendecomposition of the tridiagonal matrix using the implicit
+//! QL algorithm.
+//!
+//! For a general square matrix $A$, the eigenvalue decomposition is a decomposition of the form
+//! $$AU = US,$$
+//! where $S$ is a diagonal matrix containing the (possibly complex) eigenvalues of $A$, and the
+//! columns of $U$ are the corresponding eigenvectors.
+//!
+//! The decomposition is computed by first reducing $A$ to upper Hessenberg form using householder
+//! reflections, then computing its Schur form using the Francis double shift QR algorithm for
+//! real matrices, or the single shift QR algorithm for complex matrices. The eigenvectors are
+//! then obtained by back substitution.
+
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    error::ConvergenceError,
+    householder::{
+        apply_househodler_on_the_left, apply_householder_sequence_on_the_left,
+        apply_householder_sequence_on_the_left_req,
+    },
+    mul::triangular::{self, BlockStructure},
+    temp_mat_req, temp_mat_uninit, temp_mat_zeroed, ColMut, ComplexField, Conj, MatMut, MatRef,
+    Parallelism, RealField,
+};
+use num_complex::Complex;
+use reborrow::*;
+
+pub mod hessenberg;
+pub mod schur;
+pub mod tridiag;
+pub mod tridiag_real_evd;
+
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct SymmetricEvdParams {}
+
+/// Computes the size and alignment of required workspace for performing a hermitian eigenvalue
+/// decomposition.
+pub fn compute_hermitian_evd_req<T: ComplexField>(
+    dim: usize,
+    compute_eigenvectors: bool,
+    parallelism: Parallelism,
+    params: SymmetricEvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = params;
+    let n = dim;
+    StackReq::try_all_of([
+        temp_mat_req::<T>(n, n)?,
+        temp_mat_req::<T>(n.saturating_sub(1), 1)?,
+        StackReq::try_new::<T::Real>(n)?,
+        StackReq::try_new::<T::Real>(n.saturating_sub(1))?,
+        if compute_eigenvectors {
+            StackReq::try_all_of([temp_mat_req::<T>(n, 1)?, temp_mat_req::<T::Real>(n, n)?])?
+        } else {
+            StackReq::default()
+        },
+        StackReq::try_any_of([
+            tridiag::tridiagonalize_in_place_req::<T>(n, parallelism)?,
+            temp_mat_req::<T>(n, 1)?,
+        ])?,
+    ])
+}
+
+/// Computes the eigenvalue decomposition of a hermitian input matrix $A$, such that
+/// $$A = U S U^*.$$
+///
+/// The eigenvalues are stored in `s` in ascending order. If `u` is provided, the corresponding
+/// eigenvectors are stored in its columns.
+///
+/// The input matrix is interpreted as hermitian and only the lower triangular part is read.
+///
+/// An error is returned if the QL iterations fail to converge, which may happen if the input
+/// contains non-finite values.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if `s` doesn't have the same number of rows as the dimension of the matrix.
+/// - Panics if `u` is provided and doesn't have the same dimensions as the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn compute_hermitian_evd<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    s: ColMut<'_, T::Real>,
+    u: Option<MatMut<'_, T>>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: SymmetricEvdParams,
+) -> Result<(), ConvergenceError> {
+    let _ = params;
+    fancy_assert!(matrix.nrows() == matrix.ncols());
+    let n = matrix.nrows();
+    fancy_assert!(s.nrows() == n);
+    if let Some(u) = &u {
+        fancy_assert!((u.nrows(), u.ncols()) == (n, n));
+    }
+
+    let mut s = s;
+    let mut u = u;
+    let mut stack = stack;
+
+    temp_mat_uninit! {
+        let (mut trid, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack.rb_mut()) };
+        let (mut householder, mut stack) =
+            unsafe { temp_mat_uninit::<T>(n.saturating_sub(1), 1, stack) };
+    }
+    let mut householder = householder.rb_mut().col(0);
+
+    trid.rb_mut()
+        .cwise()
+        .zip(matrix)
+        .for_each(|dst, src| *dst = *src);
+
+    tridiag::tridiagonalize_in_place(
+        trid.rb_mut(),
+        householder.rb_mut(),
+        parallelism,
+        stack.rb_mut(),
+    );
+    let trid = trid.into_const();
+
+    let (mut diag, stack) = stack.make_with(n, |i| trid[(i, i)].real());
+    let (mut offdiag, stack) = stack.make_with(n.saturating_sub(1), |i| trid[(i + 1, i)].abs());
+
+    match u.as_mut() {
+        None => tridiag_real_evd::compute_tridiag_real_evd(&mut diag, &mut offdiag, None)?,
+        Some(u) => {
+            // the tridiagonal matrix is unitarily similar to a real symmetric tridiagonal
+            // matrix, T = D S D^*, where D is diagonal and d_{k + 1} = d_k × t_{k + 1, k} / |t_{k + 1, k}|
+            temp_mat_uninit! {
+                let (mut d, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+            }
+            temp_mat_zeroed! {
+                let (mut z, mut stack) = temp_mat_zeroed::<T::Real>(n, n, stack);
+            }
+            let mut d = d.rb_mut().col(0);
+
+            for i in 0..n {
+                d[i] = if i == 0 {
+                    T::one()
+                } else {
+                    let e = trid[(i, i - 1)];
+                    let e_abs = offdiag[i - 1];
+                    if e_abs == T::Real::zero() {
+                        d[i - 1]
+                    } else {
+                        d[i - 1] * e.scale(e_abs.inv())
+                    }
+                };
+                z[(i, i)] = T::Real::one();
+            }
+
+            tridiag_real_evd::compute_tridiag_real_evd(&mut diag, &mut offdiag, Some(z.rb_mut()))?;
+
+            // U = Q D Z
+            for j in 0..n {
+                for i in 0..n {
+                    u[(i, j)] = d[i].scale(z[(i, j)]);
+                }
+            }
+            for k in (0..n.saturating_sub(1)).rev() {
+                apply_househodler_on_the_left(
+                    u.rb_mut().submatrix(k + 1, 0, n - k - 1, n),
+                    trid.col(k).split_at(k + 2).1,
+                    householder[k],
+                    stack.rb_mut(),
+                );
+            }
+        }
+    }
+
+    for i in 0..n {
+        s[i] = diag[i];
+    }
+
+    Ok(())
+}
+
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct EvdParams {}
+
+/// Computes the size and alignment of required workspace for performing an eigenvalue
+/// decomposition of a complex matrix.
+pub fn compute_evd_complex_req<T: ComplexField>(
+    dim: usize,
+    compute_eigenvectors: bool,
+    parallelism: Parallelism,
+    params: EvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = params;
+    let n = dim;
+    let n_vec = if compute_eigenvectors { n } else { 0 };
+    StackReq::try_all_of([
+        temp_mat_req::<T>(n, n)?,
+        temp_mat_req::<T>(n.saturating_sub(1), 1)?,
+        temp_mat_req::<T>(n_vec, n_vec)?,
+        temp_mat_req::<T>(n_vec, n_vec)?,
+        StackReq::try_any_of([
+            hessenberg::make_hessenberg_in_place_req::<T>(n, parallelism)?,
+            apply_householder_sequence_on_the_left_req::<T>(
+                n.saturating_sub(1),
+                n.saturating_sub(1),
+                n_vec,
+            )?,
+        ])?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for performing an eigenvalue
+/// decomposition of a real matrix.
+pub fn compute_evd_real_req<T: RealField>(
+    dim: usize,
+    compute_eigenvectors: bool,
+    parallelism: Parallelism,
+    params: EvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = params;
+    let n = dim;
+    let n_vec = if compute_eigenvectors { n } else { 0 };
+    StackReq::try_all_of([
+        temp_mat_req::<T>(n, n)?,
+        temp_mat_req::<T>(n.saturating_sub(1), 1)?,
+        temp_mat_req::<T>(n, 1)?,
+        temp_mat_req::<T>(n, 1)?,
+        temp_mat_req::<T>(n_vec, n_vec)?,
+        temp_mat_req::<Complex<T>>(n_vec, n_vec)?,
+        temp_mat_req::<Complex<T>>(n_vec, n_vec)?,
+        temp_mat_req::<Complex<T>>(n_vec, n_vec)?,
+        StackReq::try_any_of([
+            hessenberg::make_hessenberg_in_place_req::<T>(n, parallelism)?,
+            apply_householder_sequence_on_the_left_req::<T>(
+                n.saturating_sub(1),
+                n.saturating_sub(1),
+                n_vec,
+            )?,
+        ])?,
+    ])
+}
+
+/// Reduces `matrix` to upper Hessenberg form $H = Q^* A Q$, and stores $Q$ in `q` if it is
+/// provided.
+fn make_hessenberg<T: ComplexField>(
+    h: MatMut<'_, T>,
+    householder: ColMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    q: Option<MatMut<'_, T>>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = matrix.nrows();
+    let mut h = h;
+    let mut householder = householder;
+    let mut stack = stack;
+
+    h.rb_mut()
+        .cwise()
+        .zip(matrix)
+        .for_each(|dst, src| *dst = *src);
+    hessenberg::make_hessenberg_in_place(
+        h.rb_mut(),
+        householder.rb_mut(),
+        parallelism,
+        stack.rb_mut(),
+    );
+
+    if let Some(mut q) = q {
+        q.rb_mut().cwise().for_each(|e| *e = T::zero());
+        q.rb_mut().diagonal().cwise().for_each(|e| *e = T::one());
+        if n > 0 {
+            apply_householder_sequence_on_the_left(
+                q.submatrix(1, 0, n - 1, n),
+                h.rb().submatrix(1, 0, n - 1, n - 1),
+                householder.rb(),
+                true,
+                parallelism,
+                stack,
+            );
+        }
+    }
+}
+
+/// Computes the eigenvectors $U = Z X$ of a matrix with the complex Schur form $A = Z T Z^*$,
+/// where $X$ is the upper triangular matrix whose columns are the eigenvectors of $T$. The
+/// eigenvectors are normalized to have a unit norm.
+fn compute_eigenvectors_from_schur<T: ComplexField>(
+    u: MatMut<'_, T>,
+    t: MatRef<'_, T>,
+    z: MatRef<'_, T>,
+    x: MatMut<'_, T>,
+    parallelism: Parallelism,
+) {
+    let n = t.nrows();
+    let mut u = u;
+    let mut x = x;
+
+    let epsilon = T::Real::epsilon();
+    let min_positive = T::Real::min_positive();
+    let mut t_norm = T::Real::zero();
+    for j in 0..n {
+        for i in 0..j + 1 {
+            let abs = t[(i, j)].abs();
+            if abs > t_norm {
+                t_norm = abs;
+            }
+        }
+    }
+
+    // solve (T - λ_k I) x_k = 0, with x_k[k] = 1, using back substitution
+    x.rb_mut().cwise().for_each(|e| *e = T::zero());
+    for k in 0..n {
+        let eigenvalue = t[(k, k)];
+        let mut small = epsilon * t_norm;
+        if small < min_positive {
+            small = min_positive;
+        }
+
+        x[(k, k)] = T::one();
+        for i in (0..k).rev() {
+            let mut acc = T::zero();
+            for j in i + 1..k + 1 {
+                acc = acc + t[(i, j)] * x[(j, k)];
+            }
+            let mut denom = t[(i, i)] - eigenvalue;
+            if denom.abs() < small {
+                denom = T::from_real(small);
+            }
+            x[(i, k)] = -(acc / denom);
+        }
+    }
+
+    triangular::matmul(
+        u.rb_mut(),
+        BlockStructure::Rectangular,
+        Conj::No,
+        z,
+        BlockStructure::Rectangular,
+        Conj::No,
+        x.rb(),
+        BlockStructure::TriangularUpper,
+        Conj::No,
+        None,
+        T::one(),
+        parallelism,
+    );
+
+    for j in 0..n {
+        let mut squared_norm = T::Real::zero();
+        for i in 0..n {
+            squared_norm = squared_norm + (u[(i, j)] * u[(i, j)].conj()).real();
+        }
+        let factor = squared_norm.sqrt().inv();
+        u.rb_mut()
+            .col(j)
+            .cwise()
+            .for_each(|e| *e = (*e).scale(factor));
+    }
+}
+
+/// Computes the eigenvalue decomposition of a complex square input matrix $A$, such that
+/// $$AU = US,$$
+/// where $S$ is a diagonal matrix containing the eigenvalues of $A$, and the columns of $U$ are
+/// the corresponding eigenvectors, normalized to have a unit norm.
+///
+/// The eigenvalues are stored in `s`. If `u` is provided, the eigenvectors are stored in its
+/// columns.
+///
+/// An error is returned if the QR algorithm fails to converge, which may happen if the input
+/// contains non-finite values. In that case, the contents of `s` and `u` are unspecified.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if `s` doesn't have the same number of rows as the dimension of the matrix.
+/// - Panics if `u` is provided and doesn't have the same dimensions as the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn compute_evd_complex<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    s: ColMut<'_, T>,
+    u: Option<MatMut<'_, T>>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: EvdParams,
+) -> Result<(), ConvergenceError> {
+    let _ = params;
+    fancy_assert!(matrix.nrows() == matrix.ncols());
+    let n = matrix.nrows();
+    fancy_assert!(s.nrows() == n);
+    if let Some(u) = &u {
+        fancy_assert!((u.nrows(), u.ncols()) == (n, n));
+    }
+
+    let n_vec = if u.is_some() { n } else { 0 };
+    let mut stack = stack;
+
+    temp_mat_uninit! {
+        let (mut h, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack.rb_mut()) };
+        let (mut householder, stack) =
+            unsafe { temp_mat_uninit::<T>(n.saturating_sub(1), 1, stack) };
+        let (mut z, stack) = unsafe { temp_mat_uninit::<T>(n_vec, n_vec, stack) };
+        let (mut x, mut stack) = unsafe { temp_mat_uninit::<T>(n_vec, n_vec, stack) };
+    }
+
+    make_hessenberg(
+        h.rb_mut(),
+        householder.rb_mut().col(0),
+        matrix,
+        if u.is_some() { Some(z.rb_mut()) } else { None },
+        parallelism,
+        stack.rb_mut(),
+    );
+
+    match u {
+        None => schur::compute_complex_schur(h.rb_mut(), None, s)?,
+        Some(u) => {
+            schur::compute_complex_schur(h.rb_mut(), Some(z.rb_mut()), s)?;
+            compute_eigenvectors_from_schur(u, h.rb(), z.rb(), x.rb_mut(), parallelism);
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the eigenvalue decomposition of a real square input matrix $A$, such that
+/// $$AU = US,$$
+/// where $S$ is a diagonal matrix containing the eigenvalues of $A$, and the columns of $U$ are
+/// the corresponding eigenvectors, normalized to have a unit norm.
+///
+/// The eigenvalues are stored in `s`, with complex conjugate pairs of eigenvalues stored
+/// consecutively, the one with the positive imaginary part first. If `u` is provided, the
+/// eigenvectors are stored in its columns.
+///
+/// An error is returned if the QR algorithm fails to converge, which may happen if the input
+/// contains non-finite values. In that case, the contents of `s` and `u` are unspecified.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if `s` doesn't have the same number of rows as the dimension of the matrix.
+/// - Panics if `u` is provided and doesn't have the same dimensions as the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn compute_evd_real<T: RealField>(
+    matrix: MatRef<'_, T>,
+    s: ColMut<'_, Complex<T>>,
+    u: Option<MatMut<'_, Complex<T>>>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: EvdParams,
+) -> Result<(), ConvergenceError>
+where
+    Complex<T>: ComplexField<Real = T>,
+{
+    let _ = params;
+    fancy_assert!(matrix.nrows() == matrix.ncols());
+    let n = matrix.nrows();
+    fancy_assert!(s.nrows() == n);
+    if let Some(u) = &u {
+        fancy_assert!((u.nrows(), u.ncols()) == (n, n));
+    }
+
+    let n_vec = if u.is_some() { n } else { 0 };
+    let mut s = s;
+    let mut stack = stack;
+
+    temp_mat_uninit! {
+        let (mut h, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack.rb_mut()) };
+        let (mut householder, stack) =
+            unsafe { temp_mat_uninit::<T>(n.saturating_sub(1), 1, stack) };
+        let (mut w_re, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut w_im, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut z, stack) = unsafe { temp_mat_uninit::<T>(n_vec, n_vec, stack) };
+        let (mut t_complex, stack) = unsafe { temp_mat_uninit::<Complex<T>>(n_vec, n_vec, stack) };
+        let (mut z_complex, stack) = unsafe { temp_mat_uninit::<Complex<T>>(n_vec, n_vec, stack) };
+        let (mut x, mut stack) = unsafe { temp_mat_uninit::<Complex<T>>(n_vec, n_vec, stack) };
+    }
+
+    make_hessenberg(
+        h.rb_mut(),
+        householder.rb_mut().col(0),
+        matrix,
+        if u.is_some() { Some(z.rb_mut()) } else { None },
+        parallelism,
+        stack.rb_mut(),
+    );
+
+    schur::compute_real_schur(
+        h.rb_mut(),
+        if u.is_some() { Some(z.rb_mut()) } else { None },
+        w_re.rb_mut().col(0),
+        w_im.rb_mut().col(0),
+    )?;
+
+    for i in 0..n {
+        s[i] = Complex::new(w_re[(i, 0)], w_im[(i, 0)]);
+    }
+
+    if let Some(u) = u {
+        t_complex
+            .rb_mut()
+            .cwise()
+            .zip(h.rb())
+            .for_each(|dst, src| *dst = Complex::from_real(*src));
+        z_complex
+            .rb_mut()
+            .cwise()
+            .zip(z.rb())
+            .for_each(|dst, src| *dst = Complex::from_real(*src));
+
+        // reduce the 2×2 blocks of the real schur form with unitary rotations, to obtain the
+        // complex schur form
+        for m in 1..n {
+            let sub = h[(m, m - 1)];
+            if sub == T::zero() {
+                continue;
+            }
+
+            // the first column of G^* is the eigenvector of the block that corresponds to s[m - 1]
+            let mu = s[m - 1] - t_complex[(m, m)];
+            let r = ((mu * mu.conj()).real() + sub * sub).sqrt();
+            let c = mu.scale(r.inv());
+            let sn = sub / r;
+
+            // G = [conj(c) s; -s c]
+            for j in m - 1..n {
+                let a = t_complex[(m - 1, j)];
+                let b = t_complex[(m, j)];
+                t_complex[(m - 1, j)] = c.conj() * a + b.scale(sn);
+                t_complex[(m, j)] = c * b - a.scale(sn);
+            }
+            for i in 0..m + 1 {
+                let a = t_complex[(i, m - 1)];
+                let b = t_complex[(i, m)];
+                t_complex[(i, m - 1)] = a * c + b.scale(sn);
+                t_complex[(i, m)] = b * c.conj() - a.scale(sn);
+            }
+            for i in 0..n {
+                let a = z_complex[(i, m - 1)];
+                let b = z_complex[(i, m)];
+                z_complex[(i, m - 1)] = a * c + b.scale(sn);
+                z_complex[(i, m)] = b * c.conj() - a.scale(sn);
+            }
+            t_complex[(m, m - 1)] = Complex::zero();
+            t_complex[(m - 1, m - 1)] = s[m - 1];
+            t_complex[(m, m)] = s[m];
+        }
+
+        compute_eigenvectors_from_schur(u, t_complex.rb(), z_complex.rb(), x.rb_mut(), parallelism);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, mul::matmul, Conj, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn norm<T: ComplexField>(a: MatRef<'_, T>) -> T::Real {
+        let mut sum = T::Real::zero();
+        for j in 0..a.ncols() {
+            for i in 0..a.nrows() {
+                sum = sum + (a[(i, j)] * a[(i, j)].conj()).real();
+            }
+        }
+        sum.sqrt()
+    }
+
+    fn test_evd<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for n in [0, 1, 2, 3, 4, 10, 33, 64, 100] {
+            let mut a = Mat::with_dims(|_, _| gen(), n, n);
+            for j in 0..n {
+                a[(j, j)] = T::from_real(a[(j, j)].real());
+                for i in 0..j {
+                    a[(i, j)] = a[(j, i)].conj();
+                }
+            }
+
+            let mut s = Mat::zeros(n, 1);
+            let mut u = Mat::zeros(n, n);
+            compute_hermitian_evd(
+                a.as_ref(),
+                s.as_mut().col(0),
+                Some(u.as_mut()),
+                Parallelism::Rayon(0),
+                make_stack!(compute_hermitian_evd_req::<T>(
+                    n,
+                    true,
+                    Parallelism::Rayon(0),
+                    Default::default()
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .unwrap();
+
+            // A U = U S
+            let mut au = Mat::zeros(n, n);
+            matmul(
+                au.as_mut(),
+                Conj::No,
+                a.as_ref(),
+                Conj::No,
+                u.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+            let mut residual = au.clone();
+            for j in 0..n {
+                for i in 0..n {
+                    residual[(i, j)] = au[(i, j)] - u[(i, j)].scale(s[(j, 0)]);
+                }
+            }
+            fancy_assert!(norm(residual.as_ref()) <= epsilon * norm(a.as_ref()));
+
+            // U^* U = I
+            let mut uhu = Mat::zeros(n, n);
+            matmul(
+                uhu.as_mut(),
+                Conj::No,
+                u.as_ref().transpose(),
+                Conj::Yes,
+                u.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+            for j in 0..n {
+                uhu[(j, j)] = uhu[(j, j)] - T::one();
+            }
+            fancy_assert!(norm(uhu.as_ref()) <= epsilon * norm(u.as_ref()));
+
+            for j in 1..n {
+                fancy_assert!(s[(j - 1, 0)] <= s[(j, 0)]);
+            }
+
+            // the eigenvalues are the same when the eigenvectors are not requested
+            let mut s_only = Mat::zeros(n, 1);
+            compute_hermitian_evd(
+                a.as_ref(),
+                s_only.as_mut().col(0),
+                None,
+                Parallelism::Rayon(0),
+                make_stack!(compute_hermitian_evd_req::<T>(
+                    n,
+                    false,
+                    Parallelism::Rayon(0),
+                    Default::default()
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .unwrap();
+            for j in 0..n {
+                fancy_assert!((s_only[(j, 0)] - s[(j, 0)]).abs() <= epsilon * norm(a.as_ref()));
+            }
+        }
+    }
+
+    fn test_evd_diagonal<T: ComplexField>() {
+        let n = 10;
+
+        // diagonal entries in descending order: n, n - 1, ..., 1
+        let mut a = Mat::zeros(n, n);
+        let mut value = T::zero();
+        for i in (0..n).rev() {
+            value = value + T::one();
+            a[(i, i)] = value;
+        }
+
+        let mut s = Mat::zeros(n, 1);
+        let mut u = Mat::zeros(n, n);
+        compute_hermitian_evd(
+            a.as_ref(),
+            s.as_mut().col(0),
+            Some(u.as_mut()),
+            Parallelism::None,
+            make_stack!(compute_hermitian_evd_req::<T>(
+                n,
+                true,
+                Parallelism::None,
+                Default::default()
+            )
+            .unwrap()),
+            Default::default(),
+        )
+        .unwrap();
+
+        let mut value = T::Real::zero();
+        for j in 0..n {
+            value = value + T::Real::one();
+            fancy_assert!(s[(j, 0)] == value);
+            for i in 0..n {
+                let expected = if i + j == n - 1 {
+                    T::Real::one()
+                } else {
+                    T::Real::zero()
+                };
+                fancy_assert!(u[(i, j)].abs() == expected);
+            }
+        }
+    }
+
+    fn check_general_evd<T: ComplexField>(
+        a: MatRef<'_, T>,
+        s: MatRef<'_, T>,
+        u: MatRef<'_, T>,
+        epsilon: T::Real,
+    ) {
+        let n = a.nrows();
+
+        let a_norm = norm(a) + T::Real::one();
+
+        let mut au = Mat::zeros(n, n);
+        matmul(
+            au.as_mut(),
+            Conj::No,
+            a,
+            Conj::No,
+            u,
+            Conj::No,
+            None,
+            T::one(),
+            Parallelism::None,
+        );
+
+        for j in 0..n {
+            let mut squared_norm = T::Real::zero();
+            for i in 0..n {
+                squared_norm = squared_norm + (u[(i, j)] * u[(i, j)].conj()).real();
+                fancy_assert!((au[(i, j)] - u[(i, j)] * s[(j, 0)]).abs() < epsilon * a_norm);
+            }
+            fancy_assert!((squared_norm - T::Real::one()).abs() < epsilon);
+        }
+    }
+
+    fn test_complex_evd<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for n in [0, 1, 2, 3, 4, 10, 33, 64] {
+            let a = Mat::with_dims(|_, _| gen(), n, n);
+
+            let mut s = Mat::zeros(n, 1);
+            let mut u = Mat::zeros(n, n);
+            compute_evd_complex(
+                a.as_ref(),
+                s.as_mut().col(0),
+                Some(u.as_mut()),
+                Parallelism::Rayon(0),
+                make_stack!(compute_evd_complex_req::<T>(
+                    n,
+                    true,
+                    Parallelism::Rayon(0),
+                    Default::default()
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .unwrap();
+            check_general_evd(a.as_ref(), s.as_ref(), u.as_ref(), epsilon);
+
+            // the eigenvalues are the same when the eigenvectors are not requested
+            let mut s_only = Mat::zeros(n, 1);
+            compute_evd_complex(
+                a.as_ref(),
+                s_only.as_mut().col(0),
+                None,
+                Parallelism::Rayon(0),
+                make_stack!(compute_evd_complex_req::<T>(
+                    n,
+                    false,
+                    Parallelism::Rayon(0),
+                    Default::default()
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .unwrap();
+            for i in 0..n {
+                fancy_assert!((s_only[(i, 0)] - s[(i, 0)]).abs() < epsilon);
+            }
+        }
+    }
+
+    fn test_real_evd<T: RealField>(mut gen: impl FnMut() -> T, epsilon: T)
+    where
+        Complex<T>: ComplexField<Real = T>,
+    {
+        for n in [0, 1, 2, 3, 4, 10, 33, 64] {
+            let a = Mat::with_dims(|_, _| gen(), n, n);
+
+            let mut s = Mat::zeros(n, 1);
+            let mut u = Mat::zeros(n, n);
+            compute_evd_real(
+                a.as_ref(),
+                s.as_mut().col(0),
+                Some(u.as_mut()),
+                Parallelism::Rayon(0),
+                make_stack!(compute_evd_real_req::<T>(
+                    n,
+                    true,
+                    Parallelism::Rayon(0),
+                    Default::default()
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .unwrap();
+            let a_complex = Mat::with_dims(|i, j| Complex::from_real(a[(i, j)]), n, n);
+            check_general_evd(a_complex.as_ref(), s.as_ref(), u.as_ref(), epsilon);
+
+            // complex eigenvalues come in conjugate pairs
+            let mut i = 0;
+            while i < n {
+                let s0: Complex<T> = s[(i, 0)];
+                if s0.im == T::zero() {
+                    i += 1;
+                } else {
+                    fancy_assert!(s0.im > T::zero());
+                    fancy_assert!((s[(i + 1, 0)] - s0.conj()).abs() == T::zero());
+                    i += 2;
+                }
+            }
+
+            // the eigenvalues are the same when the eigenvectors are not requested
+            let mut s_only = Mat::zeros(n, 1);
+            compute_evd_real(
+                a.as_ref(),
+                s_only.as_mut().col(0),
+                None,
+                Parallelism::Rayon(0),
+                make_stack!(compute_evd_real_req::<T>(
+                    n,
+                    false,
+                    Parallelism::Rayon(0),
+                    Default::default()
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .unwrap();
+            for i in 0..n {
+                fancy_assert!((s_only[(i, 0)] - s[(i, 0)]).abs() < epsilon);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evd_f64() {
+        test_evd(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_evd_f32() {
+        test_evd(random::<f32>, 1e-3);
+    }
+
+    #[test]
+    fn test_evd_c64() {
+        test_evd(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_evd_c32() {
+        test_evd(|| c32::new(random(), random()), 1e-3);
+    }
+
+    #[test]
+    fn test_evd_diagonal_f64() {
+        test_evd_diagonal::<f64>();
+    }
+
+    #[test]
+    fn test_evd_diagonal_c64() {
+        test_evd_diagonal::<c64>();
+    }
+
+    #[test]
+    fn test_evd_non_finite() {
+        let n = 10;
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), n, n);
+        a[(4, 2)] = f64::NAN;
+
+        let mut s = Mat::zeros(n, 1);
+        let mut u = Mat::zeros(n, n);
+        fancy_assert!(compute_hermitian_evd(
+            a.as_ref(),
+            s.as_mut().col(0),
+            Some(u.as_mut()),
+            Parallelism::None,
+            make_stack!(compute_hermitian_evd_req::<f64>(
+                n,
+                true,
+                Parallelism::None,
+                Default::default()
+            )
+            .unwrap()),
+            Default::default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_real_evd_non_finite() {
+        let n = 10;
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), n, n);
+        a[(4, 2)] = f64::INFINITY;
+
+        let mut s = Mat::zeros(n, 1);
+        let mut u = Mat::zeros(n, n);
+        fancy_assert!(compute_evd_real(
+            a.as_ref(),
+            s.as_mut().col(0),
+            Some(u.as_mut()),
+            Parallelism::None,
+            make_stack!(compute_evd_real_req::<f64>(
+                n,
+                true,
+                Parallelism::None,
+                Default::default()
+            )
+            .unwrap()),
+            Default::default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_real_evd_f64() {
+        test_real_evd(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_real_evd_f32() {
+        test_real_evd(random::<f32>, 1e-3);
+    }
+
+    #[test]
+    fn test_complex_evd_c64() {
+        test_complex_evd(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_complex_evd_c32() {
+        test_complex_evd(|| c32::new(random(), random()), 1e-3);
+    }
+}
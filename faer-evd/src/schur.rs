@@ -0,0 +1,604 @@
+use assert2::assert as fancy_assert;
+use faer_core::{error::ConvergenceError, ColMut, ComplexField, MatMut, RealField};
+use reborrow::*;
+
+/// Maximum number of QR sweeps performed for each eigenvalue.
+const MAX_ITERATIONS_PER_EIGENVALUE: usize = 30;
+
+/// Computes the eigenvalues of the real $2 \times 2$ matrix
+/// $\begin{bmatrix} a & b \\\\ c & d \end{bmatrix}$, as `(re0, im0, re1, im1)`.
+///
+/// If the eigenvalues are complex, the one with the positive imaginary part is returned first.
+pub(crate) fn real_block_eigenvalues<T: RealField>(a: T, b: T, c: T, d: T) -> (T, T, T, T) {
+    let two = T::one() + T::one();
+    let half_trace = (a + d) / two;
+    let p = (a - d) / two;
+    let discriminant = p * p + b * c;
+    if discriminant >= T::zero() {
+        let root = discriminant.sqrt();
+        (half_trace + root, T::zero(), half_trace - root, T::zero())
+    } else {
+        let root = (-discriminant).sqrt();
+        (half_trace, root, half_trace, -root)
+    }
+}
+
+/// Reduces the $2 \times 2$ diagonal block of `h` starting at index `k` to upper triangular form
+/// with a plane rotation if its eigenvalues are real, and updates the rest of `h` and `z`
+/// accordingly.
+fn triangularize_real_block<T: RealField>(h: MatMut<'_, T>, z: Option<MatMut<'_, T>>, k: usize) {
+    let mut h = h;
+    let n = h.nrows();
+
+    let a = h[(k, k)];
+    let b = h[(k, k + 1)];
+    let c = h[(k + 1, k)];
+    let d = h[(k + 1, k + 1)];
+
+    let two = T::one() + T::one();
+    let p = (a - d) / two;
+    let discriminant = p * p + b * c;
+    if discriminant < T::zero() {
+        return;
+    }
+
+    // (mu, c) is an eigenvector of the block, where the sign of the root is chosen to avoid
+    // cancellation
+    let root = discriminant.sqrt();
+    let mu = if p >= T::zero() { p + root } else { p - root };
+    let r = (mu * mu + c * c).sqrt();
+    if r == T::zero() {
+        return;
+    }
+    let cs = mu / r;
+    let sn = c / r;
+
+    // H <- G^T H G, where G = [cs -sn; sn cs]
+    for j in k..n {
+        let x = h[(k, j)];
+        let y = h[(k + 1, j)];
+        h[(k, j)] = cs * x + sn * y;
+        h[(k + 1, j)] = cs * y - sn * x;
+    }
+    for i in 0..k + 2 {
+        let x = h[(i, k)];
+        let y = h[(i, k + 1)];
+        h[(i, k)] = cs * x + sn * y;
+        h[(i, k + 1)] = cs * y - sn * x;
+    }
+    h[(k + 1, k)] = T::zero();
+
+    if let Some(mut z) = z {
+        for i in 0..z.nrows() {
+            let x = z[(i, k)];
+            let y = z[(i, k + 1)];
+            z[(i, k)] = cs * x + sn * y;
+            z[(i, k + 1)] = cs * y - sn * x;
+        }
+    }
+}
+
+/// Returns the index of the start of the trailing unreduced block of the active window ending at
+/// `p`, setting the negligible subdiagonal element that separates it from the rest of the matrix
+/// to zero.
+fn find_unreduced_block_start<T: ComplexField>(h: MatMut<'_, T>, p: usize, norm: T::Real) -> usize {
+    let mut h = h;
+    let epsilon = T::Real::epsilon();
+    let min_positive = T::Real::min_positive();
+
+    let mut l = p;
+    while l > 0 {
+        let mut scale = h[(l - 1, l - 1)].abs() + h[(l, l)].abs();
+        if scale == T::Real::zero() {
+            scale = norm;
+        }
+        let sub = h[(l, l - 1)].abs();
+        if sub <= epsilon * scale || sub <= min_positive {
+            h[(l, l - 1)] = T::zero();
+            break;
+        }
+        l -= 1;
+    }
+    l
+}
+
+/// Sets the elements below the first subdiagonal to zero, and returns the sum of the absolute
+/// values of the remaining elements.
+fn clear_below_subdiagonal<T: ComplexField>(h: MatMut<'_, T>) -> T::Real {
+    let mut h = h;
+    let n = h.nrows();
+    let mut norm = T::Real::zero();
+    for j in 0..n {
+        for i in 0..n {
+            if i > j + 1 {
+                h[(i, j)] = T::zero();
+            } else {
+                norm = norm + h[(i, j)].abs();
+            }
+        }
+    }
+    norm
+}
+
+/// Computes a householder reflection $I - \tau v v^T$ with $v_0 = 1$, mapping the first `len`
+/// elements of `x` to a multiple of the first unit vector.
+fn make_real_reflector<T: RealField>(x: [T; 3], len: usize) -> ([T; 3], T) {
+    let mut tail_squared_norm = T::zero();
+    for &e in &x[1..len] {
+        tail_squared_norm = tail_squared_norm + e * e;
+    }
+    if tail_squared_norm == T::zero() {
+        return ([T::one(), T::zero(), T::zero()], T::zero());
+    }
+
+    let norm = (x[0] * x[0] + tail_squared_norm).sqrt();
+    let beta = if x[0] >= T::zero() { -norm } else { norm };
+    let head = x[0] - beta;
+    let mut v = [T::one(), T::zero(), T::zero()];
+    for i in 1..len {
+        v[i] = x[i] / head;
+    }
+    (v, (beta - x[0]) / beta)
+}
+
+/// Multiplies the rows `k..k + len` of the columns `cols` of `matrix` on the left by the
+/// reflection $I - \tau v v^T$.
+fn apply_real_reflector_on_the_left<T: RealField>(
+    matrix: MatMut<'_, T>,
+    v: [T; 3],
+    tau: T,
+    len: usize,
+    k: usize,
+    cols: core::ops::Range<usize>,
+) {
+    let mut matrix = matrix;
+    for j in cols {
+        let mut dot = T::zero();
+        for i in 0..len {
+            dot = dot + v[i] * matrix[(k + i, j)];
+        }
+        let dot = tau * dot;
+        for i in 0..len {
+            matrix[(k + i, j)] = matrix[(k + i, j)] - dot * v[i];
+        }
+    }
+}
+
+/// Multiplies the columns `k..k + len` of the rows `rows` of `matrix` on the right by the
+/// reflection $I - \tau v v^T$.
+fn apply_real_reflector_on_the_right<T: RealField>(
+    matrix: MatMut<'_, T>,
+    v: [T; 3],
+    tau: T,
+    len: usize,
+    k: usize,
+    rows: core::ops::Range<usize>,
+) {
+    apply_real_reflector_on_the_left(matrix.transpose(), v, tau, len, k, rows);
+}
+
+/// Computes the real Schur form $T$ of a real upper Hessenberg matrix $H$, using the Francis
+/// double shift QR algorithm, such that
+/// $$H = Z T Z^T,$$
+/// where $Z$ is an orthogonal matrix and $T$ is a quasi upper triangular matrix, with $1 \times 1$
+/// and $2 \times 2$ blocks on its diagonal. The eigenvalues of each $2 \times 2$ block are a pair
+/// of complex conjugate numbers.
+///
+/// On entry, the part of `h` below its first subdiagonal is ignored. On exit, `h` contains $T$,
+/// and the real and imaginary parts of the eigenvalues of $H$ are stored in `w_re` and `w_im`, in
+/// the order in which they appear on the diagonal of $T$, the eigenvalue with the positive
+/// imaginary part of each conjugate pair coming first.
+///
+/// If `z` is provided, it is multiplied on the right by $Z$.
+///
+/// An error is returned if one of the eigenvalues fails to converge within the maximum number of
+/// iterations, which may happen if the input contains non-finite values. In that case, the
+/// contents of `h`, `z` and `w_re`, `w_im` are unspecified.
+///
+/// # Panics
+///
+/// - Panics if `h` is not square.
+/// - Panics if `w_re` or `w_im` doesn't have $n$ rows, where $n$ is the dimension of `h`.
+/// - Panics if `z` is provided and doesn't have $n$ columns.
+#[track_caller]
+pub fn compute_real_schur<T: RealField>(
+    h: MatMut<'_, T>,
+    z: Option<MatMut<'_, T>>,
+    w_re: ColMut<'_, T>,
+    w_im: ColMut<'_, T>,
+) -> Result<(), ConvergenceError> {
+    fancy_assert!(h.nrows() == h.ncols());
+    let n = h.nrows();
+    fancy_assert!(w_re.nrows() == n);
+    fancy_assert!(w_im.nrows() == n);
+    if let Some(z) = &z {
+        fancy_assert!(z.ncols() == n);
+    }
+
+    let mut h = h;
+    let mut z = z;
+    let mut w_re = w_re;
+    let mut w_im = w_im;
+
+    let norm = clear_below_subdiagonal(h.rb_mut());
+    if !norm.is_finite() {
+        return Err(ConvergenceError { index: n - 1 });
+    }
+
+    let two = T::one() + T::one();
+    let three = two + T::one();
+    let four = two * two;
+    let exceptional_diag = three / four;
+    let exceptional_offdiag = -(four + three) / (four * four);
+
+    let mut end = n;
+    let mut iter = 0;
+    while end > 0 {
+        let p = end - 1;
+        let l = find_unreduced_block_start(h.rb_mut(), p, norm);
+
+        if l == p {
+            end -= 1;
+            iter = 0;
+            continue;
+        }
+        if l + 1 == p {
+            triangularize_real_block(h.rb_mut(), z.as_mut().map(|z| z.rb_mut()), l);
+            end -= 2;
+            iter = 0;
+            continue;
+        }
+        if iter == MAX_ITERATIONS_PER_EIGENVALUE {
+            return Err(ConvergenceError { index: p });
+        }
+        iter += 1;
+
+        // the double shift is given by the eigenvalues of the trailing 2×2 block, except for
+        // exceptional shifts that are used to break cycles
+        let (sum, prod) = if iter % 10 == 0 {
+            let s = h[(p, p - 1)].abs() + h[(p - 1, p - 2)].abs();
+            let h11 = exceptional_diag * s + h[(p, p)];
+            let h12 = exceptional_offdiag * s;
+            (h11 + h11, h11 * h11 - h12 * s)
+        } else {
+            (
+                h[(p - 1, p - 1)] + h[(p, p)],
+                h[(p - 1, p - 1)] * h[(p, p)] - h[(p - 1, p)] * h[(p, p - 1)],
+            )
+        };
+
+        // first column of (H - s0 I)(H - s1 I)
+        let mut x = h[(l, l)] * h[(l, l)] + h[(l, l + 1)] * h[(l + 1, l)] - sum * h[(l, l)] + prod;
+        let mut y = h[(l + 1, l)] * (h[(l, l)] + h[(l + 1, l + 1)] - sum);
+        let mut w = h[(l + 1, l)] * h[(l + 2, l + 1)];
+
+        // chase the bulge down the diagonal
+        for k in l..p - 1 {
+            let (v, tau) = make_real_reflector([x, y, w], 3);
+            let q = if k > l { k - 1 } else { l };
+            apply_real_reflector_on_the_left(h.rb_mut(), v, tau, 3, k, q..n);
+            if k > l {
+                h[(k + 1, k - 1)] = T::zero();
+                h[(k + 2, k - 1)] = T::zero();
+            }
+            let r = (k + 3).min(p);
+            apply_real_reflector_on_the_right(h.rb_mut(), v, tau, 3, k, 0..r + 1);
+            if let Some(z) = z.as_mut() {
+                let nrows = z.nrows();
+                apply_real_reflector_on_the_right(z.rb_mut(), v, tau, 3, k, 0..nrows);
+            }
+
+            x = h[(k + 1, k)];
+            y = h[(k + 2, k)];
+            if k + 3 <= p {
+                w = h[(k + 3, k)];
+            }
+        }
+
+        let (v, tau) = make_real_reflector([x, y, T::zero()], 2);
+        apply_real_reflector_on_the_left(h.rb_mut(), v, tau, 2, p - 1, p - 2..n);
+        h[(p, p - 2)] = T::zero();
+        apply_real_reflector_on_the_right(h.rb_mut(), v, tau, 2, p - 1, 0..p + 1);
+        if let Some(z) = z.as_mut() {
+            let nrows = z.nrows();
+            apply_real_reflector_on_the_right(z.rb_mut(), v, tau, 2, p - 1, 0..nrows);
+        }
+    }
+
+    let mut i = 0;
+    while i < n {
+        if i + 1 < n && h[(i + 1, i)] != T::zero() {
+            let (re0, im0, re1, im1) =
+                real_block_eigenvalues(h[(i, i)], h[(i, i + 1)], h[(i + 1, i)], h[(i + 1, i + 1)]);
+            w_re[i] = re0;
+            w_im[i] = im0;
+            w_re[i + 1] = re1;
+            w_im[i + 1] = im1;
+            i += 2;
+        } else {
+            w_re[i] = h[(i, i)];
+            w_im[i] = T::zero();
+            i += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the complex Schur form $T$ of an upper Hessenberg matrix $H$, using the single
+/// shift QR algorithm with Wilkinson shifts, such that
+/// $$H = Z T Z^*,$$
+/// where $Z$ is a unitary matrix and $T$ is an upper triangular matrix.
+///
+/// On entry, the part of `h` below its first subdiagonal is ignored. On exit, `h` contains $T$,
+/// and the eigenvalues of $H$ are stored in `w`, in the order in which they appear on the
+/// diagonal of $T$.
+///
+/// If `z` is provided, it is multiplied on the right by $Z$.
+///
+/// An error is returned if one of the eigenvalues fails to converge within the maximum number of
+/// iterations, which may happen if the input contains non-finite values. In that case, the
+/// contents of `h`, `z` and `w` are unspecified.
+///
+/// # Panics
+///
+/// - Panics if `h` is not square.
+/// - Panics if `w` doesn't have $n$ rows, where $n$ is the dimension of `h`.
+/// - Panics if `z` is provided and doesn't have $n$ columns.
+#[track_caller]
+pub fn compute_complex_schur<T: ComplexField>(
+    h: MatMut<'_, T>,
+    z: Option<MatMut<'_, T>>,
+    w: ColMut<'_, T>,
+) -> Result<(), ConvergenceError> {
+    fancy_assert!(h.nrows() == h.ncols());
+    let n = h.nrows();
+    fancy_assert!(w.nrows() == n);
+    if let Some(z) = &z {
+        fancy_assert!(z.ncols() == n);
+    }
+
+    let mut h = h;
+    let mut z = z;
+    let mut w = w;
+
+    let norm = clear_below_subdiagonal(h.rb_mut());
+    if !norm.is_finite() {
+        return Err(ConvergenceError { index: n - 1 });
+    }
+    let two = T::from_real(T::Real::one() + T::Real::one());
+
+    let mut end = n;
+    let mut iter = 0;
+    while end > 0 {
+        let p = end - 1;
+        let l = find_unreduced_block_start(h.rb_mut(), p, norm);
+
+        if l == p {
+            end -= 1;
+            iter = 0;
+            continue;
+        }
+        if iter == MAX_ITERATIONS_PER_EIGENVALUE {
+            return Err(ConvergenceError { index: p });
+        }
+        iter += 1;
+
+        let shift = if iter % 10 == 0 {
+            // exceptional shift, used to break cycles
+            let mut s = h[(p, p - 1)].abs();
+            if p >= l + 2 {
+                s = s + h[(p - 1, p - 2)].abs();
+            }
+            h[(p, p)] + T::from_real(s)
+        } else {
+            // wilkinson shift: the eigenvalue of the trailing 2×2 block that is closest to its
+            // bottom right element
+            let a = h[(p - 1, p - 1)];
+            let b = h[(p - 1, p)];
+            let c = h[(p, p - 1)];
+            let d = h[(p, p)];
+            let half_diff = (a - d) / two;
+            let root = (half_diff * half_diff + b * c).sqrt();
+            let mean = (a + d) / two;
+            let s0 = mean + root;
+            let s1 = mean - root;
+            if (s0 - d).abs() <= (s1 - d).abs() {
+                s0
+            } else {
+                s1
+            }
+        };
+
+        let mut x = h[(l, l)] - shift;
+        let mut y = h[(l + 1, l)];
+
+        // chase the bulge down the diagonal
+        for k in l..p {
+            // G = [c s; -conj(s) c], such that G [x; y] = [r; 0]
+            let x_abs = x.abs();
+            let y_abs = y.abs();
+            let r = x_abs.hypot(y_abs);
+            let (c, s) = if r == T::Real::zero() {
+                (T::Real::one(), T::zero())
+            } else if x_abs == T::Real::zero() {
+                (T::Real::zero(), T::one())
+            } else {
+                (x_abs / r, x.scale(x_abs.inv()) * y.conj().scale(r.inv()))
+            };
+
+            let q = if k > l { k - 1 } else { l };
+            for j in q..n {
+                let a = h[(k, j)];
+                let b = h[(k + 1, j)];
+                h[(k, j)] = a.scale(c) + s * b;
+                h[(k + 1, j)] = -(s.conj() * a) + b.scale(c);
+            }
+            if k > l {
+                h[(k + 1, k - 1)] = T::zero();
+            }
+
+            let r = (k + 2).min(p);
+            for i in 0..r + 1 {
+                let a = h[(i, k)];
+                let b = h[(i, k + 1)];
+                h[(i, k)] = a.scale(c) + b * s.conj();
+                h[(i, k + 1)] = -(a * s) + b.scale(c);
+            }
+            if let Some(z) = z.as_mut() {
+                for i in 0..z.nrows() {
+                    let a = z[(i, k)];
+                    let b = z[(i, k + 1)];
+                    z[(i, k)] = a.scale(c) + b * s.conj();
+                    z[(i, k + 1)] = -(a * s) + b.scale(c);
+                }
+            }
+
+            if k + 1 < p {
+                x = h[(k + 1, k)];
+                y = h[(k + 2, k)];
+            }
+        }
+    }
+
+    for i in 0..n {
+        w[i] = h[(i, i)];
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use faer_core::{c64, mul::matmul, Conj, Mat, Parallelism};
+    use rand::random;
+
+    fn reconstruct<T: ComplexField>(z: &Mat<T>, t: &Mat<T>) -> Mat<T> {
+        let n = z.nrows();
+        let mut zt = Mat::zeros(n, n);
+        let mut reconstructed = Mat::zeros(n, n);
+        matmul(
+            zt.as_mut(),
+            Conj::No,
+            z.as_ref(),
+            Conj::No,
+            t.as_ref(),
+            Conj::No,
+            None,
+            T::one(),
+            Parallelism::None,
+        );
+        matmul(
+            reconstructed.as_mut(),
+            Conj::No,
+            zt.as_ref(),
+            Conj::No,
+            z.as_ref().transpose(),
+            Conj::Yes,
+            None,
+            T::one(),
+            Parallelism::None,
+        );
+        reconstructed
+    }
+
+    fn random_hessenberg<T: ComplexField>(n: usize, mut gen: impl FnMut() -> T) -> Mat<T> {
+        Mat::with_dims(|i, j| if i <= j + 1 { gen() } else { T::zero() }, n, n)
+    }
+
+    #[test]
+    fn test_real_schur() {
+        for n in [0, 1, 2, 3, 4, 10, 33, 64] {
+            let h = random_hessenberg(n, random::<f64>);
+
+            let mut t = h.clone();
+            let mut z = Mat::with_dims(|i, j| if i == j { 1.0 } else { 0.0 }, n, n);
+            let mut w_re = Mat::zeros(n, 1);
+            let mut w_im = Mat::zeros(n, 1);
+            compute_real_schur(
+                t.as_mut(),
+                Some(z.as_mut()),
+                w_re.as_mut().col(0),
+                w_im.as_mut().col(0),
+            )
+            .unwrap();
+
+            // T is quasi upper triangular, with no two consecutive nonzero subdiagonal elements
+            for j in 0..n {
+                for i in j + 2..n {
+                    fancy_assert!(t[(i, j)] == 0.0);
+                }
+                if j + 2 < n {
+                    fancy_assert!(t[(j + 1, j)] == 0.0 || t[(j + 2, j + 1)] == 0.0);
+                }
+                // the 2×2 blocks have complex conjugate eigenvalues
+                if j + 1 < n && t[(j + 1, j)] != 0.0 {
+                    fancy_assert!(w_im[(j, 0)] > 0.0);
+                    fancy_assert!(w_im[(j + 1, 0)] == -w_im[(j, 0)]);
+                }
+            }
+
+            let reconstructed = reconstruct(&z, &t);
+            for j in 0..n {
+                for i in 0..n {
+                    fancy_assert!((reconstructed[(i, j)] - h[(i, j)]).abs() < 1e-10);
+                }
+            }
+
+            // the eigenvalues are the same when the schur vectors are not requested
+            let mut t_only = h.clone();
+            let mut w_re_only = Mat::zeros(n, 1);
+            let mut w_im_only = Mat::zeros(n, 1);
+            compute_real_schur(
+                t_only.as_mut(),
+                None,
+                w_re_only.as_mut().col(0),
+                w_im_only.as_mut().col(0),
+            )
+            .unwrap();
+            for i in 0..n {
+                fancy_assert!((w_re_only[(i, 0)] - w_re[(i, 0)]).abs() < 1e-10);
+                fancy_assert!((w_im_only[(i, 0)] - w_im[(i, 0)]).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_schur() {
+        for n in [0, 1, 2, 3, 4, 10, 33, 64] {
+            let h = random_hessenberg(n, || c64::new(random(), random()));
+
+            let mut t = h.clone();
+            let mut z = Mat::with_dims(|i, j| if i == j { c64::one() } else { c64::zero() }, n, n);
+            let mut w = Mat::zeros(n, 1);
+            compute_complex_schur(t.as_mut(), Some(z.as_mut()), w.as_mut().col(0)).unwrap();
+
+            // T is upper triangular
+            for j in 0..n {
+                for i in j + 1..n {
+                    fancy_assert!(t[(i, j)] == c64::zero());
+                }
+                fancy_assert!(w[(j, 0)] == t[(j, j)]);
+            }
+
+            let reconstructed = reconstruct(&z, &t);
+            for j in 0..n {
+                for i in 0..n {
+                    fancy_assert!((reconstructed[(i, j)] - h[(i, j)]).abs() < 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_schur_non_finite() {
+        let n = 10;
+        let mut h = random_hessenberg(n, || c64::new(random(), random()));
+        h[(5, 4)] = c64::new(f64::NAN, 0.0);
+
+        let mut w = Mat::zeros(n, 1);
+        fancy_assert!(compute_complex_schur(h.as_mut(), None, w.as_mut().col(0)).is_err());
+    }
+}
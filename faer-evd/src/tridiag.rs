@@ -0,0 +1,253 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    householder::make_householder_in_place,
+    mul::triangular::{self, BlockStructure},
+    temp_mat_req, temp_mat_uninit, ColMut, ComplexField, Conj, MatMut, Parallelism,
+};
+use reborrow::*;
+
+/// Computes the size and alignment of required workspace for reducing a hermitian matrix to
+/// tridiagonal form.
+pub fn tridiagonalize_in_place_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    let m = dim.saturating_sub(1);
+    temp_mat_req::<T>(m, 2)?.try_and(temp_mat_req::<T>(m, 2)?)
+}
+
+/// Reduces a hermitian matrix $A$ to tridiagonal form $T$, such that
+/// $$A = QTQ^*,$$
+/// where $Q$ is a unitary matrix, stored as a sequence of householder reflections.
+///
+/// The input matrix is interpreted as hermitian and only the lower triangular part is read.
+///
+/// On exit, the diagonal and the first subdiagonal of `matrix` contain the diagonal and the
+/// subdiagonal of $T$. The essential parts of the householder reflections are stored below the
+/// first subdiagonal, and their coefficients are stored in `householder_factor`, so that the
+/// $k$-th reflection acts on the rows and columns with indices greater than $k$. The strictly
+/// upper triangular part of the matrix is not accessed.
+///
+/// # Panics
+///
+/// - Panics if the input matrix is not square.
+/// - Panics if `householder_factor` doesn't have $\max(n - 1, 0)$ rows, where $n$ is the
+///   dimension of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn tridiagonalize_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_factor: ColMut<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(matrix.nrows() == matrix.ncols());
+    let n = matrix.nrows();
+    fancy_assert!(householder_factor.nrows() == n.saturating_sub(1));
+
+    let mut matrix = matrix;
+    let mut householder_factor = householder_factor;
+    let mut stack = stack;
+
+    for k in 0..n.saturating_sub(1) {
+        let m = n - k - 1;
+        let (_, _, col, a22) = matrix.rb_mut().submatrix(k, k, n - k, n - k).split_at(1, 1);
+        let (mut head, mut tail) = col.col(0).split_at(1);
+
+        let mut tail_squared_norm = T::Real::zero();
+        for &elem in tail.rb() {
+            tail_squared_norm = tail_squared_norm + (elem * elem.conj()).real();
+        }
+
+        // the column is already in tridiagonal form
+        if tail_squared_norm == T::Real::zero() {
+            householder_factor[k] = T::zero();
+            continue;
+        }
+
+        let (tau, beta) = make_householder_in_place(tail.rb_mut(), head[0], tail_squared_norm);
+        head[0] = beta;
+        householder_factor[k] = tau;
+
+        temp_mat_uninit! {
+            let (mut vw, stack) = unsafe { temp_mat_uninit::<T>(m, 2, stack.rb_mut()) };
+            let (mut wv, _) = unsafe { temp_mat_uninit::<T>(m, 2, stack) };
+        }
+
+        // v = [1; essential]
+        vw[(0, 0)] = T::one();
+        for i in 1..m {
+            vw[(i, 0)] = tail[i - 1];
+        }
+
+        // H A22 H = A22 - v p^* - p v^* + tau (v^* p) v v^*
+        //         = A22 - v w^* - w v^*
+        // where p = tau A22 v and w = p - tau/2 (v^* p) v
+
+        let (_, _, v, mut p) = vw.rb_mut().split_at(0, 1);
+        let v = v.rb();
+
+        // p <- tau A22 v, with A22 = tril(A22) + tril(A22, -1)^*
+        triangular::matmul(
+            p.rb_mut(),
+            BlockStructure::Rectangular,
+            Conj::No,
+            a22.rb(),
+            BlockStructure::TriangularLower,
+            Conj::No,
+            v,
+            BlockStructure::Rectangular,
+            Conj::No,
+            None,
+            T::from_real(tau.real()),
+            parallelism,
+        );
+        triangular::matmul(
+            p.rb_mut(),
+            BlockStructure::Rectangular,
+            Conj::No,
+            a22.rb().transpose(),
+            BlockStructure::StrictTriangularUpper,
+            Conj::Yes,
+            v,
+            BlockStructure::Rectangular,
+            Conj::No,
+            Some(T::one()),
+            T::from_real(tau.real()),
+            parallelism,
+        );
+
+        let mut vhp = T::zero();
+        for i in 0..m {
+            vhp = vhp + v[(i, 0)].conj() * p[(i, 0)];
+        }
+        let alpha = T::from_real(tau.real() * vhp.real() / (T::Real::one() + T::Real::one()));
+        for i in 0..m {
+            p[(i, 0)] = p[(i, 0)] - alpha * v[(i, 0)];
+        }
+
+        // wv = [w v]
+        for i in 0..m {
+            wv[(i, 0)] = vw[(i, 1)];
+            wv[(i, 1)] = vw[(i, 0)];
+        }
+
+        // A22 <- A22 - [v w] [w v]^*
+        triangular::matmul(
+            a22,
+            BlockStructure::TriangularLower,
+            Conj::No,
+            vw.rb(),
+            BlockStructure::Rectangular,
+            Conj::No,
+            wv.rb().transpose(),
+            BlockStructure::Rectangular,
+            Conj::Yes,
+            Some(T::one()),
+            -T::one(),
+            parallelism,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c64, householder::apply_househodler_on_the_left, mul::matmul, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn test_tridiag<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for n in [0, 1, 2, 3, 4, 10, 33, 64] {
+            let mut a = Mat::with_dims(|_, _| gen(), n, n);
+            for j in 0..n {
+                a[(j, j)] = T::from_real(a[(j, j)].real());
+                for i in 0..j {
+                    a[(i, j)] = a[(j, i)].conj();
+                }
+            }
+
+            let mut trid = a.clone();
+            let mut householder = Mat::zeros(n.saturating_sub(1), 1);
+            tridiagonalize_in_place(
+                trid.as_mut(),
+                householder.as_mut().col(0),
+                Parallelism::Rayon(0),
+                make_stack!(tridiagonalize_in_place_req::<T>(n, Parallelism::Rayon(0)).unwrap()),
+            );
+
+            let mut t = Mat::zeros(n, n);
+            for i in 0..n {
+                t[(i, i)] = trid[(i, i)];
+                if i + 1 < n {
+                    t[(i + 1, i)] = trid[(i + 1, i)];
+                    t[(i, i + 1)] = trid[(i + 1, i)].conj();
+                }
+            }
+
+            // Q = H_0 × ... × H_{n - 2}
+            let mut q = Mat::zeros(n, n);
+            for i in 0..n {
+                q[(i, i)] = T::one();
+            }
+            for k in (0..n.saturating_sub(1)).rev() {
+                apply_househodler_on_the_left(
+                    q.as_mut().submatrix(k + 1, 0, n - k - 1, n),
+                    trid.as_ref().col(k).split_at(k + 2).1,
+                    householder[(k, 0)],
+                    make_stack!(temp_mat_req::<T>(n, 1).unwrap()),
+                );
+            }
+
+            let mut qt = Mat::zeros(n, n);
+            let mut reconstructed = Mat::zeros(n, n);
+            matmul(
+                qt.as_mut(),
+                Conj::No,
+                q.as_ref(),
+                Conj::No,
+                t.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+            matmul(
+                reconstructed.as_mut(),
+                Conj::No,
+                qt.as_ref(),
+                Conj::No,
+                q.as_ref().transpose(),
+                Conj::Yes,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+
+            for j in 0..n {
+                for i in 0..n {
+                    fancy_assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tridiag_f64() {
+        test_tridiag(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_tridiag_c64() {
+        test_tridiag(|| c64::new(random(), random()), 1e-10);
+    }
+}
@@ -0,0 +1,225 @@
+use assert2::assert as fancy_assert;
+use faer_core::{error::ConvergenceError, MatMut, RealField};
+use reborrow::*;
+
+/// Maximum number of implicit QL sweeps performed for each eigenvalue.
+const MAX_ITERATIONS_PER_EIGENVALUE: usize = 30;
+
+/// Returns `a` with the sign of `b`.
+fn copysign<T: RealField>(a: T, b: T) -> T {
+    if b >= T::zero() {
+        a.abs()
+    } else {
+        -a.abs()
+    }
+}
+
+/// Computes the eigenvalues of a real symmetric tridiagonal matrix $T$, and optionally its
+/// eigenvectors, using the implicit QL algorithm with Wilkinson shifts.
+///
+/// `diag` contains the diagonal of $T$, and `offdiag` contains its subdiagonal, such that
+/// `offdiag[i]` is the element at the position $(i + 1, i)$.
+///
+/// On exit, `diag` contains the eigenvalues of $T$ in ascending order, and `offdiag` is
+/// clobbered.
+///
+/// If `u` is provided, it is multiplied on the right by the orthogonal matrix $Z$ whose columns
+/// are the eigenvectors of $T$, in the same order as the eigenvalues. If `u` is initially the
+/// identity matrix, then on exit it contains the eigenvectors of $T$.
+///
+/// An error is returned if one of the eigenvalues fails to converge within the maximum number of
+/// iterations, which may happen if the input contains non-finite values. In that case, the
+/// contents of `diag` and `u` are unspecified.
+///
+/// # Panics
+///
+/// - Panics if `offdiag` doesn't have $\max(n - 1, 0)$ elements, where $n$ is the length of
+///   `diag`.
+/// - Panics if `u` is provided and doesn't have $n$ columns.
+#[track_caller]
+pub fn compute_tridiag_real_evd<T: RealField>(
+    diag: &mut [T],
+    offdiag: &mut [T],
+    u: Option<MatMut<'_, T>>,
+) -> Result<(), ConvergenceError> {
+    let n = diag.len();
+    fancy_assert!(offdiag.len() == n.saturating_sub(1));
+    if let Some(u) = &u {
+        fancy_assert!(u.ncols() == n);
+    }
+
+    let mut u = u;
+    let d = diag;
+    let e = offdiag;
+    let epsilon = T::epsilon();
+    let min_positive = T::min_positive();
+    let two = T::one() + T::one();
+
+    for l in 0..n {
+        let mut iter = 0;
+        loop {
+            // look for a negligible subdiagonal element, to split the matrix
+            let mut m = l;
+            while m + 1 < n {
+                let dd = d[m].abs() + d[m + 1].abs();
+                if e[m].abs() <= epsilon * dd || e[m].abs() <= min_positive {
+                    e[m] = T::zero();
+                    break;
+                }
+                m += 1;
+            }
+            if m == l {
+                break;
+            }
+            if iter == MAX_ITERATIONS_PER_EIGENVALUE {
+                return Err(ConvergenceError { index: l });
+            }
+            iter += 1;
+
+            // wilkinson shift
+            let mut g = (d[l + 1] - d[l]) / (two * e[l]);
+            let mut r = g.hypot(T::one());
+            g = d[m] - d[l] + e[l] / (g + copysign(r, g));
+
+            let mut s = T::one();
+            let mut c = T::one();
+            let mut p = T::zero();
+
+            let mut underflow = false;
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                if i + 1 < m {
+                    e[i + 1] = r;
+                }
+                if r == T::zero() {
+                    // recover from underflow
+                    d[i + 1] = d[i + 1] - p;
+                    if m < n - 1 {
+                        e[m] = T::zero();
+                    }
+                    underflow = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + two * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+
+                // apply the plane rotation to the eigenvectors
+                if let Some(u) = u.as_mut() {
+                    for k in 0..u.nrows() {
+                        let f = u[(k, i + 1)];
+                        let h = u[(k, i)];
+                        u[(k, i + 1)] = s * h + c * f;
+                        u[(k, i)] = c * h - s * f;
+                    }
+                }
+            }
+            if underflow {
+                continue;
+            }
+
+            d[l] = d[l] - p;
+            e[l] = g;
+            if m < n - 1 {
+                e[m] = T::zero();
+            }
+        }
+    }
+
+    // sort the eigenvalues in ascending order, along with the eigenvectors
+    for i in 0..n {
+        let mut min_idx = i;
+        for j in i + 1..n {
+            if d[j] < d[min_idx] {
+                min_idx = j;
+            }
+        }
+        if min_idx != i {
+            d.swap(i, min_idx);
+            if let Some(u) = u.as_mut() {
+                faer_core::permutation::swap_cols(u.rb_mut(), i, min_idx);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use faer_core::{mul::matmul, Conj, Mat, Parallelism};
+    use rand::random;
+
+    #[test]
+    fn test_tridiag_real_evd() {
+        for n in [0usize, 1, 2, 3, 4, 10, 33, 64] {
+            let diag = (0..n).map(|_| random::<f64>()).collect::<Vec<_>>();
+            let offdiag = (0..n.saturating_sub(1))
+                .map(|_| random::<f64>())
+                .collect::<Vec<_>>();
+
+            let mut s = diag.clone();
+            let mut e = offdiag.clone();
+            let mut u = Mat::with_dims(|i, j| if i == j { 1.0 } else { 0.0 }, n, n);
+            compute_tridiag_real_evd(&mut s, &mut e, Some(u.as_mut())).unwrap();
+
+            let mut t = Mat::zeros(n, n);
+            for i in 0..n {
+                t[(i, i)] = diag[i];
+                if i + 1 < n {
+                    t[(i + 1, i)] = offdiag[i];
+                    t[(i, i + 1)] = offdiag[i];
+                }
+            }
+
+            let mut tu = Mat::zeros(n, n);
+            matmul(
+                tu.as_mut(),
+                Conj::No,
+                t.as_ref(),
+                Conj::No,
+                u.as_ref(),
+                Conj::No,
+                None,
+                1.0,
+                Parallelism::None,
+            );
+
+            for j in 0..n {
+                if j + 1 < n {
+                    fancy_assert!(s[j] <= s[j + 1]);
+                }
+                for i in 0..n {
+                    fancy_assert!((tu[(i, j)] - u[(i, j)] * s[j]).abs() < 1e-10);
+                }
+            }
+
+            // the eigenvalues are the same when the eigenvectors are not requested
+            let mut s_only = diag.clone();
+            let mut e = offdiag.clone();
+            compute_tridiag_real_evd(&mut s_only, &mut e, None).unwrap();
+            for i in 0..n {
+                fancy_assert!((s_only[i] - s[i]).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let mut diag = vec![1.0, f64::NAN, 2.0, 3.0];
+        let mut offdiag = vec![1.0, 1.0, 1.0];
+        fancy_assert!(compute_tridiag_real_evd(&mut diag, &mut offdiag, None).is_err());
+
+        let mut diag = vec![1.0, 2.0, 3.0];
+        let mut offdiag = vec![f64::INFINITY, 1.0];
+        fancy_assert!(compute_tridiag_real_evd(&mut diag, &mut offdiag, None).is_err());
+    }
+}
@@ -209,7 +209,7 @@ pub fn lu(c: &mut Criterion) {
                     n,
                     n,
                     Parallelism::None,
-                    full_params,
+                    FullPivLuComputeParams::default(),
                 )
                 .unwrap(),
             );
@@ -224,7 +224,7 @@ pub fn lu(c: &mut Criterion) {
                     &mut col_perm_inv,
                     Parallelism::Rayon(rayon::current_num_threads()),
                     stack.rb_mut(),
-                    full_params,
+                    FullPivLuComputeParams::default(),
                 );
             })
         });
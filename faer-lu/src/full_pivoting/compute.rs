@@ -8,6 +8,7 @@ use assert2::{assert as fancy_assert, debug_assert as fancy_debug_assert};
 use bytemuck::cast;
 use dyn_stack::{DynStack, StackReq};
 use faer_core::{
+    error::{check_dimensions, check_workspace, FaerError},
     mul::matmul,
     permutation::{swap_cols, swap_rows, PermutationIndicesMut},
     ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism, RowRef,
@@ -966,6 +967,7 @@ fn lu_in_place_unblocked<T: ComplexField>(
     parallelism: Parallelism,
     transposed: bool,
     disable_parallelism: fn(usize, usize) -> bool,
+    rank_threshold: Option<T::Real>,
 ) -> usize {
     let m = matrix.nrows();
     let n = matrix.ncols();
@@ -982,8 +984,25 @@ fn lu_in_place_unblocked<T: ComplexField>(
     let mut n_transpositions = 0;
 
     let (mut max_row, mut max_col, _) = best_in_matrix(matrix.rb());
+    let mut first_pivot = T::Real::zero();
 
     for k in 0..size {
+        if let Some(threshold) = rank_threshold {
+            let pivot = matrix.rb().get(max_row, max_col).abs();
+            if k == 0 {
+                first_pivot = pivot;
+            }
+            // the remaining submatrix is negligible, so it is treated as zero
+            if pivot <= threshold * first_pivot {
+                matrix
+                    .rb_mut()
+                    .submatrix(k, k, m - k, n - k)
+                    .cwise()
+                    .for_each(|e| *e = T::zero());
+                break;
+            }
+        }
+
         row_transpositions[k] = max_row;
         col_transpositions[k] = max_col;
 
@@ -1071,19 +1090,37 @@ fn lu_in_place_unblocked<T: ComplexField>(
     n_transpositions
 }
 
-#[derive(Default, Copy, Clone)]
+#[derive(Copy, Clone)]
 #[non_exhaustive]
-pub struct FullPivLuComputeParams {
+pub struct FullPivLuComputeParams<R> {
     pub disable_parallelism: Option<fn(nrows: usize, ncols: usize) -> bool>,
+    /// Relative threshold below which the remaining pivots are considered to be zero.
+    ///
+    /// Once the largest absolute value of the remaining submatrix is less than or equal to
+    /// `rank_threshold` times the absolute value of the first pivot, the elimination stops, and
+    /// the remaining submatrix is set to zero. The numerical rank can then be queried with
+    /// [`numerical_rank`](super::rank::numerical_rank). Defaults to `None`, in which case the
+    /// elimination always runs to completion.
+    pub rank_threshold: Option<R>,
+}
+
+impl<R> Default for FullPivLuComputeParams<R> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            disable_parallelism: None,
+            rank_threshold: None,
+        }
+    }
 }
 
 /// Computes the size and alignment of required workspace for performing an LU
 /// decomposition with full pivoting.
-pub fn lu_in_place_req<T: 'static>(
+pub fn lu_in_place_req<T: ComplexField>(
     m: usize,
     n: usize,
     parallelism: Parallelism,
-    params: FullPivLuComputeParams,
+    params: FullPivLuComputeParams<T::Real>,
 ) -> Result<StackReq, dyn_stack::SizeOverflow> {
     let _ = parallelism;
     let _ = params;
@@ -1135,7 +1172,7 @@ pub fn lu_in_place<'out, T: ComplexField>(
     col_perm_inv: &'out mut [usize],
     parallelism: Parallelism,
     stack: DynStack<'_>,
-    params: FullPivLuComputeParams,
+    params: FullPivLuComputeParams<T::Real>,
 ) -> (
     usize,
     PermutationIndicesMut<'out>,
@@ -1144,6 +1181,7 @@ pub fn lu_in_place<'out, T: ComplexField>(
     let disable_parallelism = params
         .disable_parallelism
         .unwrap_or(default_disable_parallelism);
+    let rank_threshold = params.rank_threshold;
 
     let _ = parallelism;
     let m = matrix.nrows();
@@ -1164,6 +1202,7 @@ pub fn lu_in_place<'out, T: ComplexField>(
             parallelism,
             false,
             disable_parallelism,
+            rank_threshold,
         )
     } else {
         lu_in_place_unblocked(
@@ -1173,6 +1212,7 @@ pub fn lu_in_place<'out, T: ComplexField>(
             parallelism,
             true,
             disable_parallelism,
+            rank_threshold,
         )
     };
 
@@ -1202,6 +1242,51 @@ pub fn lu_in_place<'out, T: ComplexField>(
     }
 }
 
+/// Computes the LU decomposition of the given matrix with full pivoting, replacing the matrix
+/// with its factors in place.
+///
+/// This is the fallible variant of [`lu_in_place`], which returns an error instead of panicking
+/// if the length of the row (resp. column) permutation slices is not equal to the number of rows
+/// (resp. columns) of the matrix, or if the provided memory in `stack` is insufficient.
+///
+/// A rank deficient matrix is not an error, since its rank is revealed by the decomposition. See
+/// [`numerical_rank`](super::rank::numerical_rank).
+pub fn try_lu_in_place<'out, T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    row_perm: &'out mut [usize],
+    row_perm_inv: &'out mut [usize],
+    col_perm: &'out mut [usize],
+    col_perm_inv: &'out mut [usize],
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: FullPivLuComputeParams<T::Real>,
+) -> Result<
+    (
+        usize,
+        PermutationIndicesMut<'out>,
+        PermutationIndicesMut<'out>,
+    ),
+    FaerError,
+> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    check_dimensions("row_perm", (row_perm.len(), 1), (m, 1))?;
+    check_dimensions("row_perm_inv", (row_perm_inv.len(), 1), (m, 1))?;
+    check_dimensions("col_perm", (col_perm.len(), 1), (n, 1))?;
+    check_dimensions("col_perm_inv", (col_perm_inv.len(), 1), (n, 1))?;
+    check_workspace(&stack, lu_in_place_req::<T>(m, n, parallelism, params))?;
+    Ok(lu_in_place(
+        matrix,
+        row_perm,
+        row_perm_inv,
+        col_perm,
+        col_perm_inv,
+        parallelism,
+        stack,
+        params,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use faer_core::{permutation::PermutationIndicesRef, Mat};
@@ -1351,4 +1436,71 @@ mod tests {
         compute_lu_row_major_generic::<f64>(random, 1e-6);
         compute_lu_row_major_generic::<f32>(random, 1e-2);
     }
+
+    #[test]
+    fn test_try_lu() {
+        let (m, n) = (4, 3);
+        let mut mat = Mat::with_dims(|_, _| random::<f64>(), m, n);
+        let mut row_perm = vec![0; m];
+        let mut row_perm_inv = vec![0; m];
+        let mut col_perm = vec![0; n];
+        let mut short_col_perm_inv = vec![0; n - 1];
+
+        let result = try_lu_in_place(
+            mat.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut short_col_perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(m, n, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+        fancy_assert!(matches!(
+            result,
+            Err(FaerError::DimensionMismatch {
+                operand: "col_perm_inv",
+                ..
+            })
+        ));
+
+        let mut col_perm_inv = vec![0; n];
+        let result = try_lu_in_place(
+            mat.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut col_perm_inv,
+            Parallelism::None,
+            make_stack!(dyn_stack::StackReq::new::<u8>(0)),
+            Default::default(),
+        );
+        fancy_assert!(matches!(
+            result,
+            Err(FaerError::InsufficientWorkspace { .. })
+        ));
+
+        let mat_orig = mat.clone();
+        let (_, row_perm, col_perm) = try_lu_in_place(
+            mat.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut col_perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(m, n, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        )
+        .unwrap();
+        let reconstructed = reconstruct_matrix(mat.as_ref(), row_perm.rb(), col_perm.rb());
+        for j in 0..n {
+            for i in 0..m {
+                fancy_assert!((mat_orig[(i, j)] - reconstructed[(i, j)]).abs() < 1e-10);
+            }
+        }
+    }
 }
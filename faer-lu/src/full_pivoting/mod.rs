@@ -7,6 +7,9 @@
 //! pivoting, but is more expensive to compute.
 
 pub mod compute;
+pub mod determinant;
 pub mod inverse;
+pub mod rank;
 pub mod reconstruct;
 pub mod solve;
+pub mod spaces;
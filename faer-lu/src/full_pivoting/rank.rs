@@ -0,0 +1,213 @@
+use assert2::assert as fancy_assert;
+use faer_core::{permutation::PermutationIndicesRef, ComplexField, MatRef};
+
+/// Computes the numerical rank of a matrix, given its full pivoting LU decomposition.
+///
+/// The rank is the number of leading diagonal elements of $U$ whose absolute value is greater
+/// than `threshold` times the absolute value of the first one. Since full pivoting selects the
+/// largest remaining element at each step, this is a reliable estimate of the rank in practice.
+///
+/// If the decomposition was computed with a
+/// [`rank_threshold`](super::compute::FullPivLuComputeParams::rank_threshold), the remaining
+/// submatrix is exactly zero, and a `threshold` of zero returns the rank at which the elimination
+/// stopped.
+pub fn numerical_rank<T: ComplexField>(lu_factors: MatRef<'_, T>, threshold: T::Real) -> usize {
+    let size = lu_factors.nrows().min(lu_factors.ncols());
+    if size == 0 {
+        return 0;
+    }
+
+    let first_pivot = lu_factors[(0, 0)].abs();
+    let bound = threshold * first_pivot;
+    let mut rank = 0;
+    for k in 0..size {
+        let pivot = lu_factors[(k, k)].abs();
+        if pivot <= bound || pivot == T::Real::zero() {
+            break;
+        }
+        rank += 1;
+    }
+    rank
+}
+
+/// Computes the reciprocal pivot growth factor of the full pivoting LU decomposition of a matrix
+/// $A$, given $A$ and its factors.
+///
+/// The reciprocal pivot growth factor is defined as
+/// $$\min_j \frac{\max_i |(AQ^\top)_{ij}|}{\max_i |U_{ij}|},$$
+/// where the minimum is taken over the columns $j$ of $U$ that are not zero. A value much smaller
+/// than one indicates that the elements of $U$ grew during the elimination, so that the
+/// factorization, and the solutions computed from it, may be inaccurate.
+///
+/// # Panics
+///
+/// - Panics if `matrix` and `lu_factors` don't have the same shape.
+/// - Panics if `col_perm` doesn't have the same dimension as the number of columns of `matrix`.
+#[track_caller]
+pub fn reciprocal_pivot_growth<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    lu_factors: MatRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+) -> T::Real {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    fancy_assert!((lu_factors.nrows(), lu_factors.ncols()) == (m, n));
+    fancy_assert!(col_perm.len() == n);
+
+    let col_perm = col_perm.into_arrays().0;
+
+    let zero = T::Real::zero();
+    let mut growth = T::Real::one();
+    for j in 0..n {
+        let mut u_max = zero;
+        for i in 0..m.min(j + 1) {
+            let abs = lu_factors[(i, j)].abs();
+            if abs > u_max {
+                u_max = abs;
+            }
+        }
+        if u_max == zero {
+            continue;
+        }
+
+        let mut a_max = zero;
+        for i in 0..m {
+            let abs = matrix[(i, col_perm[j])].abs();
+            if abs > a_max {
+                a_max = abs;
+            }
+        }
+
+        let ratio = a_max / u_max;
+        if ratio < growth {
+            growth = ratio;
+        }
+    }
+    growth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::full_pivoting::{
+        compute::{lu_in_place, lu_in_place_req, FullPivLuComputeParams},
+        reconstruct::{reconstruct_req, reconstruct_to},
+    };
+    use faer_core::{c64, mul::matmul, Conj, Mat, Parallelism};
+    use rand::random;
+    use reborrow::*;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::DynStack::new(&mut ::dyn_stack::GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn random_low_rank(m: usize, n: usize, rank: usize) -> Mat<c64> {
+        let lhs = Mat::with_dims(|_, _| c64::new(random(), random()), m, rank);
+        let rhs = Mat::with_dims(|_, _| c64::new(random(), random()), rank, n);
+        let mut a = Mat::zeros(m, n);
+        matmul(
+            a.as_mut(),
+            Conj::No,
+            lhs.as_ref(),
+            Conj::No,
+            rhs.as_ref(),
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        a
+    }
+
+    #[test]
+    fn test_rank() {
+        for (m, n) in [(1, 1), (4, 4), (10, 6), (6, 10), (32, 32), (100, 80)] {
+            for rank in [0, 1, 2, m.min(n) / 2, m.min(n)] {
+                let rank = rank.min(m.min(n));
+                let a = random_low_rank(m, n, rank);
+
+                for rank_threshold in [None, Some(1e-10)] {
+                    let mut lu = a.clone();
+                    let mut row_perm = vec![0; m];
+                    let mut row_perm_inv = vec![0; m];
+                    let mut col_perm = vec![0; n];
+                    let mut col_perm_inv = vec![0; n];
+                    let params = FullPivLuComputeParams {
+                        rank_threshold,
+                        ..Default::default()
+                    };
+                    let (_, row_perm, col_perm) = lu_in_place(
+                        lu.as_mut(),
+                        &mut row_perm,
+                        &mut row_perm_inv,
+                        &mut col_perm,
+                        &mut col_perm_inv,
+                        Parallelism::None,
+                        make_stack!(
+                            lu_in_place_req::<c64>(m, n, Parallelism::None, params).unwrap()
+                        ),
+                        params,
+                    );
+
+                    if rank_threshold.is_some() {
+                        // the elimination stopped early, and the factors are finite
+                        fancy_assert!(numerical_rank(lu.as_ref(), 0.0) == rank);
+
+                        let mut reconstructed = Mat::zeros(m, n);
+                        reconstruct_to(
+                            reconstructed.as_mut(),
+                            lu.as_ref(),
+                            row_perm.rb(),
+                            col_perm.rb(),
+                            Parallelism::None,
+                            make_stack!(reconstruct_req::<c64>(m, n, Parallelism::None).unwrap()),
+                        );
+                        for j in 0..n {
+                            for i in 0..m {
+                                fancy_assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < 1e-8);
+                            }
+                        }
+                    } else if rank > 0 {
+                        fancy_assert!(numerical_rank(lu.as_ref(), 1e-10) == rank);
+                    }
+
+                    // without a threshold, a rank deficient matrix produces infinite factors
+                    if rank_threshold.is_some() || rank == m.min(n) {
+                        let growth =
+                            reciprocal_pivot_growth(a.as_ref(), lu.as_ref(), col_perm.rb());
+                        fancy_assert!(growth > 0.0);
+                        fancy_assert!(growth <= 1.0);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pivot_growth() {
+        let n = 4;
+        let a = Mat::with_dims(|i, j| if i == j { 2.0 } else { 1.0 }, n, n);
+        let mut lu = a.clone();
+        let mut row_perm = vec![0; n];
+        let mut row_perm_inv = vec![0; n];
+        let mut col_perm = vec![0; n];
+        let mut col_perm_inv = vec![0; n];
+        let (_, _, col_perm) = lu_in_place(
+            lu.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut col_perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(n, n, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+
+        // the matrix is diagonally dominant, so there is no growth
+        fancy_assert!(reciprocal_pivot_growth(a.as_ref(), lu.as_ref(), col_perm.rb()) == 1.0);
+    }
+}
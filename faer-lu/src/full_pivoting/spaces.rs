@@ -0,0 +1,318 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    solve::solve_upper_triangular_in_place, temp_mat_req, temp_mat_uninit, ComplexField, Conj,
+    MatMut, MatRef, Parallelism, RealField,
+};
+use reborrow::*;
+
+use super::{
+    compute::{lu_in_place, lu_in_place_req, FullPivLuComputeParams},
+    rank::numerical_rank,
+};
+
+/// The elimination stops at the first exactly zero pivot, instead of dividing by it.
+fn compute_params<R: RealField>() -> FullPivLuComputeParams<R> {
+    FullPivLuComputeParams {
+        rank_threshold: Some(R::zero()),
+        ..Default::default()
+    }
+}
+
+fn factorize_req<T: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T>(nrows, ncols)?,
+        StackReq::try_new::<usize>(nrows)?,
+        StackReq::try_new::<usize>(nrows)?,
+        StackReq::try_new::<usize>(ncols)?,
+        StackReq::try_new::<usize>(ncols)?,
+        lu_in_place_req::<T>(nrows, ncols, parallelism, compute_params())?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for computing a basis of the null space
+/// of a matrix with dimensions $m\times n$.
+pub fn null_space_req<T: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    factorize_req::<T>(nrows, ncols, parallelism)
+}
+
+/// Computes the size and alignment of required workspace for computing a basis of the column space
+/// of a matrix with dimensions $m\times n$.
+pub fn column_space_req<T: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    factorize_req::<T>(nrows, ncols, parallelism)
+}
+
+/// Computes a basis of the null space of the matrix $A$, i.e., of the vectors $x$ such that
+/// $Ax = 0$, using its full pivoting LU decomposition.
+///
+/// The numerical rank $r$ of $A$ is determined with
+/// [`numerical_rank`](super::rank::numerical_rank), using the relative `tolerance`. Given the
+/// decomposition $PAQ^\top = LU$, where $U = \begin{bmatrix} U_{11} & U_{12} \end{bmatrix}$ and
+/// $U_{11}$ is the leading $r\times r$ block, the basis is given by the columns of
+/// $$Q^\top \begin{bmatrix} -U_{11}^{-1} U_{12} \\\\ I \end{bmatrix}.$$
+/// The basis is not orthonormal, but it is exactly sparse in the rows corresponding to the
+/// non-pivot columns of $A$.
+///
+/// The basis is stored in the first $n - r$ columns of `dst`, and $n - r$ is returned. The
+/// remaining columns of `dst` are left unchanged.
+///
+/// # Panics
+///
+/// - Panics if `dst` isn't a square matrix with the same number of rows as the number of columns
+///   of `matrix`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn null_space<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    tolerance: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> usize {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, n));
+
+    let mut dst = dst;
+
+    temp_mat_uninit! {
+        let (mut lu, stack) = unsafe { temp_mat_uninit::<T>(m, n, stack) };
+    }
+    lu.rb_mut()
+        .cwise()
+        .zip(matrix)
+        .for_each(|dst, src| *dst = *src);
+
+    let (mut row_perm, stack) = stack.make_with(m, |_| 0);
+    let (mut row_perm_inv, stack) = stack.make_with(m, |_| 0);
+    let (mut col_perm, stack) = stack.make_with(n, |_| 0);
+    let (mut col_perm_inv, stack) = stack.make_with(n, |_| 0);
+
+    let params = compute_params();
+    lu_in_place(
+        lu.rb_mut(),
+        &mut row_perm,
+        &mut row_perm_inv,
+        &mut col_perm,
+        &mut col_perm_inv,
+        parallelism,
+        stack,
+        params,
+    );
+
+    let rank = numerical_rank(lu.rb(), tolerance);
+    let nullity = n - rank;
+
+    // U_12 <- U_11^-1 U_12
+    let (u11, mut u12, _, _) = lu.rb_mut().split_at(rank, rank);
+    solve_upper_triangular_in_place(u11.rb(), Conj::No, u12.rb_mut(), Conj::No, parallelism);
+
+    for j in 0..nullity {
+        let mut col = dst.rb_mut().col(j);
+        col.rb_mut().cwise().for_each(|e| *e = T::zero());
+        for i in 0..rank {
+            col[col_perm[i]] = -u12[(i, j)];
+        }
+        col[col_perm[rank + j]] = T::one();
+    }
+
+    nullity
+}
+
+/// Computes a basis of the column space of the matrix $A$, i.e., of the vectors $Ax$, using its
+/// full pivoting LU decomposition.
+///
+/// The numerical rank $r$ of $A$ is determined with
+/// [`numerical_rank`](super::rank::numerical_rank), using the relative `tolerance`. The basis is
+/// made of the $r$ columns of $A$ that were selected as pivots during the decomposition. It is not
+/// orthonormal, but it consists of actual columns of $A$.
+///
+/// The basis is stored in the first $r$ columns of `dst`, and $r$ is returned. The remaining
+/// columns of `dst` are left unchanged.
+///
+/// # Panics
+///
+/// - Panics if `dst` doesn't have the same number of rows as `matrix`, or if its number of
+///   columns isn't equal to the minimum of the number of rows and the number of columns of
+///   `matrix`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn column_space<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    tolerance: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> usize {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    fancy_assert!((dst.nrows(), dst.ncols()) == (m, m.min(n)));
+
+    let mut dst = dst;
+
+    temp_mat_uninit! {
+        let (mut lu, stack) = unsafe { temp_mat_uninit::<T>(m, n, stack) };
+    }
+    lu.rb_mut()
+        .cwise()
+        .zip(matrix)
+        .for_each(|dst, src| *dst = *src);
+
+    let (mut row_perm, stack) = stack.make_with(m, |_| 0);
+    let (mut row_perm_inv, stack) = stack.make_with(m, |_| 0);
+    let (mut col_perm, stack) = stack.make_with(n, |_| 0);
+    let (mut col_perm_inv, stack) = stack.make_with(n, |_| 0);
+
+    let params = compute_params();
+    lu_in_place(
+        lu.rb_mut(),
+        &mut row_perm,
+        &mut row_perm_inv,
+        &mut col_perm,
+        &mut col_perm_inv,
+        parallelism,
+        stack,
+        params,
+    );
+
+    let rank = numerical_rank(lu.rb(), tolerance);
+    for j in 0..rank {
+        dst.rb_mut()
+            .col(j)
+            .cwise()
+            .zip(matrix.col(col_perm[j]))
+            .for_each(|dst, src| *dst = *src);
+    }
+
+    rank
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use faer_core::{c64, mul::matmul, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::DynStack::new(&mut ::dyn_stack::GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn random_low_rank(m: usize, n: usize, rank: usize) -> Mat<c64> {
+        let lhs = Mat::with_dims(|_, _| c64::new(random(), random()), m, rank);
+        let rhs = Mat::with_dims(|_, _| c64::new(random(), random()), rank, n);
+        let mut a = Mat::zeros(m, n);
+        matmul(
+            a.as_mut(),
+            Conj::No,
+            lhs.as_ref(),
+            Conj::No,
+            rhs.as_ref(),
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        a
+    }
+
+    #[test]
+    fn test_null_space() {
+        for (m, n) in [(1, 1), (4, 4), (10, 6), (6, 10), (32, 32), (50, 80)] {
+            for rank in [0, 1, 2, m.min(n) / 2, m.min(n)] {
+                let rank = rank.min(m.min(n));
+                let a = random_low_rank(m, n, rank);
+
+                let mut basis = Mat::zeros(n, n);
+                let nullity = null_space(
+                    basis.as_mut(),
+                    a.as_ref(),
+                    1e-10,
+                    Parallelism::None,
+                    make_stack!(null_space_req::<c64>(m, n, Parallelism::None).unwrap()),
+                );
+                fancy_assert!(nullity == n - rank);
+
+                let mut product = Mat::zeros(m, nullity);
+                matmul(
+                    product.as_mut(),
+                    Conj::No,
+                    a.as_ref(),
+                    Conj::No,
+                    basis.as_ref().submatrix(0, 0, n, nullity),
+                    Conj::No,
+                    None,
+                    c64::one(),
+                    Parallelism::None,
+                );
+                for j in 0..nullity {
+                    for i in 0..m {
+                        fancy_assert!(product[(i, j)].abs() < 1e-8);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_column_space() {
+        for (m, n) in [(1, 1), (4, 4), (10, 6), (6, 10), (32, 32), (50, 80)] {
+            for rank in [0, 1, 2, m.min(n) / 2, m.min(n)] {
+                let rank = rank.min(m.min(n));
+                let a = random_low_rank(m, n, rank);
+
+                let mut basis = Mat::zeros(m, m.min(n));
+                let r = column_space(
+                    basis.as_mut(),
+                    a.as_ref(),
+                    1e-10,
+                    Parallelism::None,
+                    make_stack!(column_space_req::<c64>(m, n, Parallelism::None).unwrap()),
+                );
+                fancy_assert!(r == rank);
+
+                // the basis has full rank, and spans the columns of A
+                let basis = basis.as_ref().submatrix(0, 0, m, r);
+                let mut augmented = Mat::zeros(m, r + n);
+                for j in 0..r + n {
+                    for i in 0..m {
+                        augmented[(i, j)] = if j < r { basis[(i, j)] } else { a[(i, j - r)] };
+                    }
+                }
+                let mut null_basis = Mat::zeros(r, r);
+                fancy_assert!(
+                    null_space(
+                        null_basis.as_mut(),
+                        basis,
+                        1e-10,
+                        Parallelism::None,
+                        make_stack!(null_space_req::<c64>(m, r, Parallelism::None).unwrap()),
+                    ) == 0
+                );
+                let mut null_basis = Mat::zeros(r + n, r + n);
+                fancy_assert!(
+                    null_space(
+                        null_basis.as_mut(),
+                        augmented.as_ref(),
+                        1e-10,
+                        Parallelism::None,
+                        make_stack!(null_space_req::<c64>(m, r + n, Parallelism::None).unwrap()),
+                    ) == n
+                );
+            }
+        }
+    }
+}
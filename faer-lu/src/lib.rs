@@ -1,2 +1,4 @@
+#![allow(clippy::too_many_arguments)]
+
 pub mod full_pivoting;
 pub mod partial_pivoting;
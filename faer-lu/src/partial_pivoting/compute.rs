@@ -1,12 +1,13 @@
 use assert2::{assert as fancy_assert, debug_assert as fancy_debug_assert};
 use dyn_stack::{DynStack, SizeOverflow, StackReq};
 use faer_core::{
+    error::{check_dimensions, check_workspace, FaerError},
     mul::matmul,
     permutation::{swap_rows, PermutationIndicesMut},
     solve::solve_unit_lower_triangular_in_place,
     temp_mat_req, temp_mat_uninit,
     zip::ColUninit,
-    ColMut, ComplexField, Conj, MatMut, Parallelism,
+    ColMut, ComplexField, Conj, MatMut, Parallelism, RealField,
 };
 use reborrow::*;
 
@@ -380,6 +381,48 @@ pub fn lu_in_place<'out, T: ComplexField>(
     })
 }
 
+/// Computes the LU decomposition of the given matrix with partial pivoting, replacing the matrix
+/// with its factors in place.
+///
+/// This is the fallible variant of [`lu_in_place`], which returns an error instead of panicking
+/// if the length of the permutation slices is not equal to the number of rows of the matrix, or
+/// if the provided memory in `stack` is insufficient.
+///
+/// It also returns [`FaerError::Singular`] if a diagonal element of $U$ is zero or not finite, in
+/// which case the matrix holds the factors computed so far, which aren't suitable for solving
+/// linear systems. Since a zero pivot makes the following ones non-finite, the returned index is
+/// the one of the pivot at which the factorization breaks down.
+///
+/// For rectangular matrices, only the first `min(nrows, ncols)` diagonal elements of $U$ are
+/// checked. A zero pivot at index `k` means that the first `k + 1` columns of the matrix are
+/// linearly dependent, so for tall matrices it is reported if and only if the matrix doesn't have
+/// full column rank, while for wide matrices it doesn't rule out full row rank.
+pub fn try_lu_in_place<'out, T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    perm: &'out mut [usize],
+    perm_inv: &'out mut [usize],
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: PartialPivLuComputeParams,
+) -> Result<(usize, PermutationIndicesMut<'out>), FaerError> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    check_dimensions("perm", (perm.len(), 1), (m, 1))?;
+    check_dimensions("perm_inv", (perm_inv.len(), 1), (m, 1))?;
+    check_workspace(&stack, lu_in_place_req::<T>(m, n, parallelism, params))?;
+
+    let mut matrix = matrix;
+    let (n_transpositions, perm) =
+        lu_in_place(matrix.rb_mut(), perm, perm_inv, parallelism, stack, params);
+    for k in 0..m.min(n) {
+        let d = matrix[(k, k)].abs();
+        if d == T::Real::zero() || !d.is_finite() {
+            return Err(FaerError::Singular { index: k });
+        }
+    }
+    Ok((n_transpositions, perm))
+}
+
 #[cfg(test)]
 mod tests {
     use assert_approx_eq::assert_approx_eq;
@@ -460,4 +503,67 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_try_lu_singular() {
+        let n = 4;
+        // the second column is zero, so the second pivot is exactly zero
+        let mut mat = Mat::with_dims(|_, j| if j == 1 { 0.0 } else { random::<f64>() }, n, n);
+        let mut perm = vec![0; n];
+        let mut perm_inv = vec![0; n];
+
+        let result = try_lu_in_place(
+            mat.as_mut(),
+            &mut perm,
+            &mut perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(n, n, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+        fancy_assert!(matches!(result, Err(FaerError::Singular { index: 1 })));
+    }
+
+    #[test]
+    fn test_try_lu_singular_rectangular() {
+        let (m, n) = (6, 4);
+        // the third column is zero, so the tall matrix doesn't have full column rank
+        let mut mat = Mat::with_dims(|_, j| if j == 2 { 0.0 } else { random::<f64>() }, m, n);
+        let mut perm = vec![0; m];
+        let mut perm_inv = vec![0; m];
+
+        let result = try_lu_in_place(
+            mat.as_mut(),
+            &mut perm,
+            &mut perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(m, n, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+        fancy_assert!(matches!(result, Err(FaerError::Singular { index: 2 })));
+    }
+
+    #[test]
+    fn test_try_lu_non_finite() {
+        let n = 4;
+        let mut mat = Mat::with_dims(|_, _| random::<f64>(), n, n);
+        mat[(0, 0)] = f64::INFINITY;
+        let mut perm = vec![0; n];
+        let mut perm_inv = vec![0; n];
+
+        let result = try_lu_in_place(
+            mat.as_mut(),
+            &mut perm,
+            &mut perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(n, n, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+        fancy_assert!(matches!(result, Err(FaerError::Singular { index: 0 })));
+    }
 }
@@ -0,0 +1,217 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    norm::norm_one_estimate, permutation::PermutationIndicesRef, temp_mat_req, temp_mat_uninit,
+    ColMut, ComplexField, Conj, MatRef, Parallelism,
+};
+use reborrow::*;
+
+use super::solve::{solve_in_place, solve_req, solve_transpose_in_place};
+
+/// Computes the size and alignment of required workspace for estimating the reciprocal condition
+/// number of a matrix, given its partial pivoting LU decomposition.
+pub fn reciprocal_condition_number_req<T: 'static>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T>(dim, 1)?,
+        temp_mat_req::<T>(dim, 1)?,
+        solve_req::<T>(dim, dim, 1, parallelism)?,
+    ])
+}
+
+/// Estimates the reciprocal of the condition number of a matrix $A$ in the 1-norm, given its
+/// partial pivoting LU decomposition and the 1-norm of $A$, i.e., its maximum absolute column
+/// sum, which must be computed before the factorization.
+///
+/// The reciprocal condition number is defined as
+/// $$\frac{1}{\|A\|_1 \|A^{-1}\|_1},$$
+/// where $\|A^{-1}\|_1$ is estimated with Hager's method, as refined by Higham, without forming
+/// the inverse. The estimate of $\|A^{-1}\|_1$ is a lower bound that is exact in most cases, so
+/// the returned value may slightly overestimate the reciprocal condition number.
+///
+/// A value close to the machine epsilon indicates that the solutions of linear systems involving
+/// $A$ are not trustworthy. If $A$ is exactly singular, zero is returned.
+///
+/// # Panics
+///
+/// - Panics if `lu_factors` is not a square matrix.
+/// - Panics if `row_perm` doesn't have the same dimension as `lu_factors`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn reciprocal_condition_number<T: ComplexField>(
+    lu_factors: MatRef<'_, T>,
+    row_perm: PermutationIndicesRef<'_>,
+    matrix_norm_one: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> T::Real {
+    let n = lu_factors.nrows();
+    fancy_assert!(lu_factors.ncols() == n);
+    fancy_assert!(row_perm.len() == n);
+
+    if n == 0 {
+        return T::Real::one();
+    }
+    if matrix_norm_one == T::Real::zero() {
+        return T::Real::zero();
+    }
+    // the matrix is exactly singular
+    for &u in lu_factors.diagonal() {
+        if u.abs() == T::Real::zero() {
+            return T::Real::zero();
+        }
+    }
+
+    let mut stack = stack;
+    temp_mat_uninit! {
+        let (mut x, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack.rb_mut()) };
+        let (mut sign, mut stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+    }
+    let mut x = x.rb_mut().col(0);
+    let mut sign = sign.rb_mut().col(0);
+
+    // solves A x = x
+    let solve = |x: ColMut<'_, T>, stack: DynStack<'_>| {
+        solve_in_place(
+            lu_factors,
+            Conj::No,
+            row_perm,
+            x.as_2d(),
+            Conj::No,
+            parallelism,
+            stack,
+        )
+    };
+    // solves A^* x = x
+    let solve_adjoint = |x: ColMut<'_, T>, stack: DynStack<'_>| {
+        solve_transpose_in_place(
+            lu_factors,
+            Conj::Yes,
+            row_perm,
+            x.as_2d(),
+            Conj::No,
+            parallelism,
+            stack,
+        )
+    };
+
+    let inverse_norm = norm_one_estimate(
+        x.rb_mut(),
+        sign.rb_mut(),
+        solve,
+        solve_adjoint,
+        stack.rb_mut(),
+    );
+
+    if inverse_norm == T::Real::zero() {
+        T::Real::zero()
+    } else {
+        (matrix_norm_one * inverse_norm).inv()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partial_pivoting::{
+        compute::{lu_in_place, lu_in_place_req},
+        inverse::{invert_req, invert_to},
+    };
+    use faer_core::{c64, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::DynStack::new(&mut ::dyn_stack::GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn matrix_norm_one<T: ComplexField>(a: MatRef<'_, T>) -> T::Real {
+        let mut max = T::Real::zero();
+        for j in 0..a.ncols() {
+            let mut sum = T::Real::zero();
+            for i in 0..a.nrows() {
+                sum = sum + a[(i, j)].abs();
+            }
+            if sum > max {
+                max = sum;
+            }
+        }
+        max
+    }
+
+    fn test_rcond<T: ComplexField<Real = f64>>(a: &Mat<T>) {
+        let n = a.nrows();
+        let a_norm = matrix_norm_one(a.as_ref());
+
+        let mut lu = a.clone();
+        let mut row_perm = vec![0; n];
+        let mut row_perm_inv = vec![0; n];
+        let (_, row_perm) = lu_in_place(
+            lu.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            Parallelism::Rayon(0),
+            make_stack!(
+                lu_in_place_req::<T>(n, n, Parallelism::Rayon(0), Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+
+        let rcond = reciprocal_condition_number(
+            lu.as_ref(),
+            row_perm.rb(),
+            a_norm,
+            Parallelism::Rayon(0),
+            make_stack!(reciprocal_condition_number_req::<T>(n, Parallelism::Rayon(0)).unwrap()),
+        );
+
+        let mut inv = Mat::zeros(n, n);
+        invert_to(
+            inv.as_mut(),
+            lu.as_ref(),
+            row_perm.rb(),
+            Parallelism::Rayon(0),
+            make_stack!(invert_req::<T>(n, n, Parallelism::Rayon(0)).unwrap()),
+        );
+        let exact = 1.0 / (a_norm * matrix_norm_one(inv.as_ref()));
+
+        // the estimate of the norm of the inverse is a lower bound, and is usually within a small
+        // factor of the exact value
+        fancy_assert!(rcond >= exact * (1.0 - 1e-8));
+        fancy_assert!(rcond <= exact * 10.0);
+    }
+
+    #[test]
+    fn test_rcond_random() {
+        for n in [1, 2, 3, 4, 10, 33, 64, 100] {
+            test_rcond(&Mat::with_dims(|_, _| random::<f64>(), n, n));
+            test_rcond(&Mat::with_dims(|_, _| c64::new(random(), random()), n, n));
+        }
+    }
+
+    #[test]
+    fn test_rcond_ill_conditioned() {
+        for n in [4, 10, 33] {
+            // the columns have wildly different scales
+            let a = Mat::with_dims(|_, j| random::<f64>() * 10.0f64.powi(-(j as i32)), n, n);
+            test_rcond(&a);
+        }
+
+        // a singular matrix has a zero reciprocal condition number
+        let n = 4;
+        let mut lu = Mat::with_dims(|i, j| if i == j { 1.0 } else { 0.0 }, n, n);
+        lu[(2, 2)] = 0.0;
+        let row_perm = [0, 1, 2, 3];
+        let rcond = reciprocal_condition_number(
+            lu.as_ref(),
+            unsafe { PermutationIndicesRef::new_unchecked(&row_perm, &row_perm) },
+            1.0,
+            Parallelism::None,
+            make_stack!(reciprocal_condition_number_req::<f64>(n, Parallelism::None).unwrap()),
+        );
+        fancy_assert!(rcond == 0.0);
+    }
+}
@@ -0,0 +1,211 @@
+use assert2::assert as fancy_assert;
+use faer_core::{ComplexField, MatRef, RealField};
+
+/// Computes the determinant of a matrix, given its partial pivoting LU decomposition and the
+/// number of transpositions that constitute the row permutation, as returned by
+/// [`lu_in_place`](super::compute::lu_in_place).
+///
+/// The result may overflow or underflow for large matrices, in which case
+/// [`log_abs_determinant`] should be used instead.
+///
+/// # Panics
+///
+/// Panics if `lu_factors` is not a square matrix.
+#[track_caller]
+pub fn determinant<T: ComplexField>(lu_factors: MatRef<'_, T>, n_transpositions: usize) -> T {
+    fancy_assert!(lu_factors.nrows() == lu_factors.ncols());
+
+    let mut det = T::one();
+    for &u in lu_factors.diagonal() {
+        det = det * u;
+    }
+    if n_transpositions % 2 == 1 {
+        -det
+    } else {
+        det
+    }
+}
+
+/// Computes the sign and the logarithm of the absolute value of the determinant of a matrix,
+/// given its partial pivoting LU decomposition and the number of transpositions that constitute
+/// the row permutation, as returned by [`lu_in_place`](super::compute::lu_in_place), such that
+/// $$\det(A) = \text{sign} \times \exp(\text{log\\_abs\\_det}).$$
+///
+/// # Output
+///
+/// - The sign of the determinant, which is a number with a unit absolute value, or zero if the
+///   matrix is singular,
+/// - the logarithm of the absolute value of the determinant, which is $-\infty$ if the matrix is
+///   singular.
+///
+/// # Panics
+///
+/// Panics if `lu_factors` is not a square matrix.
+#[track_caller]
+pub fn log_abs_determinant<T: ComplexField>(
+    lu_factors: MatRef<'_, T>,
+    n_transpositions: usize,
+) -> (T, T::Real) {
+    fancy_assert!(lu_factors.nrows() == lu_factors.ncols());
+
+    let mut sign = if n_transpositions % 2 == 1 {
+        -T::one()
+    } else {
+        T::one()
+    };
+    let mut log_abs_det = T::Real::zero();
+    for &u in lu_factors.diagonal() {
+        let abs = u.abs();
+        if abs == T::Real::zero() {
+            sign = T::zero();
+        } else {
+            sign = sign * u.scale(abs.inv());
+        }
+        log_abs_det = log_abs_det + abs.ln();
+    }
+    (sign, log_abs_det)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partial_pivoting::compute::{lu_in_place, lu_in_place_req};
+    use assert_approx_eq::assert_approx_eq;
+    use faer_core::{c64, mul::matmul, permutation::swap_rows, Conj, Mat, Parallelism};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::DynStack::new(&mut ::dyn_stack::GlobalMemBuffer::new($req))
+        };
+    }
+
+    /// Returns the matrix LU, where L is unit lower triangular and U is upper triangular with the
+    /// given diagonal, so that its determinant is the product of the diagonal elements.
+    fn random_with_diagonal(diag: &[c64]) -> Mat<c64> {
+        let n = diag.len();
+        let l = Mat::with_dims(
+            |i, j| {
+                if i == j {
+                    c64::one()
+                } else if i > j {
+                    c64::new(random(), random())
+                } else {
+                    c64::zero()
+                }
+            },
+            n,
+            n,
+        );
+        let u = Mat::with_dims(
+            |i, j| {
+                if i == j {
+                    diag[i]
+                } else if i < j {
+                    c64::new(random(), random())
+                } else {
+                    c64::zero()
+                }
+            },
+            n,
+            n,
+        );
+        let mut a = Mat::zeros(n, n);
+        matmul(
+            a.as_mut(),
+            Conj::No,
+            l.as_ref(),
+            Conj::No,
+            u.as_ref(),
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        a
+    }
+
+    fn factorize(a: &Mat<c64>) -> (Mat<c64>, usize) {
+        let n = a.nrows();
+        let mut lu = a.clone();
+        let mut row_perm = vec![0; n];
+        let mut row_perm_inv = vec![0; n];
+        let (n_transpositions, _) = lu_in_place(
+            lu.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            Parallelism::Rayon(0),
+            make_stack!(
+                lu_in_place_req::<c64>(n, n, Parallelism::Rayon(0), Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+        (lu, n_transpositions)
+    }
+
+    #[test]
+    fn test_determinant() {
+        for n in [0, 1, 2, 3, 4, 10, 16] {
+            let diag = (0..n)
+                .map(|_| c64::new(random::<f64>() + 0.5, random::<f64>()))
+                .collect::<Vec<_>>();
+            let mut a = random_with_diagonal(&diag);
+            let mut target = diag.iter().fold(c64::one(), |acc, &d| acc * d);
+
+            for swap in [false, true] {
+                if swap && n >= 2 {
+                    swap_rows(a.as_mut(), 0, n - 1);
+                    target = -target;
+                }
+
+                let (lu, n_transpositions) = factorize(&a);
+                let det = determinant(lu.as_ref(), n_transpositions);
+                assert_approx_eq!(det, target, 1e-6 * target.abs());
+
+                let (sign, log_abs_det) = log_abs_determinant(lu.as_ref(), n_transpositions);
+                assert_approx_eq!(sign, target.scale(target.abs().inv()), 1e-6);
+                assert_approx_eq!(log_abs_det, target.abs().ln(), 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_abs_determinant_overflow() {
+        let n = 64;
+        let diag = (0..n)
+            .map(|i| c64::new(if i % 2 == 0 { 1e10 } else { -1e10 }, 0.0))
+            .collect::<Vec<_>>();
+        let a = random_with_diagonal(&diag);
+        let (lu, n_transpositions) = factorize(&a);
+
+        fancy_assert!(!determinant(lu.as_ref(), n_transpositions).re.is_finite());
+
+        let (sign, log_abs_det) = log_abs_determinant(lu.as_ref(), n_transpositions);
+        assert_approx_eq!(sign, c64::one(), 1e-6);
+        assert_approx_eq!(log_abs_det, n as f64 * 1e10f64.ln(), 1e-6);
+    }
+
+    #[test]
+    fn test_singular() {
+        let n = 4;
+        // the last column is zero, so that the last diagonal element of U is exactly zero
+        let mut a = Mat::with_dims(|_, j| if j + 1 < n { random::<f64>() } else { 0.0 }, n, n);
+        let mut row_perm = vec![0; n];
+        let mut row_perm_inv = vec![0; n];
+        let (n_transpositions, _) = lu_in_place(
+            a.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            Parallelism::Rayon(0),
+            make_stack!(
+                lu_in_place_req::<f64>(n, n, Parallelism::Rayon(0), Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+
+        fancy_assert!(determinant(a.as_ref(), n_transpositions) == 0.0);
+        let (sign, log_abs_det) = log_abs_determinant(a.as_ref(), n_transpositions);
+        fancy_assert!(sign == 0.0);
+        fancy_assert!(log_abs_det == f64::NEG_INFINITY);
+    }
+}
@@ -0,0 +1,331 @@
+use assert2::assert as fancy_assert;
+use faer_core::{ColMut, ColRef, ComplexField, MatMut, MatRef, RealField};
+use reborrow::*;
+
+/// Information about the scaling of a matrix, computed by [`compute_equilibration`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EquilibrationInfo<T> {
+    /// Ratio of the smallest to the largest row scale factor. If it is at least `0.1`, and
+    /// `max_abs` is neither too close to overflow nor to underflow, scaling by the row factors is
+    /// not worth it.
+    pub row_condition: T,
+    /// Ratio of the smallest to the largest column scale factor. If it is at least `0.1`, scaling
+    /// by the column factors is not worth it.
+    pub col_condition: T,
+    /// Largest absolute value of the elements of the matrix.
+    pub max_abs: T,
+}
+
+/// Error returned when a matrix can't be equilibrated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EquilibrationError {
+    /// The row with the given index is exactly zero.
+    ZeroRow(usize),
+    /// The column with the given index is exactly zero.
+    ZeroColumn(usize),
+}
+
+/// Returns the largest power of two that is less than or equal to `value`, which must be positive
+/// and finite.
+fn round_to_power_of_two<T: RealField>(value: T) -> T {
+    let one = T::one();
+    let two = one + one;
+    let half = two.inv();
+
+    let mut power = one;
+    while power > value {
+        power = power * half;
+    }
+    while power * two <= value {
+        power = power * two;
+    }
+    power
+}
+
+/// Computes row and column scale factors $R$ and $C$ intended to equilibrate the matrix $A$, so
+/// that the largest absolute value in each row and column of $RAC$ is in $[1/2, 1]$, which
+/// reduces the pivot growth of the LU decomposition of badly scaled matrices.
+///
+/// The scale factors are restricted to powers of two, so that scaling a matrix doesn't introduce
+/// rounding errors. They are stored in `row_scale` and `col_scale`.
+///
+/// # Panics
+///
+/// - Panics if `row_scale` doesn't have the same length as the number of rows of `matrix`.
+/// - Panics if `col_scale` doesn't have the same length as the number of columns of `matrix`.
+#[track_caller]
+pub fn compute_equilibration<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    row_scale: ColMut<'_, T::Real>,
+    col_scale: ColMut<'_, T::Real>,
+) -> Result<EquilibrationInfo<T::Real>, EquilibrationError> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    fancy_assert!(row_scale.nrows() == m);
+    fancy_assert!(col_scale.nrows() == n);
+
+    let mut row_scale = row_scale;
+    let mut col_scale = col_scale;
+
+    let zero = T::Real::zero();
+    let one = T::Real::one();
+    let small = T::Real::min_positive();
+    let big = small.inv();
+
+    if m == 0 || n == 0 {
+        row_scale.rb_mut().cwise().for_each(|r| *r = one);
+        col_scale.rb_mut().cwise().for_each(|c| *c = one);
+        return Ok(EquilibrationInfo {
+            row_condition: one,
+            col_condition: one,
+            max_abs: zero,
+        });
+    }
+
+    let clamp = |value: T::Real| {
+        if value < small {
+            small
+        } else if value > big {
+            big
+        } else {
+            value
+        }
+    };
+
+    // row scale factors
+    for i in 0..m {
+        let mut max = zero;
+        for j in 0..n {
+            let abs = matrix[(i, j)].abs();
+            if abs > max {
+                max = abs;
+            }
+        }
+        if max == zero {
+            return Err(EquilibrationError::ZeroRow(i));
+        }
+        row_scale[i] = max;
+    }
+
+    let mut max_abs = zero;
+    let mut row_min = big;
+    let mut row_max = zero;
+    for i in 0..m {
+        let max = row_scale[i];
+        if max > max_abs {
+            max_abs = max;
+        }
+        if max < row_min {
+            row_min = max;
+        }
+        if max > row_max {
+            row_max = max;
+        }
+        row_scale[i] = round_to_power_of_two(clamp(max).inv());
+    }
+    let row_condition = clamp(row_min) / clamp(row_max);
+
+    // column scale factors, computed for the row scaled matrix
+    let mut col_min = big;
+    let mut col_max = zero;
+    for j in 0..n {
+        let mut max = zero;
+        for i in 0..m {
+            let abs = matrix[(i, j)].abs() * row_scale[i];
+            if abs > max {
+                max = abs;
+            }
+        }
+        if max == zero {
+            return Err(EquilibrationError::ZeroColumn(j));
+        }
+        if max < col_min {
+            col_min = max;
+        }
+        if max > col_max {
+            col_max = max;
+        }
+        col_scale[j] = round_to_power_of_two(clamp(max).inv());
+    }
+    let col_condition = clamp(col_min) / clamp(col_max);
+
+    Ok(EquilibrationInfo {
+        row_condition,
+        col_condition,
+        max_abs,
+    })
+}
+
+/// Scales the matrix $A$ in place, replacing it with $RAC$, where $R$ and $C$ are the diagonal
+/// matrices whose diagonal elements are stored in `row_scale` and `col_scale`.
+///
+/// # Panics
+///
+/// - Panics if `row_scale` doesn't have the same length as the number of rows of `matrix`.
+/// - Panics if `col_scale` doesn't have the same length as the number of columns of `matrix`.
+#[track_caller]
+pub fn equilibrate_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    row_scale: ColRef<'_, T::Real>,
+    col_scale: ColRef<'_, T::Real>,
+) {
+    fancy_assert!(row_scale.nrows() == matrix.nrows());
+    fancy_assert!(col_scale.nrows() == matrix.ncols());
+
+    let mut matrix = matrix;
+    for j in 0..matrix.ncols() {
+        let c = col_scale[j];
+        for i in 0..matrix.nrows() {
+            let a = matrix[(i, j)];
+            matrix[(i, j)] = a.scale(row_scale[i] * c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partial_pivoting::{
+        compute::{lu_in_place, lu_in_place_req},
+        solve::{solve_equilibrated_in_place, solve_req},
+    };
+    use faer_core::{c64, mul::matmul, Conj, Mat, Parallelism};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::DynStack::new(&mut ::dyn_stack::GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn is_power_of_two(value: f64) -> bool {
+        value > 0.0 && value.to_bits() & ((1 << 52) - 1) == 0
+    }
+
+    #[test]
+    fn test_equilibrate() {
+        for n in [1, 2, 3, 4, 10, 32, 64] {
+            // badly scaled rows and columns
+            let row_factor: Vec<f64> = (0..n).map(|_| 10.0f64.powi(random::<i32>() % 20)).collect();
+            let col_factor: Vec<f64> = (0..n).map(|_| 10.0f64.powi(random::<i32>() % 20)).collect();
+            let a = Mat::with_dims(
+                |i, j| c64::new(random(), random()).scale(row_factor[i] * col_factor[j]),
+                n,
+                n,
+            );
+
+            let mut row_scale = Mat::<f64>::zeros(n, 1);
+            let mut col_scale = Mat::<f64>::zeros(n, 1);
+            let info = compute_equilibration(
+                a.as_ref(),
+                row_scale.as_mut().col(0),
+                col_scale.as_mut().col(0),
+            )
+            .unwrap();
+            fancy_assert!(info.row_condition <= 1.0);
+            fancy_assert!(info.col_condition <= 1.0);
+
+            for i in 0..n {
+                fancy_assert!(is_power_of_two(row_scale[(i, 0)]));
+                fancy_assert!(is_power_of_two(col_scale[(i, 0)]));
+            }
+
+            let mut lu = a.clone();
+            equilibrate_in_place(
+                lu.as_mut(),
+                row_scale.as_ref().col(0),
+                col_scale.as_ref().col(0),
+            );
+
+            // the largest element of each column is in [1/2, 1]
+            for j in 0..n {
+                let max = (0..n).map(|i| lu[(i, j)].abs()).fold(0.0, f64::max);
+                fancy_assert!(max > 0.5 - 1e-12);
+                fancy_assert!(max <= 1.0);
+            }
+
+            let mut row_perm = vec![0; n];
+            let mut row_perm_inv = vec![0; n];
+            let (_, row_perm) = lu_in_place(
+                lu.as_mut(),
+                &mut row_perm,
+                &mut row_perm_inv,
+                Parallelism::None,
+                make_stack!(
+                    lu_in_place_req::<c64>(n, n, Parallelism::None, Default::default()).unwrap()
+                ),
+                Default::default(),
+            );
+
+            let k = 2;
+            let rhs = Mat::with_dims(|_, _| c64::new(random(), random()), n, k);
+            for conj_lhs in [Conj::No, Conj::Yes] {
+                let mut sol = rhs.clone();
+                solve_equilibrated_in_place(
+                    lu.as_ref(),
+                    conj_lhs,
+                    row_perm.rb(),
+                    row_scale.as_ref().col(0),
+                    col_scale.as_ref().col(0),
+                    sol.as_mut(),
+                    Conj::No,
+                    Parallelism::None,
+                    make_stack!(solve_req::<c64>(n, n, k, Parallelism::None).unwrap()),
+                );
+
+                // the backward error is small relative to the magnitude of each row
+                let mut residual = rhs.clone();
+                matmul(
+                    residual.as_mut(),
+                    Conj::No,
+                    a.as_ref(),
+                    conj_lhs,
+                    sol.as_ref(),
+                    Conj::No,
+                    Some(c64::one()),
+                    -c64::one(),
+                    Parallelism::None,
+                );
+                for j in 0..k {
+                    for i in 0..n {
+                        let mut bound = rhs[(i, j)].abs();
+                        for l in 0..n {
+                            bound += a[(i, l)].abs() * sol[(l, j)].abs();
+                        }
+                        fancy_assert!(residual[(i, j)].abs() <= 1e-10 * bound);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_equilibrate_zero() {
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), 4, 4);
+        let mut row_scale = Mat::<f64>::zeros(4, 1);
+        let mut col_scale = Mat::<f64>::zeros(4, 1);
+
+        for i in 0..4 {
+            a[(2, i)] = 0.0;
+        }
+        fancy_assert!(
+            compute_equilibration(
+                a.as_ref(),
+                row_scale.as_mut().col(0),
+                col_scale.as_mut().col(0),
+            ) == Err(EquilibrationError::ZeroRow(2))
+        );
+
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), 4, 4);
+        for i in 0..4 {
+            a[(i, 1)] = 0.0;
+        }
+        fancy_assert!(
+            compute_equilibration(
+                a.as_ref(),
+                row_scale.as_mut().col(0),
+                col_scale.as_mut().col(0),
+            ) == Err(EquilibrationError::ZeroColumn(1))
+        );
+    }
+}
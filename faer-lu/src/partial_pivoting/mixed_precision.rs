@@ -0,0 +1,413 @@
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    mixed_precision::{
+        has_converged, FallbackReason, MixedPrecision, MixedPrecisionInfo, MixedPrecisionParams,
+    },
+    mul::matmul,
+    norm::norm_inf,
+    temp_mat_req, temp_mat_uninit, ComplexField, Conj, MatMut, MatRef, Parallelism, RealField,
+};
+use reborrow::*;
+
+use assert2::assert as fancy_assert;
+
+use super::{
+    compute::{lu_in_place, lu_in_place_req},
+    solve,
+};
+
+fn solve_mixed_precision_req<T: MixedPrecision>(
+    dim: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T::Lower>(dim, dim)?,
+        StackReq::try_new::<usize>(dim)?,
+        StackReq::try_new::<usize>(dim)?,
+        temp_mat_req::<T::Lower>(dim, rhs_ncols)?,
+        temp_mat_req::<T>(dim, rhs_ncols)?,
+        StackReq::try_any_of([
+            lu_in_place_req::<T::Lower>(dim, dim, parallelism, Default::default())?,
+            solve::solve_req::<T::Lower>(dim, dim, rhs_ncols, parallelism)?,
+        ])?,
+    ])
+}
+
+fn solve_working_precision_req<T: 'static>(
+    dim: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T>(dim, dim)?,
+        StackReq::try_new::<usize>(dim)?,
+        StackReq::try_new::<usize>(dim)?,
+        StackReq::try_any_of([
+            lu_in_place_req::<T>(dim, dim, parallelism, Default::default())?,
+            solve::solve_req::<T>(dim, dim, rhs_ncols, parallelism)?,
+        ])?,
+    ])
+}
+
+/// Attempts to solve the system with a lower precision factorization and iterative refinement.
+fn solve_mixed_precision<T: MixedPrecision>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    rhs: MatRef<'_, T>,
+    max_iterations: usize,
+    tolerance: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> MixedPrecisionInfo {
+    let n = matrix.nrows();
+    let k = rhs.ncols();
+    let mut dst = dst;
+
+    let fallback = |iterations, reason| MixedPrecisionInfo {
+        iterations,
+        fallback_reason: Some(reason),
+    };
+
+    let threshold = tolerance * norm_inf(matrix, parallelism);
+
+    temp_mat_uninit! {
+        let (mut lu, stack) = unsafe { temp_mat_uninit::<T::Lower>(n, n, stack) };
+    }
+    let (mut perm, stack) = stack.make_with(n, |_| 0);
+    let (mut perm_inv, stack) = stack.make_with(n, |_| 0);
+    temp_mat_uninit! {
+        let (mut correction, stack) = unsafe { temp_mat_uninit::<T::Lower>(n, k, stack) };
+        let (mut residual, mut stack) = unsafe { temp_mat_uninit::<T>(n, k, stack) };
+    }
+
+    let mut representable = true;
+    lu.rb_mut().cwise().zip(matrix).for_each(|dst, src| {
+        *dst = src.to_lower();
+        representable &= dst.abs().is_finite();
+    });
+    if !representable {
+        return fallback(0, FallbackReason::NotRepresentable);
+    }
+
+    let (_, perm) = lu_in_place(
+        lu.rb_mut(),
+        &mut perm,
+        &mut perm_inv,
+        parallelism,
+        stack.rb_mut(),
+        Default::default(),
+    );
+    for &u in lu.rb().diagonal() {
+        let abs = u.abs();
+        if !abs.is_finite() || abs == <T::Lower as ComplexField>::Real::zero() {
+            return fallback(0, FallbackReason::FactorizationBreakdown);
+        }
+    }
+
+    // x = A^-1 b, computed in lower precision
+    correction
+        .rb_mut()
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| *dst = src.to_lower());
+    solve::solve_in_place(
+        lu.rb(),
+        Conj::No,
+        perm.rb(),
+        correction.rb_mut(),
+        Conj::No,
+        parallelism,
+        stack.rb_mut(),
+    );
+    dst.rb_mut()
+        .cwise()
+        .zip(correction.rb())
+        .for_each(|dst, src| *dst = T::from_lower(*src));
+
+    let mut iterations = 0;
+    loop {
+        // r = b - A x, computed in working precision
+        residual
+            .rb_mut()
+            .cwise()
+            .zip(rhs)
+            .for_each(|dst, src| *dst = *src);
+        matmul(
+            residual.rb_mut(),
+            Conj::No,
+            matrix,
+            Conj::No,
+            dst.rb(),
+            Conj::No,
+            Some(T::one()),
+            -T::one(),
+            parallelism,
+        );
+
+        if has_converged(residual.rb(), dst.rb(), threshold) {
+            return MixedPrecisionInfo {
+                iterations,
+                fallback_reason: None,
+            };
+        }
+        if iterations == max_iterations {
+            return fallback(iterations, FallbackReason::NoConvergence);
+        }
+
+        // x += A^-1 r, where the correction is computed in lower precision
+        correction
+            .rb_mut()
+            .cwise()
+            .zip(residual.rb())
+            .for_each(|dst, src| *dst = src.to_lower());
+        solve::solve_in_place(
+            lu.rb(),
+            Conj::No,
+            perm.rb(),
+            correction.rb_mut(),
+            Conj::No,
+            parallelism,
+            stack.rb_mut(),
+        );
+        dst.rb_mut()
+            .cwise()
+            .zip(correction.rb())
+            .for_each(|dst, src| *dst = *dst + T::from_lower(*src));
+        iterations += 1;
+    }
+}
+
+fn solve_working_precision<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    rhs: MatRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = matrix.nrows();
+    let mut dst = dst;
+
+    temp_mat_uninit! {
+        let (mut lu, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+    }
+    let (mut perm, stack) = stack.make_with(n, |_| 0);
+    let (mut perm_inv, mut stack) = stack.make_with(n, |_| 0);
+
+    lu.rb_mut()
+        .cwise()
+        .zip(matrix)
+        .for_each(|dst, src| *dst = *src);
+    let (_, perm) = lu_in_place(
+        lu.rb_mut(),
+        &mut perm,
+        &mut perm_inv,
+        parallelism,
+        stack.rb_mut(),
+        Default::default(),
+    );
+
+    dst.rb_mut()
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| *dst = *src);
+    solve::solve_in_place(
+        lu.rb(),
+        Conj::No,
+        perm.rb(),
+        dst,
+        Conj::No,
+        parallelism,
+        stack,
+    );
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system with a
+/// mixed precision LU decomposition.
+pub fn solve_req<T: MixedPrecision>(
+    dim: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        solve_mixed_precision_req::<T>(dim, rhs_ncols, parallelism)?,
+        solve_working_precision_req::<T>(dim, rhs_ncols, parallelism)?,
+    ])
+}
+
+/// Solves the linear system $AX = B$ in the working precision `T`, where the LU decomposition
+/// with partial pivoting of $A$ is computed in the lower precision `T::Lower` (e.g. `f32` for
+/// `f64`), and the solution is refined iteratively, with the residuals computed in the working
+/// precision.
+///
+/// The refinement stops once the normwise backward error of each column of the solution is below
+/// the tolerance. If that doesn't happen within the maximum number of iterations, or if the
+/// matrix can't be represented in the lower precision, or if the lower precision factorization
+/// breaks down, the system is solved using a working precision factorization instead, and the
+/// reason is reported in the returned information.
+///
+/// The solution is stored in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `matrix` is not a square matrix.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `matrix`.
+/// - Panics if `rhs` and `dst` don't have the same shape.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve<T: MixedPrecision>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    rhs: MatRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: MixedPrecisionParams<T::Real>,
+) -> MixedPrecisionInfo {
+    let n = matrix.nrows();
+    fancy_assert!(matrix.ncols() == n);
+    fancy_assert!(rhs.nrows() == n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (rhs.nrows(), rhs.ncols()));
+
+    let mut dst = dst;
+    let mut stack = stack;
+
+    let (max_iterations, tolerance) = params.normalize(n);
+
+    let info = solve_mixed_precision(
+        dst.rb_mut(),
+        matrix,
+        rhs,
+        max_iterations,
+        tolerance,
+        parallelism,
+        stack.rb_mut(),
+    );
+    if info.fallback_reason.is_some() {
+        solve_working_precision(dst, matrix, rhs, parallelism, stack);
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use faer_core::{c64, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::DynStack::new(&mut ::dyn_stack::GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn solve_and_check<T: MixedPrecision>(
+        a: &Mat<T>,
+        mut gen: impl FnMut() -> T,
+        params: MixedPrecisionParams<T::Real>,
+        epsilon: T::Real,
+    ) -> MixedPrecisionInfo {
+        let n = a.nrows();
+        let k = 3;
+        let b = Mat::with_dims(|_, _| gen(), n, k);
+        let mut x = Mat::zeros(n, k);
+
+        let info = solve(
+            x.as_mut(),
+            a.as_ref(),
+            b.as_ref(),
+            Parallelism::Rayon(0),
+            make_stack!(solve_req::<T>(n, k, Parallelism::Rayon(0)).unwrap()),
+            params,
+        );
+
+        let mut r = b.clone();
+        matmul(
+            r.as_mut(),
+            Conj::No,
+            a.as_ref(),
+            Conj::No,
+            x.as_ref(),
+            Conj::No,
+            Some(T::one()),
+            -T::one(),
+            Parallelism::None,
+        );
+        fancy_assert!(has_converged(
+            r.as_ref(),
+            x.as_ref(),
+            epsilon * norm_inf(a.as_ref(), Parallelism::None)
+        ));
+
+        info
+    }
+
+    fn diagonally_dominant<T: ComplexField>(n: usize, mut gen: impl FnMut() -> T) -> Mat<T> {
+        let mut a = Mat::with_dims(|_, _| gen(), n, n);
+        let n_real = T::from_real(T::Real::from_f64(n as f64));
+        for i in 0..n {
+            a[(i, i)] = a[(i, i)] + n_real;
+        }
+        a
+    }
+
+    #[test]
+    fn test_mixed_precision() {
+        for n in [1, 2, 3, 4, 10, 33, 64, 200] {
+            // diagonally dominant, hence well conditioned
+            let a = diagonally_dominant(n, random::<f64>);
+            let info = solve_and_check(&a, random::<f64>, Default::default(), 1e-12);
+            fancy_assert!(info.fallback_reason == None);
+
+            let gen = || c64::new(random(), random());
+            let a = diagonally_dominant(n, gen);
+            let info = solve_and_check(&a, gen, Default::default(), 1e-12);
+            fancy_assert!(info.fallback_reason == None);
+        }
+    }
+
+    #[test]
+    fn test_mixed_precision_fallback() {
+        // too ill conditioned for the single precision factorization to be useful
+        let n = 32;
+        let mut a = Mat::with_dims(|_, _| random::<f64>(), n, n);
+        for j in 0..n {
+            a[(1, j)] = a[(0, j)] + 1e-10 * random::<f64>();
+        }
+        let info = solve_and_check(&a, random::<f64>, Default::default(), 1e-12);
+        fancy_assert!(info.fallback_reason.is_some());
+
+        // the refinement isn't allowed to run
+        let a = diagonally_dominant(n, random::<f64>);
+        let mut params = MixedPrecisionParams::default();
+        params.max_iterations = Some(0);
+        let info = solve_and_check(&a, random::<f64>, params, 1e-12);
+        fancy_assert!(
+            info == MixedPrecisionInfo {
+                iterations: 0,
+                fallback_reason: Some(FallbackReason::NoConvergence),
+            }
+        );
+
+        // the number of iterations that were performed is reported
+        params.max_iterations = Some(1);
+        params.tolerance = Some(0.0);
+        let info = solve_and_check(&a, random::<f64>, params, 1e-12);
+        fancy_assert!(
+            info == MixedPrecisionInfo {
+                iterations: 1,
+                fallback_reason: Some(FallbackReason::NoConvergence),
+            }
+        );
+
+        // out of the range of single precision
+        let mut a = diagonally_dominant(n, random::<f64>);
+        a[(0, 0)] = 1e300;
+        let info = solve_and_check(&a, random::<f64>, Default::default(), 1e-12);
+        fancy_assert!(
+            info == MixedPrecisionInfo {
+                iterations: 0,
+                fallback_reason: Some(FallbackReason::NotRepresentable),
+            }
+        );
+    }
+}
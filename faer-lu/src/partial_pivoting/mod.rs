@@ -4,6 +4,10 @@
 //! an upper triangular matrix.
 
 pub mod compute;
+pub mod condition;
+pub mod determinant;
+pub mod equilibrate;
 pub mod inverse;
+pub mod mixed_precision;
 pub mod reconstruct;
 pub mod solve;
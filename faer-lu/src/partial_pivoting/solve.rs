@@ -1,13 +1,20 @@
+use assert2::assert as fancy_assert;
 use dyn_stack::{DynStack, SizeOverflow, StackReq};
 use faer_core::{
+    error::{check_dimensions, check_workspace, FaerError},
+    mul::matmul,
+    norm::norm_one_estimate,
     permutation::{permute_rows, PermutationIndicesRef},
     solve::*,
     temp_mat_req, temp_mat_uninit,
     zip::MatUninit,
-    ComplexField, Conj, MatMut, MatRef, Parallelism,
+    ColMut, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism, RealField,
 };
 use reborrow::*;
 
+/// Default maximum number of refinement steps of [`solve_expert_to`].
+const DEFAULT_MAX_REFINEMENT_STEPS: usize = 5;
+
 fn solve_impl<T: ComplexField>(
     lu_factors: MatRef<'_, T>,
     conj_lhs: Conj,
@@ -196,6 +203,165 @@ pub fn solve_in_place<T: ComplexField>(
     );
 }
 
+/// Checks the arguments of the fallible solve functions, as well as the invertibility of the
+/// factors.
+fn check_solve_args<T: ComplexField>(
+    lu_factors: MatRef<'_, T>,
+    row_perm: PermutationIndicesRef<'_>,
+    rhs: (usize, usize),
+    parallelism: Parallelism,
+    stack: &DynStack<'_>,
+) -> Result<(), FaerError> {
+    let n = lu_factors.nrows();
+    check_dimensions("lu_factors", (n, lu_factors.ncols()), (n, n))?;
+    check_dimensions("row_perm", (row_perm.len(), 1), (n, 1))?;
+    check_dimensions("rhs", rhs, (n, rhs.1))?;
+    check_workspace(stack, solve_req::<T>(n, n, rhs.1, parallelism))?;
+    for k in 0..n {
+        if lu_factors[(k, k)].abs() == T::Real::zero() {
+            return Err(FaerError::Singular { index: k });
+        }
+    }
+    Ok(())
+}
+
+/// Given the LU factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
+/// the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// This is the fallible variant of [`solve_to`]. Instead of panicking, it returns an error if
+/// the dimensions of the inputs don't match, or if the provided memory in `stack` is
+/// insufficient. An error is also returned if $U$ has a zero diagonal element, in which case $A$
+/// is singular.
+pub fn try_solve_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    lu_factors: MatRef<'_, T>,
+    conj_lhs: Conj,
+    row_perm: PermutationIndicesRef<'_>,
+    rhs: MatRef<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> Result<(), FaerError> {
+    check_dimensions(
+        "dst",
+        (dst.nrows(), dst.ncols()),
+        (rhs.nrows(), rhs.ncols()),
+    )?;
+    check_solve_args(
+        lu_factors,
+        row_perm,
+        (rhs.nrows(), rhs.ncols()),
+        parallelism,
+        &stack,
+    )?;
+    solve_to(
+        dst,
+        lu_factors,
+        conj_lhs,
+        row_perm,
+        rhs,
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+    Ok(())
+}
+
+/// Given the LU factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
+/// the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// This is the fallible variant of [`solve_in_place`]. Instead of panicking, it returns an error
+/// if the dimensions of the inputs don't match, or if the provided memory in `stack` is
+/// insufficient. An error is also returned if $U$ has a zero diagonal element, in which case $A$
+/// is singular.
+pub fn try_solve_in_place<T: ComplexField>(
+    lu_factors: MatRef<'_, T>,
+    conj_lhs: Conj,
+    row_perm: PermutationIndicesRef<'_>,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> Result<(), FaerError> {
+    check_solve_args(
+        lu_factors,
+        row_perm,
+        (rhs.nrows(), rhs.ncols()),
+        parallelism,
+        &stack,
+    )?;
+    solve_in_place(
+        lu_factors,
+        conj_lhs,
+        row_perm,
+        rhs,
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+    Ok(())
+}
+
+/// Given the LU factors of the equilibrated matrix $RAC$, where $R$ and $C$ are the diagonal
+/// matrices whose diagonal elements are stored in `row_scale` and `col_scale`, and a matrix $B$
+/// stored in `rhs`, this function computes the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B),$$
+/// by solving $\text{Op}_A(RAC)Y = R\text{Op}_B(B)$, then undoing the scaling with $X = CY$.
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in `rhs`.
+///
+/// # Panics
+///
+/// - Panics if `lu_factors` is not a square matrix.
+/// - Panics if `row_perm` doesn't have the same dimension as `lu_factors`.
+/// - Panics if `row_scale` or `col_scale` don't have the same length as the dimension of
+///   `lu_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `lu_factors`.
+#[track_caller]
+pub fn solve_equilibrated_in_place<T: ComplexField>(
+    lu_factors: MatRef<'_, T>,
+    conj_lhs: Conj,
+    row_perm: PermutationIndicesRef<'_>,
+    row_scale: ColRef<'_, T::Real>,
+    col_scale: ColRef<'_, T::Real>,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = lu_factors.nrows();
+    fancy_assert!(row_scale.nrows() == n);
+    fancy_assert!(col_scale.nrows() == n);
+    fancy_assert!(rhs.nrows() == n);
+
+    let mut rhs = rhs;
+    let scale_rows = |mut rhs: MatMut<'_, T>, scale: ColRef<'_, T::Real>| {
+        for j in 0..rhs.ncols() {
+            for i in 0..n {
+                let x = rhs[(i, j)];
+                rhs[(i, j)] = x.scale(scale[i]);
+            }
+        }
+    };
+
+    scale_rows(rhs.rb_mut(), row_scale);
+    solve_in_place(
+        lu_factors,
+        conj_lhs,
+        row_perm,
+        rhs.rb_mut(),
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+    scale_rows(rhs, col_scale);
+}
+
 /// Given the LU factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
 /// the solution of the linear system:
 /// $$\text{Op}_A(A)^\top X = \text{Op}_B(B).$$
@@ -267,6 +433,272 @@ pub fn solve_transpose_in_place<T: ComplexField>(
     );
 }
 
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct SolveExpertParams {
+    /// Maximum number of iterative refinement steps performed for each right-hand side. Defaults
+    /// to `5`.
+    pub max_refinement_steps: Option<usize>,
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by a
+/// matrix, given its partial pivoting LU decomposition, along with error bounds on the solution.
+pub fn solve_expert_req<T: 'static>(
+    dim: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T>(dim, 1)?,
+        temp_mat_req::<T>(dim, 1)?,
+        temp_mat_req::<T>(dim, 1)?,
+        StackReq::try_any_of([
+            solve_req::<T>(dim, dim, rhs_ncols, parallelism)?,
+            solve_req::<T>(dim, dim, 1, parallelism)?,
+        ])?,
+    ])
+}
+
+/// Given a matrix $A$, its LU factors, and a matrix $B$ stored in `rhs`, this function computes
+/// the solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B),$$
+/// improves it with iterative refinement, and computes error bounds for each of its columns.
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in `dst`.
+///
+/// For each column $x$ of the solution, with corresponding column $b$ of $\text{Op}_B(B)$, the
+/// componentwise relative backward error
+/// $$\max_i \frac{|b - \text{Op}_A(A) x|_i}{(|\text{Op}_A(A)| |x| + |b|)_i}$$
+/// is stored in `berr`, and an estimated bound on the relative forward error
+/// $\|x - x_{\text{true}}\|_\infty / \|x\|_\infty$ is stored in `ferr`.
+///
+/// The refinement of a column stops once its backward error reaches the machine epsilon, stops
+/// decreasing by at least a factor of two, or the maximum number of refinement steps is reached.
+///
+/// # Panics
+///
+/// - Panics if `lu_factors` is not a square matrix.
+/// - Panics if `matrix` doesn't have the same shape as `lu_factors`.
+/// - Panics if `row_perm` doesn't have the same dimension as `lu_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `lu_factors`.
+/// - Panics if `rhs` and `dst` don't have the same shape.
+/// - Panics if `berr` or `ferr` don't have the same length as the number of columns of `rhs`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_expert_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    berr: ColMut<'_, T::Real>,
+    ferr: ColMut<'_, T::Real>,
+    matrix: MatRef<'_, T>,
+    lu_factors: MatRef<'_, T>,
+    conj_lhs: Conj,
+    row_perm: PermutationIndicesRef<'_>,
+    rhs: MatRef<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: SolveExpertParams,
+) {
+    let n = lu_factors.nrows();
+    let k = rhs.ncols();
+    fancy_assert!(lu_factors.ncols() == n);
+    fancy_assert!((matrix.nrows(), matrix.ncols()) == (n, n));
+    fancy_assert!(row_perm.len() == n);
+    fancy_assert!(rhs.nrows() == n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, k));
+    fancy_assert!(berr.nrows() == k);
+    fancy_assert!(ferr.nrows() == k);
+
+    let mut dst = dst;
+    let mut berr = berr;
+    let mut ferr = ferr;
+    let mut stack = stack;
+
+    let max_refinement_steps = params
+        .max_refinement_steps
+        .unwrap_or(DEFAULT_MAX_REFINEMENT_STEPS);
+
+    solve_to(
+        dst.rb_mut(),
+        lu_factors,
+        conj_lhs,
+        row_perm,
+        rhs,
+        conj_rhs,
+        parallelism,
+        stack.rb_mut(),
+    );
+
+    temp_mat_uninit! {
+        let (mut residual, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack.rb_mut()) };
+        let (mut weights, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut sign, mut stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+    }
+    let mut residual = residual.rb_mut().col(0);
+    let mut weights = weights.rb_mut().col(0);
+    let mut sign = sign.rb_mut().col(0);
+
+    let zero = T::Real::zero();
+    let one = T::Real::one();
+    let eps = T::Real::epsilon();
+    // maximum number of nonzeros in a row of A, plus one
+    let nz = T::Real::from_f64((n + 1) as f64);
+    // thresholds that guard against dividing by a tiny or zero denominator
+    let safe1 = nz * T::Real::min_positive();
+    let safe2 = safe1 / eps;
+
+    let conj_b = |b: T| match conj_rhs {
+        Conj::No => b,
+        Conj::Yes => b.conj(),
+    };
+
+    for j in 0..k {
+        let mut x = dst.rb_mut().col(j);
+        let b = rhs.col(j);
+
+        let mut prev_berr = one + one + one;
+        let mut step = 0;
+        loop {
+            // r = Op_B(b) - Op_A(A) x
+            for i in 0..n {
+                residual[i] = conj_b(b[i]);
+            }
+            matmul(
+                residual.rb_mut().as_2d(),
+                Conj::No,
+                matrix,
+                conj_lhs,
+                x.rb().as_2d(),
+                Conj::No,
+                Some(T::one()),
+                -T::one(),
+                parallelism,
+            );
+
+            // w = |A| |x| + |b|
+            for i in 0..n {
+                let mut w = b[i].abs();
+                for l in 0..n {
+                    w = w + matrix[(i, l)].abs() * x[l].abs();
+                }
+                weights[i] = T::from_real(w);
+            }
+
+            let mut backward_error = zero;
+            for i in 0..n {
+                let r = residual[i].abs();
+                let w = weights[i].real();
+                let ratio = if w > safe2 {
+                    r / w
+                } else {
+                    (r + safe1) / (w + safe1)
+                };
+                if ratio > backward_error {
+                    backward_error = ratio;
+                }
+            }
+            berr[j] = backward_error;
+
+            if !(backward_error > eps
+                && backward_error + backward_error <= prev_berr
+                && step < max_refinement_steps)
+            {
+                break;
+            }
+
+            // x += Op_A(A)^-1 r
+            solve_in_place(
+                lu_factors,
+                conj_lhs,
+                row_perm,
+                residual.rb_mut().as_2d(),
+                Conj::No,
+                parallelism,
+                stack.rb_mut(),
+            );
+            for i in 0..n {
+                x[i] = x[i] + residual[i];
+            }
+
+            prev_berr = backward_error;
+            step += 1;
+        }
+
+        // the error is bounded by || |A^-1| (|r| + nz eps (|A| |x| + |b|)) ||, where the
+        // second term accounts for the rounding errors in the computation of the residual
+        for i in 0..n {
+            let r = residual[i].abs();
+            let w = weights[i].real();
+            let w = if w > safe2 {
+                r + nz * eps * w
+            } else {
+                r + nz * eps * w + safe1
+            };
+            weights[i] = T::from_real(w);
+        }
+        let weights = weights.rb();
+
+        // || |A^-1| w ||_inf = ||A^-1 diag(w)||_inf is the 1-norm of diag(w) A^-*
+        let conj_adjoint = match conj_lhs {
+            Conj::No => Conj::Yes,
+            Conj::Yes => Conj::No,
+        };
+        let apply = |v: ColMut<'_, T>, stack: DynStack<'_>| {
+            let mut v = v;
+            solve_transpose_in_place(
+                lu_factors,
+                conj_adjoint,
+                row_perm,
+                v.rb_mut().as_2d(),
+                Conj::No,
+                parallelism,
+                stack,
+            );
+            for i in 0..n {
+                v[i] = v[i].scale(weights[i].real());
+            }
+        };
+        let apply_adjoint = |v: ColMut<'_, T>, stack: DynStack<'_>| {
+            let mut v = v;
+            for i in 0..n {
+                v[i] = v[i].scale(weights[i].real());
+            }
+            solve_in_place(
+                lu_factors,
+                conj_lhs,
+                row_perm,
+                v.as_2d(),
+                Conj::No,
+                parallelism,
+                stack,
+            );
+        };
+        let error_norm = norm_one_estimate(
+            residual.rb_mut(),
+            sign.rb_mut(),
+            apply,
+            apply_adjoint,
+            stack.rb_mut(),
+        );
+
+        let mut x_norm = zero;
+        for i in 0..n {
+            let abs = x[i].abs();
+            if abs > x_norm {
+                x_norm = abs;
+            }
+        }
+        ferr[j] = if x_norm == zero {
+            error_norm
+        } else {
+            error_norm / x_norm
+        };
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert2::assert as fancy_assert;
@@ -284,6 +716,131 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_try_solve() {
+        let n = 4;
+        let k = 2;
+        let mut lu = Mat::with_dims(|_, _| random::<f64>(), n, n);
+        let a = lu.clone();
+        let mut perm = vec![0; n];
+        let mut perm_inv = vec![0; n];
+        let mut short_perm = vec![0; n - 1];
+
+        let mut small_mem = GlobalMemBuffer::new(StackReq::new::<u8>(0));
+        fancy_assert!(matches!(
+            crate::partial_pivoting::compute::try_lu_in_place(
+                lu.as_mut(),
+                &mut short_perm,
+                &mut perm_inv,
+                Parallelism::None,
+                DynStack::new(&mut small_mem),
+                Default::default(),
+            ),
+            Err(FaerError::DimensionMismatch {
+                operand: "perm",
+                ..
+            })
+        ));
+
+        let (_, row_perm) = crate::partial_pivoting::compute::try_lu_in_place(
+            lu.as_mut(),
+            &mut perm,
+            &mut perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(n, n, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        )
+        .unwrap();
+        let row_perm = row_perm.rb();
+
+        let rhs = Mat::with_dims(|_, _| random::<f64>(), n, k);
+        let mut sol = Mat::zeros(n, k);
+
+        fancy_assert!(matches!(
+            try_solve_to(
+                sol.as_mut(),
+                lu.as_ref(),
+                Conj::No,
+                row_perm,
+                rhs.as_ref(),
+                Conj::No,
+                Parallelism::None,
+                DynStack::new(&mut small_mem),
+            ),
+            Err(FaerError::InsufficientWorkspace { .. })
+        ));
+        fancy_assert!(matches!(
+            try_solve_in_place(
+                lu.as_ref(),
+                Conj::No,
+                row_perm,
+                sol.as_mut().submatrix(0, 0, n - 1, k),
+                Conj::No,
+                Parallelism::None,
+                make_stack!(solve_req::<f64>(n, n, k, Parallelism::None).unwrap()),
+            ),
+            Err(FaerError::DimensionMismatch { operand: "rhs", .. })
+        ));
+
+        try_solve_to(
+            sol.as_mut(),
+            lu.as_ref(),
+            Conj::No,
+            row_perm,
+            rhs.as_ref(),
+            Conj::No,
+            Parallelism::None,
+            make_stack!(solve_req::<f64>(n, n, k, Parallelism::None).unwrap()),
+        )
+        .unwrap();
+        let mut rec = Mat::zeros(n, k);
+        matmul(
+            rec.as_mut(),
+            Conj::No,
+            a.as_ref(),
+            Conj::No,
+            sol.as_ref(),
+            Conj::No,
+            None,
+            1.0,
+            Parallelism::None,
+        );
+        for j in 0..k {
+            for i in 0..n {
+                fancy_assert!((rec[(i, j)] - rhs[(i, j)]).abs() < 1e-10);
+            }
+        }
+
+        // singular matrix, with a zero pivot in the last position
+        let mut lu = Mat::with_dims(|i, j| (i + j) as f64, 3, 3);
+        let mut perm = vec![0; 3];
+        let mut perm_inv = vec![0; 3];
+        let (_, row_perm) = lu_in_place(
+            lu.as_mut(),
+            &mut perm,
+            &mut perm_inv,
+            Parallelism::None,
+            make_stack!(
+                lu_in_place_req::<f64>(3, 3, Parallelism::None, Default::default()).unwrap()
+            ),
+            Default::default(),
+        );
+        let mut rhs = Mat::with_dims(|_, _| 1.0, 3, 1);
+        fancy_assert!(
+            try_solve_in_place(
+                lu.as_ref(),
+                Conj::No,
+                row_perm.rb(),
+                rhs.as_mut(),
+                Conj::No,
+                Parallelism::None,
+                make_stack!(solve_req::<f64>(3, 3, 1, Parallelism::None).unwrap()),
+            ) == Err(FaerError::Singular { index: 2 })
+        );
+    }
+
     fn test_solve_to<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
         (0..32).chain((1..8).map(|i| i * 32)).for_each(|n| {
             for conj_lhs in [Conj::No, Conj::Yes] {
@@ -451,4 +1008,99 @@ mod tests {
         test_solve_to(|| c32::new(random(), random()), 2e-1_f32);
         test_solve_transpose_to(|| c32::new(random(), random()), 2e-1_f32);
     }
+
+    fn test_solve_expert<T: ComplexField<Real = f64>>(mut gen: impl FnMut() -> T) {
+        for n in [0, 1, 2, 3, 4, 10, 32, 100] {
+            for conj_lhs in [Conj::No, Conj::Yes] {
+                for scale in [1.0, 1e-8] {
+                    // scaling the first column makes the matrix badly conditioned
+                    let a = Mat::with_dims(
+                        |_, j| {
+                            if j == 0 {
+                                gen().scale(scale)
+                            } else {
+                                gen()
+                            }
+                        },
+                        n,
+                        n,
+                    );
+                    let mut lu = a.clone();
+
+                    let k = 3;
+                    let x_true = Mat::with_dims(|_, _| gen(), n, k);
+                    let mut rhs = Mat::zeros(n, k);
+                    matmul(
+                        rhs.as_mut(),
+                        Conj::No,
+                        a.as_ref(),
+                        conj_lhs,
+                        x_true.as_ref(),
+                        Conj::No,
+                        None,
+                        T::one(),
+                        Parallelism::None,
+                    );
+
+                    let mut row_perm = vec![0_usize; n];
+                    let mut row_perm_inv = vec![0_usize; n];
+                    let parallelism = Parallelism::Rayon(0);
+                    let (_, row_perm) = lu_in_place(
+                        lu.as_mut(),
+                        &mut row_perm,
+                        &mut row_perm_inv,
+                        parallelism,
+                        make_stack!(
+                            lu_in_place_req::<T>(n, n, parallelism, Default::default()).unwrap()
+                        ),
+                        Default::default(),
+                    );
+
+                    let mut sol = Mat::<T>::zeros(n, k);
+                    let mut berr = Mat::<f64>::zeros(k, 1);
+                    let mut ferr = Mat::<f64>::zeros(k, 1);
+                    solve_expert_to(
+                        sol.as_mut(),
+                        berr.as_mut().col(0),
+                        ferr.as_mut().col(0),
+                        a.as_ref(),
+                        lu.as_ref(),
+                        conj_lhs,
+                        row_perm.rb(),
+                        rhs.as_ref(),
+                        Conj::No,
+                        parallelism,
+                        make_stack!(solve_expert_req::<T>(n, k, parallelism).unwrap()),
+                        Default::default(),
+                    );
+
+                    for j in 0..k {
+                        let mut error = 0.0f64;
+                        let mut x_norm = 0.0f64;
+                        for i in 0..n {
+                            error = error.max((sol[(i, j)] - x_true[(i, j)]).abs());
+                            x_norm = x_norm.max(sol[(i, j)].abs());
+                        }
+                        fancy_assert!(berr[(j, 0)] < 1e-14);
+                        if n > 0 {
+                            fancy_assert!(error <= ferr[(j, 0)] * x_norm);
+                            if scale == 1.0 {
+                                fancy_assert!(ferr[(j, 0)] < 1e-6);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_expert_f64() {
+        test_solve_expert(random::<f64>);
+    }
+
+    #[test]
+    fn test_solve_expert_c64() {
+        test_solve_expert(|| c64::new(random(), random()));
+    }
 }
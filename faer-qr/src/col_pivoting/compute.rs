@@ -6,7 +6,11 @@ use core::{
 
 use assert2::{assert as fancy_assert, debug_assert as fancy_debug_assert};
 use dyn_stack::{DynStack, SizeOverflow, StackReq};
-use faer_core::{permutation::swap_cols, ColMut, ColRef, ComplexField, MatMut, Parallelism};
+use faer_core::{
+    error::{check_dimensions, check_workspace, FaerError},
+    permutation::swap_cols,
+    ColMut, ColRef, ComplexField, MatMut, Parallelism,
+};
 use pulp::{as_arrays, as_arrays_mut, Simd};
 use reborrow::*;
 
@@ -471,6 +475,48 @@ pub fn qr_in_place<T: ComplexField>(
     })
 }
 
+/// Computes the QR decomposition with column pivoting of the given matrix, replacing the matrix
+/// with its factors in place.
+///
+/// This is the fallible variant of [`qr_in_place`], which returns an error instead of panicking
+/// if `matrix` doesn't have a unit row stride, if the dimensions of `householder_coeffs` or
+/// `col_transpositions` don't match the dimensions of `matrix`, or if the provided memory in
+/// `stack` is insufficient.
+pub fn try_qr_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_coeffs: ColMut<'_, T>,
+    col_transpositions: &mut [usize],
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: ColPivQrComputeParams,
+) -> Result<usize, FaerError> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = m.min(n);
+    if matrix.row_stride() != 1 {
+        return Err(FaerError::StrideRequirement { operand: "matrix" });
+    }
+    check_dimensions(
+        "householder_coeffs",
+        (householder_coeffs.nrows(), 1),
+        (size, 1),
+    )?;
+    check_dimensions(
+        "col_transpositions",
+        (col_transpositions.len(), 1),
+        (size, 1),
+    )?;
+    check_workspace(&stack, qr_in_place_req::<T>(m, n, parallelism))?;
+    Ok(qr_in_place(
+        matrix,
+        householder_coeffs,
+        col_transpositions,
+        parallelism,
+        stack,
+        params,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -490,6 +536,50 @@ mod tests {
 
     type T = c64;
 
+    #[test]
+    fn test_try_qr() {
+        let mut mat = Mat::with_dims(|_, _| T::new(random(), random()), 5, 3);
+        let mut householder = Mat::zeros(3, 1);
+        let mut transpositions = vec![0; 3];
+
+        let err = try_qr_in_place(
+            mat.as_mut().transpose(),
+            householder.as_mut().col(0),
+            &mut transpositions,
+            Parallelism::None,
+            placeholder_stack!(),
+            Default::default(),
+        );
+        assert_eq!(err, Err(FaerError::StrideRequirement { operand: "matrix" }));
+
+        let err = try_qr_in_place(
+            mat.as_mut(),
+            householder.as_mut().col(0),
+            &mut transpositions[..2],
+            Parallelism::None,
+            placeholder_stack!(),
+            Default::default(),
+        );
+        assert_eq!(
+            err,
+            Err(FaerError::DimensionMismatch {
+                operand: "col_transpositions",
+                expected: (3, 1),
+                found: (2, 1),
+            })
+        );
+
+        try_qr_in_place(
+            mat.as_mut(),
+            householder.as_mut().col(0),
+            &mut transpositions,
+            Parallelism::None,
+            placeholder_stack!(),
+            Default::default(),
+        )
+        .unwrap();
+    }
+
     fn reconstruct_factors<T: ComplexField>(
         qr_factors: MatRef<'_, T>,
         householder: ColRef<'_, T>,
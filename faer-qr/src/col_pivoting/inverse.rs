@@ -0,0 +1,228 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    permutation::{permute_rows, PermutationIndicesRef},
+    temp_mat_req, temp_mat_uninit, ColRef, ComplexField, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+#[track_caller]
+fn invert_impl<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: Option<MatRef<'_, T>>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let qr_factors = match qr_factors {
+        Some(qr_factors) => qr_factors,
+        None => dst.rb(),
+    };
+
+    // A P = QR
+    // A^-1 = P R^-1 Q^*
+
+    let n = qr_factors.ncols();
+
+    temp_mat_uninit! {
+        let (mut inv, stack) = unsafe { temp_mat_uninit::<T>(n, n, stack) };
+    }
+
+    // inv <- R^-1 Q^*
+    crate::no_pivoting::inverse::invert_to(
+        inv.rb_mut(),
+        qr_factors,
+        householder_factor,
+        parallelism,
+        stack,
+    );
+
+    // dst <- P R^-1 Q^*
+    permute_rows(dst, inv.rb(), col_perm.inverse());
+}
+
+/// Computes the size and alignment of required workspace for computing the inverse of a
+/// matrix, given its column pivoting QR decomposition.
+pub fn invert_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    temp_mat_req::<T>(nrows, ncols)?.try_and(crate::no_pivoting::inverse::invert_req::<T>(
+        nrows,
+        ncols,
+        parallelism,
+    )?)
+}
+
+/// Computes the inverse of a matrix, given its column pivoting QR decomposition, and stores the
+/// result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if the QR factors are not a square matrix.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the matrix.
+/// - Panics if the column permutation doesn't have the same dimension as the matrix.
+/// - Panics if the destination shape doesn't match the shape of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn invert_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = qr_factors.nrows();
+    fancy_assert!(qr_factors.ncols() == n);
+    fancy_assert!(householder_factor.nrows() == n);
+    fancy_assert!(col_perm.len() == n);
+    fancy_assert!(dst.nrows() == n);
+    fancy_assert!(dst.ncols() == n);
+    invert_impl(
+        dst,
+        Some(qr_factors),
+        householder_factor,
+        col_perm,
+        parallelism,
+        stack,
+    )
+}
+
+/// Computes the inverse of a matrix, given its column pivoting QR decomposition, and stores the
+/// result in `qr_factors`.
+///
+/// # Panics
+///
+/// - Panics if the QR factors are not a square matrix.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the matrix.
+/// - Panics if the column permutation doesn't have the same dimension as the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn invert_in_place<T: ComplexField>(
+    qr_factors: MatMut<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = qr_factors.nrows();
+    fancy_assert!(qr_factors.ncols() == n);
+    fancy_assert!(householder_factor.nrows() == n);
+    fancy_assert!(col_perm.len() == n);
+    invert_impl(
+        qr_factors,
+        None,
+        householder_factor,
+        col_perm,
+        parallelism,
+        stack,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::col_pivoting::compute::qr_in_place;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, mul::matmul, Conj, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn test_inverse<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for n in [0, 1, 2, 4, 20, 63, 100, 256] {
+            let a = Mat::with_dims(|_, _| gen(), n, n);
+            let mut qr = a.clone();
+            let mut householder = Mat::zeros(n, 1);
+            let mut transpositions = vec![0; n];
+            let parallelism = Parallelism::Rayon(0);
+
+            qr_in_place(
+                qr.as_mut(),
+                householder.as_mut().col(0),
+                &mut transpositions,
+                parallelism,
+                make_stack!(StackReq::default()),
+                Default::default(),
+            );
+
+            let mut perm = (0..n).collect::<Vec<_>>();
+            let mut perm_inv = vec![0; n];
+            for (k, &t) in transpositions.iter().enumerate() {
+                perm.swap(k, t);
+            }
+            for (i, &p) in perm.iter().enumerate() {
+                perm_inv[p] = i;
+            }
+            let col_perm = unsafe { PermutationIndicesRef::new_unchecked(&perm, &perm_inv) };
+
+            let mut inv = Mat::zeros(n, n);
+            invert_to(
+                inv.as_mut(),
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                col_perm,
+                parallelism,
+                make_stack!(invert_req::<T>(n, n, parallelism).unwrap()),
+            );
+
+            let mut inv_in_place = qr.clone();
+            invert_in_place(
+                inv_in_place.as_mut(),
+                householder.as_ref().col(0),
+                col_perm,
+                parallelism,
+                make_stack!(invert_req::<T>(n, n, parallelism).unwrap()),
+            );
+
+            let mut prod = Mat::zeros(n, n);
+            matmul(
+                prod.as_mut(),
+                Conj::No,
+                a.as_ref(),
+                Conj::No,
+                inv.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                parallelism,
+            );
+
+            for j in 0..n {
+                for i in 0..n {
+                    let target = if i == j { T::one() } else { T::zero() };
+                    fancy_assert!((prod[(i, j)] - target).abs() < epsilon);
+                    fancy_assert!((inv_in_place[(i, j)] - inv[(i, j)]).abs() < epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse_f64() {
+        test_inverse(random::<f64>, 1e-6);
+    }
+
+    #[test]
+    fn test_inverse_f32() {
+        test_inverse(random::<f32>, 1e-1);
+    }
+
+    #[test]
+    fn test_inverse_c64() {
+        test_inverse(|| c64::new(random(), random()), 1e-6);
+    }
+
+    #[test]
+    fn test_inverse_c32() {
+        test_inverse(|| c32::new(random(), random()), 1e-1);
+    }
+}
@@ -1 +1,10 @@
+//! The column pivoting QR decomposition is such that:
+//! $$AP = QR,$$
+//! where $P$ is a permutation matrix, $Q$ is a unitary matrix, stored implicitly as a sequence of
+//! householder reflections, and $R$ is an upper trapezoidal matrix.
+
 pub mod compute;
+pub mod inverse;
+pub mod reconstruct;
+pub mod solve;
+pub mod spaces;
@@ -0,0 +1,225 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    permutation::{permute_cols, PermutationIndicesRef},
+    temp_mat_req, temp_mat_uninit, ColRef, ComplexField, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+pub use crate::no_pivoting::reconstruct::{
+    extract_full_q, extract_full_q_req, extract_thin_q, extract_thin_q_req,
+};
+
+#[track_caller]
+fn reconstruct_impl<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: Option<MatRef<'_, T>>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let qr_factors = match qr_factors {
+        Some(qr_factors) => qr_factors,
+        None => dst.rb(),
+    };
+
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+
+    temp_mat_uninit! {
+        let (mut qr, stack) = unsafe { temp_mat_uninit::<T>(m, n, stack) };
+    }
+
+    // qr <- A P
+    crate::no_pivoting::reconstruct::reconstruct_to(
+        qr.rb_mut(),
+        qr_factors,
+        householder_factor,
+        parallelism,
+        stack,
+    );
+
+    // dst <- A
+    permute_cols(dst, qr.rb(), col_perm.inverse());
+}
+
+/// Computes the reconstructed matrix, given its column pivoting QR decomposition, and stores the
+/// result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if the column permutation doesn't have the same dimension as the number of columns of
+///   the matrix.
+/// - Panics if the destination shape doesn't match the shape of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn reconstruct_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(householder_factor.nrows() == qr_factors.nrows().min(qr_factors.ncols()));
+    fancy_assert!(col_perm.len() == qr_factors.ncols());
+    fancy_assert!(dst.nrows() == qr_factors.nrows());
+    fancy_assert!(dst.ncols() == qr_factors.ncols());
+    reconstruct_impl(
+        dst,
+        Some(qr_factors),
+        householder_factor,
+        col_perm,
+        parallelism,
+        stack,
+    )
+}
+
+/// Computes the reconstructed matrix, given its column pivoting QR decomposition, and stores the
+/// result in `qr_factors`.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if the column permutation doesn't have the same dimension as the number of columns of
+///   the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn reconstruct_in_place<T: ComplexField>(
+    qr_factors: MatMut<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(householder_factor.nrows() == qr_factors.nrows().min(qr_factors.ncols()));
+    fancy_assert!(col_perm.len() == qr_factors.ncols());
+    reconstruct_impl(
+        qr_factors,
+        None,
+        householder_factor,
+        col_perm,
+        parallelism,
+        stack,
+    )
+}
+
+/// Computes the size and alignment of required workspace for reconstructing a matrix,
+/// given its column pivoting QR decomposition.
+pub fn reconstruct_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    temp_mat_req::<T>(nrows, ncols)?.try_and(crate::no_pivoting::reconstruct::reconstruct_req::<T>(
+        nrows,
+        ncols,
+        parallelism,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::col_pivoting::compute::qr_in_place;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn test_reconstruct<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for (m, n) in [
+            (0, 0),
+            (1, 1),
+            (4, 4),
+            (6, 3),
+            (3, 6),
+            (20, 20),
+            (63, 32),
+            (32, 63),
+            (256, 256),
+        ] {
+            let a = Mat::with_dims(|_, _| gen(), m, n);
+            let mut qr = a.clone();
+            let size = m.min(n);
+            let mut householder = Mat::zeros(size, 1);
+            let mut transpositions = vec![0; size];
+            let parallelism = Parallelism::Rayon(0);
+
+            qr_in_place(
+                qr.as_mut(),
+                householder.as_mut().col(0),
+                &mut transpositions,
+                parallelism,
+                make_stack!(StackReq::default()),
+                Default::default(),
+            );
+
+            let mut perm = (0..n).collect::<Vec<_>>();
+            let mut perm_inv = vec![0; n];
+            for (k, &t) in transpositions.iter().enumerate() {
+                perm.swap(k, t);
+            }
+            for (i, &p) in perm.iter().enumerate() {
+                perm_inv[p] = i;
+            }
+            let col_perm = unsafe { PermutationIndicesRef::new_unchecked(&perm, &perm_inv) };
+
+            let mut reconstructed = Mat::zeros(m, n);
+            reconstruct_to(
+                reconstructed.as_mut(),
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                col_perm,
+                parallelism,
+                make_stack!(reconstruct_req::<T>(m, n, parallelism).unwrap()),
+            );
+
+            let mut reconstructed_in_place = qr.clone();
+            reconstruct_in_place(
+                reconstructed_in_place.as_mut(),
+                householder.as_ref().col(0),
+                col_perm,
+                parallelism,
+                make_stack!(reconstruct_req::<T>(m, n, parallelism).unwrap()),
+            );
+
+            for j in 0..n {
+                for i in 0..m {
+                    fancy_assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < epsilon);
+                    fancy_assert!((reconstructed_in_place[(i, j)] - a[(i, j)]).abs() < epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_f64() {
+        test_reconstruct(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_reconstruct_f32() {
+        test_reconstruct(random::<f32>, 1e-3);
+    }
+
+    #[test]
+    fn test_reconstruct_c64() {
+        test_reconstruct(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_reconstruct_c32() {
+        test_reconstruct(|| c32::new(random(), random()), 1e-3);
+    }
+}
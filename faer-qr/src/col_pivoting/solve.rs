@@ -0,0 +1,562 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    householder::{
+        apply_householder_sequence_on_the_left, apply_householder_sequence_on_the_left_req,
+    },
+    permutation::{permute_rows, PermutationIndicesRef},
+    solve::solve_upper_triangular_in_place,
+    temp_mat_req, temp_mat_uninit, temp_mat_zeroed,
+    zip::{Diag, MatUninit},
+    ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+use crate::no_pivoting;
+
+/// Specifies which least squares solution is computed when the matrix is rank deficient.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LeastSquaresSolution {
+    /// The basic solution, which has at most `rank` nonzero components.
+    Basic,
+    /// The solution with the minimum euclidean norm.
+    MinimumNorm,
+}
+
+/// Computes the numerical rank of a matrix, given its column pivoting QR decomposition.
+///
+/// The diagonal elements of $R$ are nonincreasing in magnitude, and the rank is the number of
+/// leading diagonal elements whose magnitude is greater than `tolerance` times the magnitude of
+/// the first one.
+pub fn numerical_rank<T: ComplexField>(qr_factors: MatRef<'_, T>, tolerance: T::Real) -> usize {
+    let size = qr_factors.nrows().min(qr_factors.ncols());
+    if size == 0 {
+        return 0;
+    }
+
+    let threshold = qr_factors[(0, 0)].abs() * tolerance;
+    (0..size)
+        .take_while(|&k| qr_factors[(k, k)].abs() > threshold)
+        .count()
+}
+
+fn solve_unpermuted_in_place_impl<T: ComplexField>(
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    rank: usize,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    kind: LeastSquaresSolution,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    // A P = QR, where the trailing block R22 of R is treated as zero
+    //
+    // conj(A) X = B is equivalent to A conj(X) = conj(B), so the conjugation of the lhs is
+    // handled by conjugating the rhs and the solution
+    //
+    // for the basic solution:
+    // P^T X = [R11^-1 (Q^* B)[:r]; 0]
+    //
+    // for the minimum norm solution, the complete orthogonal decomposition
+    // [R11 R12] = L^* Z^*
+    // is computed from the QR decomposition [R11 R12]^* = ZL, and
+    // P^T X = Z L^*^-1 (Q^* B)[:r]
+
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    let r = rank;
+    let mut rhs = rhs;
+    let mut stack = stack;
+
+    // rhs[:m] <- ConjA?(ConjB?(B))
+    if conj_lhs != conj_rhs {
+        rhs.rb_mut()
+            .submatrix(0, 0, m, k)
+            .cwise()
+            .for_each(|x| *x = (*x).conj());
+    }
+
+    // rhs[:m] <- Q^* ConjA?(ConjB?(B))
+    apply_householder_sequence_on_the_left(
+        rhs.rb_mut().submatrix(0, 0, m, k),
+        qr_factors,
+        householder_factor,
+        false,
+        parallelism,
+        stack.rb_mut(),
+    );
+
+    // rhs[r:n] <- 0
+    rhs.rb_mut()
+        .submatrix(r, 0, n - r, k)
+        .cwise()
+        .for_each(|x| *x = T::zero());
+
+    match kind {
+        LeastSquaresSolution::Basic => {
+            // rhs[:r] <- R11^-1 rhs[:r]
+            solve_upper_triangular_in_place(
+                qr_factors.submatrix(0, 0, r, r),
+                Conj::No,
+                rhs.rb_mut().submatrix(0, 0, r, k),
+                Conj::No,
+                parallelism,
+            );
+        }
+        LeastSquaresSolution::MinimumNorm => {
+            temp_mat_zeroed! {
+                let (mut z, stack) = temp_mat_zeroed::<T>(n, r, stack.rb_mut());
+            }
+            temp_mat_uninit! {
+                let (mut z_householder, mut stack) = unsafe { temp_mat_uninit::<T>(r, 1, stack) };
+            }
+
+            // z <- [R11 R12]^*
+            z.rb_mut()
+                .transpose()
+                .cwise()
+                .zip(qr_factors.submatrix(0, 0, r, n))
+                .for_each_triangular_upper(Diag::Include, |dst, src| *dst = (*src).conj());
+
+            no_pivoting::compute::qr_in_place(
+                z.rb_mut(),
+                z_householder.rb_mut().col(0),
+                parallelism,
+                stack.rb_mut(),
+                Default::default(),
+            );
+
+            // rhs[:n] <- the minimum norm solution of [R11 R12] X = rhs[:r]
+            no_pivoting::solve::solve_transpose_in_place(
+                z.rb(),
+                z_householder.rb().col(0),
+                Conj::Yes,
+                rhs.rb_mut().submatrix(0, 0, n, k),
+                Conj::No,
+                parallelism,
+                stack,
+            );
+        }
+    }
+
+    if conj_lhs == Conj::Yes {
+        rhs.submatrix(0, 0, n, k)
+            .cwise()
+            .for_each(|x| *x = (*x).conj());
+    }
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by a
+/// matrix, given its column pivoting QR decomposition and its numerical rank.
+pub fn solve_req<T: 'static>(
+    qr_nrows: usize,
+    qr_ncols: usize,
+    rank: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<T>(qr_nrows.max(qr_ncols), rhs_ncols)?,
+        StackReq::try_any_of([
+            apply_householder_sequence_on_the_left_req::<T>(qr_nrows, qr_ncols, rhs_ncols)?,
+            StackReq::try_all_of([
+                temp_mat_req::<T>(qr_ncols, rank)?,
+                temp_mat_req::<T>(rank, 1)?,
+                StackReq::try_any_of([
+                    no_pivoting::compute::qr_in_place_req::<T>(
+                        qr_ncols,
+                        rank,
+                        parallelism,
+                        Default::default(),
+                    )?,
+                    apply_householder_sequence_on_the_left_req::<T>(qr_ncols, rank, rhs_ncols)?,
+                ])?,
+            ])?,
+        ])?,
+    ])
+}
+
+/// Given the column pivoting QR factors of a matrix $A$ with dimensions $m\times n$, its
+/// numerical rank, and a matrix $B$ stored in `rhs`, this function computes a least squares
+/// solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The trailing part of $R$ beyond the numerical rank is treated as zero, and `kind` selects
+/// whether the basic or the minimum norm solution is computed.
+///
+/// `rhs` must have $\max(m, n)$ rows, and $B$ is read from its top $m$ rows. The solution of the
+/// linear system is stored in the top $n$ rows of `rhs`, while the remaining rows are clobbered.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if the column permutation doesn't have the same dimension as the number of columns of
+///   `qr_factors`.
+/// - Panics if `rank` is greater than the minimum of the number of rows and the number of columns
+///   of `qr_factors`.
+/// - Panics if `rhs` doesn't have $\max(m, n)$ rows.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_in_place<T: ComplexField>(
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    rank: usize,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    kind: LeastSquaresSolution,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    fancy_assert!(householder_factor.nrows() == m.min(n));
+    fancy_assert!(col_perm.len() == n);
+    fancy_assert!(rank <= m.min(n));
+    fancy_assert!(rhs.nrows() == m.max(n));
+
+    let mut rhs = rhs;
+    let mut stack = stack;
+
+    solve_unpermuted_in_place_impl(
+        qr_factors,
+        householder_factor,
+        rank,
+        conj_lhs,
+        rhs.rb_mut(),
+        conj_rhs,
+        kind,
+        parallelism,
+        stack.rb_mut(),
+    );
+
+    temp_mat_uninit! {
+        let (mut temp, _) = unsafe { temp_mat_uninit::<T>(n, k, stack) };
+    }
+
+    permute_rows(
+        temp.rb_mut(),
+        rhs.rb().submatrix(0, 0, n, k),
+        col_perm.inverse(),
+    );
+    rhs.submatrix(0, 0, n, k)
+        .cwise()
+        .zip(temp.rb())
+        .for_each(|dst, src| *dst = *src);
+}
+
+/// Given the column pivoting QR factors of a matrix $A$ with dimensions $m\times n$, its
+/// numerical rank, and a matrix $B$ stored in `rhs`, this function computes a least squares
+/// solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The trailing part of $R$ beyond the numerical rank is treated as zero, and `kind` selects
+/// whether the basic or the minimum norm solution is computed.
+///
+/// The solution of the linear system is stored in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if the column permutation doesn't have the same dimension as the number of columns of
+///   `qr_factors`.
+/// - Panics if `rank` is greater than the minimum of the number of rows and the number of columns
+///   of `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as `qr_factors`.
+/// - Panics if `dst` doesn't have the same number of rows as the number of columns of
+///   `qr_factors`, or the same number of columns as `rhs`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    col_perm: PermutationIndicesRef<'_>,
+    rank: usize,
+    conj_lhs: Conj,
+    rhs: MatRef<'_, T>,
+    conj_rhs: Conj,
+    kind: LeastSquaresSolution,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    fancy_assert!(householder_factor.nrows() == m.min(n));
+    fancy_assert!(col_perm.len() == n);
+    fancy_assert!(rank <= m.min(n));
+    fancy_assert!(rhs.nrows() == m);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, k));
+
+    temp_mat_uninit! {
+        let (mut temp, stack) = unsafe { temp_mat_uninit::<T>(m.max(n), k, stack) };
+    }
+
+    MatUninit(temp.rb_mut().submatrix(0, 0, m, k))
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| unsafe { *dst = *src });
+
+    solve_unpermuted_in_place_impl(
+        qr_factors,
+        householder_factor,
+        rank,
+        conj_lhs,
+        temp.rb_mut(),
+        conj_rhs,
+        kind,
+        parallelism,
+        stack,
+    );
+
+    permute_rows(dst, temp.rb().submatrix(0, 0, n, k), col_perm.inverse());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::col_pivoting::compute::qr_in_place;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, mul::matmul, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn norm<T: ComplexField>(mat: MatRef<'_, T>) -> T::Real {
+        let mut norm2 = T::Real::zero();
+        for j in 0..mat.ncols() {
+            for i in 0..mat.nrows() {
+                norm2 = norm2 + (mat[(i, j)] * mat[(i, j)].conj()).real();
+            }
+        }
+        norm2.sqrt()
+    }
+
+    // the error bounds are relative to the norms of the inputs and the solution, since those may
+    // be large when the matrix is ill-conditioned
+
+    fn test_solve_to<T: ComplexField>(
+        mut gen: impl FnMut() -> T,
+        tolerance: T::Real,
+        epsilon: T::Real,
+    ) {
+        for (m, n, r) in [
+            (0, 0, 0),
+            (1, 1, 1),
+            (4, 4, 4),
+            (6, 4, 2),
+            (4, 6, 2),
+            (20, 20, 15),
+            (63, 32, 10),
+            (32, 63, 20),
+            (100, 64, 40),
+        ] {
+            // A = B C is a matrix of rank r
+            let b = Mat::with_dims(|_, _| gen(), m, r);
+            let c = Mat::with_dims(|_, _| gen(), r, n);
+            let mut a = Mat::zeros(m, n);
+            matmul(
+                a.as_mut(),
+                Conj::No,
+                b.as_ref(),
+                Conj::No,
+                c.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+
+            let size = m.min(n);
+            let mut qr = a.clone();
+            let mut householder = Mat::zeros(size, 1);
+            let mut transpositions = vec![0; size];
+            let parallelism = Parallelism::Rayon(0);
+
+            qr_in_place(
+                qr.as_mut(),
+                householder.as_mut().col(0),
+                &mut transpositions,
+                parallelism,
+                make_stack!(StackReq::default()),
+                Default::default(),
+            );
+
+            let mut perm = (0..n).collect::<Vec<_>>();
+            let mut perm_inv = vec![0; n];
+            for (k, &t) in transpositions.iter().enumerate() {
+                perm.swap(k, t);
+            }
+            for (i, &p) in perm.iter().enumerate() {
+                perm_inv[p] = i;
+            }
+            let col_perm = unsafe { PermutationIndicesRef::new_unchecked(&perm, &perm_inv) };
+
+            let rank = numerical_rank(qr.as_ref(), tolerance);
+            fancy_assert!(rank == r);
+
+            for conj_lhs in [Conj::No, Conj::Yes] {
+                for conj_rhs in [Conj::No, Conj::Yes] {
+                    let k = 5;
+                    let rhs = Mat::with_dims(|_, _| gen(), m, k);
+
+                    let mut sol_basic = Mat::<T>::zeros(n, k);
+                    let mut sol_min_norm = Mat::<T>::zeros(n, k);
+                    for (sol, kind) in [
+                        (&mut sol_basic, LeastSquaresSolution::Basic),
+                        (&mut sol_min_norm, LeastSquaresSolution::MinimumNorm),
+                    ] {
+                        solve_to(
+                            sol.as_mut(),
+                            qr.as_ref(),
+                            householder.as_ref().col(0),
+                            col_perm,
+                            rank,
+                            conj_lhs,
+                            rhs.as_ref(),
+                            conj_rhs,
+                            kind,
+                            parallelism,
+                            make_stack!(solve_req::<T>(m, n, rank, k, parallelism).unwrap()),
+                        );
+
+                        // residual = Op_A(A) X - Op_B(B)
+                        let mut residual = Mat::with_dims(
+                            |i, j| match conj_rhs {
+                                Conj::No => rhs[(i, j)],
+                                Conj::Yes => rhs[(i, j)].conj(),
+                            },
+                            m,
+                            k,
+                        );
+                        matmul(
+                            residual.as_mut(),
+                            Conj::No,
+                            a.as_ref(),
+                            conj_lhs,
+                            sol.as_ref(),
+                            Conj::No,
+                            Some(-T::one()),
+                            T::one(),
+                            parallelism,
+                        );
+
+                        let norm_a = norm(a.as_ref());
+                        let scale = norm_a * norm(sol.as_ref()) + norm(rhs.as_ref());
+
+                        // the residual of a least squares solution is orthogonal to the range of
+                        // Op_A(A)
+                        let mut normal = Mat::zeros(n, k);
+                        matmul(
+                            normal.as_mut(),
+                            Conj::No,
+                            a.as_ref().transpose(),
+                            match conj_lhs {
+                                Conj::No => Conj::Yes,
+                                Conj::Yes => Conj::No,
+                            },
+                            residual.as_ref(),
+                            Conj::No,
+                            None,
+                            T::one(),
+                            parallelism,
+                        );
+                        for j in 0..k {
+                            for i in 0..n {
+                                fancy_assert!(normal[(i, j)].abs() < epsilon * norm_a * scale);
+                            }
+                        }
+                    }
+
+                    // the basic solution has at most `rank` nonzero components
+                    for j in 0..k {
+                        for i in rank..n {
+                            fancy_assert!(sol_basic[(perm[i], j)].abs() == T::Real::zero());
+                        }
+                    }
+
+                    // the two solutions differ by an element of the null space of A, to which the
+                    // minimum norm solution is orthogonal
+                    for j in 0..k {
+                        let mut dot = T::zero();
+                        for i in 0..n {
+                            dot = dot
+                                + sol_min_norm[(i, j)].conj()
+                                    * (sol_basic[(i, j)] - sol_min_norm[(i, j)]);
+                        }
+                        let scale = norm(sol_min_norm.as_ref().col(j).as_2d())
+                            * norm(sol_basic.as_ref().col(j).as_2d());
+                        fancy_assert!(dot.abs() <= epsilon * scale);
+                    }
+
+                    // the in place variant computes the same solution
+                    let mut sol_in_place = Mat::with_dims(
+                        |i, j| if i < m { rhs[(i, j)] } else { T::zero() },
+                        m.max(n),
+                        k,
+                    );
+                    solve_in_place(
+                        qr.as_ref(),
+                        householder.as_ref().col(0),
+                        col_perm,
+                        rank,
+                        conj_lhs,
+                        sol_in_place.as_mut(),
+                        conj_rhs,
+                        LeastSquaresSolution::MinimumNorm,
+                        parallelism,
+                        make_stack!(solve_req::<T>(m, n, rank, k, parallelism).unwrap()),
+                    );
+                    let scale = norm(sol_min_norm.as_ref());
+                    for j in 0..k {
+                        for i in 0..n {
+                            fancy_assert!(
+                                (sol_in_place[(i, j)] - sol_min_norm[(i, j)]).abs()
+                                    < epsilon * scale
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_to_f64() {
+        test_solve_to(random::<f64>, 1e-10, 1e-10);
+    }
+
+    #[test]
+    fn test_solve_to_f32() {
+        test_solve_to(random::<f32>, 1e-4, 1e-3);
+    }
+
+    #[test]
+    fn test_solve_to_c64() {
+        test_solve_to(|| c64::new(random(), random()), 1e-10, 1e-10);
+    }
+
+    #[test]
+    fn test_solve_to_c32() {
+        test_solve_to(|| c32::new(random(), random()), 1e-4, 1e-3);
+    }
+}
@@ -0,0 +1,326 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{temp_mat_req, temp_mat_uninit, ComplexField, MatMut, MatRef, Parallelism};
+use reborrow::*;
+
+use super::{
+    compute::{qr_in_place, qr_in_place_req},
+    reconstruct::{extract_full_q, extract_full_q_req, extract_thin_q, extract_thin_q_req},
+    solve::numerical_rank,
+};
+
+fn factorize_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let size = nrows.min(ncols);
+    StackReq::try_all_of([
+        temp_mat_req::<T>(nrows, ncols)?,
+        temp_mat_req::<T>(size, 1)?,
+        StackReq::try_new::<usize>(size)?,
+        qr_in_place_req::<T>(nrows, ncols, parallelism)?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for computing an orthonormal basis of
+/// the null space of a matrix with dimensions $m\times n$.
+pub fn null_space_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        factorize_req::<T>(ncols, nrows, parallelism)?,
+        extract_full_q_req::<T>(ncols, nrows, parallelism)?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for computing an orthonormal basis of
+/// the column space of a matrix with dimensions $m\times n$.
+pub fn column_space_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        factorize_req::<T>(nrows, ncols, parallelism)?,
+        extract_thin_q_req::<T>(nrows, ncols, parallelism)?,
+    ])
+}
+
+/// Computes an orthonormal basis of the null space of the matrix $A$, i.e., of the vectors $x$
+/// such that $Ax = 0$, using the column pivoting QR decomposition of $A^*$.
+///
+/// Given the decomposition $A^*P = QR$, the numerical rank $r$ of $A$ is the number of leading
+/// diagonal elements of $R$ whose absolute value is greater than `tolerance` times the absolute
+/// value of the first one. The first $r$ columns of $Q$ span the row space of $A$, so the
+/// remaining $n - r$ columns span its orthogonal complement, which is the null space of $A$.
+///
+/// The basis is stored in the first $n - r$ columns of `dst`, and $n - r$ is returned. The
+/// remaining columns of `dst` are clobbered.
+///
+/// # Panics
+///
+/// - Panics if `dst` isn't a square matrix with the same number of rows as the number of columns
+///   of `matrix`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn null_space<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    tolerance: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> usize {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, n));
+
+    let mut dst = dst;
+    let size = m.min(n);
+
+    temp_mat_uninit! {
+        let (mut qr, stack) = unsafe { temp_mat_uninit::<T>(n, m, stack) };
+        let (mut householder, stack) = unsafe { temp_mat_uninit::<T>(size, 1, stack) };
+    }
+    let (mut transpositions, mut stack) = stack.make_with(size, |_| 0);
+
+    // qr <- A^*
+    qr.rb_mut()
+        .cwise()
+        .zip(matrix.transpose())
+        .for_each(|dst, src| *dst = src.conj());
+
+    qr_in_place(
+        qr.rb_mut(),
+        householder.rb_mut().col(0),
+        &mut transpositions,
+        parallelism,
+        stack.rb_mut(),
+        Default::default(),
+    );
+    let rank = numerical_rank(qr.rb(), tolerance);
+
+    extract_full_q(
+        dst.rb_mut(),
+        qr.rb(),
+        householder.rb().col(0),
+        parallelism,
+        stack,
+    );
+
+    // move the last n - r columns to the front
+    for j in 0..n - rank {
+        for i in 0..n {
+            let x = dst[(i, rank + j)];
+            dst[(i, j)] = x;
+        }
+    }
+
+    n - rank
+}
+
+/// Computes an orthonormal basis of the column space of the matrix $A$, i.e., of the vectors
+/// $Ax$, using its column pivoting QR decomposition.
+///
+/// Given the decomposition $AP = QR$, the numerical rank $r$ of $A$ is the number of leading
+/// diagonal elements of $R$ whose absolute value is greater than `tolerance` times the absolute
+/// value of the first one. The basis is made of the first $r$ columns of $Q$.
+///
+/// The basis is stored in the first $r$ columns of `dst`, and $r$ is returned. The remaining
+/// columns of `dst` are clobbered.
+///
+/// # Panics
+///
+/// - Panics if `dst` doesn't have the same number of rows as `matrix`, or if its number of
+///   columns isn't equal to the minimum of the number of rows and the number of columns of
+///   `matrix`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn column_space<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    tolerance: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> usize {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = m.min(n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (m, size));
+
+    temp_mat_uninit! {
+        let (mut qr, stack) = unsafe { temp_mat_uninit::<T>(m, n, stack) };
+        let (mut householder, stack) = unsafe { temp_mat_uninit::<T>(size, 1, stack) };
+    }
+    let (mut transpositions, mut stack) = stack.make_with(size, |_| 0);
+
+    qr.rb_mut()
+        .cwise()
+        .zip(matrix)
+        .for_each(|dst, src| *dst = *src);
+
+    qr_in_place(
+        qr.rb_mut(),
+        householder.rb_mut().col(0),
+        &mut transpositions,
+        parallelism,
+        stack.rb_mut(),
+        Default::default(),
+    );
+    let rank = numerical_rank(qr.rb(), tolerance);
+
+    extract_thin_q(dst, qr.rb(), householder.rb().col(0), parallelism, stack);
+
+    rank
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use faer_core::{c64, mul::matmul, Conj, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut dyn_stack::GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn random_low_rank(m: usize, n: usize, rank: usize) -> Mat<c64> {
+        let lhs = Mat::with_dims(|_, _| c64::new(random(), random()), m, rank);
+        let rhs = Mat::with_dims(|_, _| c64::new(random(), random()), rank, n);
+        let mut a = Mat::zeros(m, n);
+        matmul(
+            a.as_mut(),
+            Conj::No,
+            lhs.as_ref(),
+            Conj::No,
+            rhs.as_ref(),
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        a
+    }
+
+    fn assert_orthonormal(basis: MatRef<'_, c64>) {
+        let k = basis.ncols();
+        let mut gram = Mat::zeros(k, k);
+        matmul(
+            gram.as_mut(),
+            Conj::No,
+            basis.transpose(),
+            Conj::Yes,
+            basis,
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        for j in 0..k {
+            for i in 0..k {
+                let target = if i == j { c64::one() } else { c64::zero() };
+                fancy_assert!((gram[(i, j)] - target).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_null_space() {
+        for (m, n) in [(1, 1), (4, 4), (10, 6), (6, 10), (32, 32), (50, 80)] {
+            for rank in [0, 1, 2, m.min(n) / 2, m.min(n)] {
+                let rank = rank.min(m.min(n));
+                let a = random_low_rank(m, n, rank);
+
+                let mut basis = Mat::zeros(n, n);
+                let nullity = null_space(
+                    basis.as_mut(),
+                    a.as_ref(),
+                    1e-10,
+                    Parallelism::None,
+                    make_stack!(null_space_req::<c64>(m, n, Parallelism::None).unwrap()),
+                );
+                fancy_assert!(nullity == n - rank);
+
+                let basis = basis.as_ref().submatrix(0, 0, n, nullity);
+                assert_orthonormal(basis);
+
+                let mut product = Mat::zeros(m, nullity);
+                matmul(
+                    product.as_mut(),
+                    Conj::No,
+                    a.as_ref(),
+                    Conj::No,
+                    basis,
+                    Conj::No,
+                    None,
+                    c64::one(),
+                    Parallelism::None,
+                );
+                for j in 0..nullity {
+                    for i in 0..m {
+                        fancy_assert!(product[(i, j)].abs() < 1e-8);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_column_space() {
+        for (m, n) in [(1, 1), (4, 4), (10, 6), (6, 10), (32, 32), (50, 80)] {
+            for rank in [0, 1, 2, m.min(n) / 2, m.min(n)] {
+                let rank = rank.min(m.min(n));
+                let a = random_low_rank(m, n, rank);
+
+                let mut basis = Mat::zeros(m, m.min(n));
+                let r = column_space(
+                    basis.as_mut(),
+                    a.as_ref(),
+                    1e-10,
+                    Parallelism::None,
+                    make_stack!(column_space_req::<c64>(m, n, Parallelism::None).unwrap()),
+                );
+                fancy_assert!(r == rank);
+
+                let basis = basis.as_ref().submatrix(0, 0, m, r);
+                assert_orthonormal(basis);
+
+                // A - Q Q^* A = 0
+                let mut coeffs = Mat::zeros(r, n);
+                matmul(
+                    coeffs.as_mut(),
+                    Conj::No,
+                    basis.transpose(),
+                    Conj::Yes,
+                    a.as_ref(),
+                    Conj::No,
+                    None,
+                    c64::one(),
+                    Parallelism::None,
+                );
+                let mut residual = a.clone();
+                matmul(
+                    residual.as_mut(),
+                    Conj::No,
+                    basis,
+                    Conj::No,
+                    coeffs.as_ref(),
+                    Conj::No,
+                    Some(c64::one()),
+                    -c64::one(),
+                    Parallelism::None,
+                );
+                for j in 0..n {
+                    for i in 0..m {
+                        fancy_assert!(residual[(i, j)].abs() < 1e-8);
+                    }
+                }
+            }
+        }
+    }
+}
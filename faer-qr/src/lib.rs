@@ -1,2 +1,4 @@
+#![allow(clippy::too_many_arguments)]
+
 pub mod col_pivoting;
 pub mod no_pivoting;
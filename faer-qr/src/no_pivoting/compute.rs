@@ -1,9 +1,12 @@
 use assert2::assert as fancy_assert;
-use dyn_stack::DynStack;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
 use faer_core::{
-    householder::{apply_block_househodler_on_the_left, make_householder_in_place},
-    mul::triangular,
-    temp_mat_uninit, ColMut, ComplexField, Conj, MatMut, MatRef, Parallelism,
+    error::{check_dimensions, check_workspace, FaerError},
+    householder::{
+        apply_block_househodler_on_the_left, default_blocksize, make_householder_factor_unblocked,
+        make_householder_in_place,
+    },
+    temp_mat_req, temp_mat_uninit, ColMut, ComplexField, MatMut, Parallelism,
 };
 use reborrow::*;
 
@@ -74,23 +77,6 @@ fn qr_in_place_unblocked<T: ComplexField>(
     });
 }
 
-#[inline]
-fn default_blocksize(m: usize, n: usize) -> usize {
-    let prod = m * n;
-
-    if prod > 8192 * 8192 {
-        128
-    } else if prod > 2048 * 2048 {
-        64
-    } else if prod > 1024 * 1024 {
-        32
-    } else if prod > 512 * 512 {
-        16
-    } else {
-        8
-    }
-}
-
 fn default_disable_parallelism(m: usize, n: usize) -> bool {
     let prod = m * n;
     prod < 192 * 256
@@ -133,6 +119,25 @@ impl QrComputeParams {
     }
 }
 
+/// Computes the size and alignment of required workspace for performing a QR decomposition.
+pub fn qr_in_place_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+    params: QrComputeParams,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    let (max_blocksize, _, _, _) = params.normalize();
+    let bs = max_blocksize.min(nrows.min(ncols));
+    StackReq::try_all_of([
+        temp_mat_req::<T>(bs, bs)?,
+        StackReq::try_any_of([
+            temp_mat_req::<T>(bs, 1)?,
+            temp_mat_req::<T>(bs, ncols)?.try_and(temp_mat_req::<T>(bs, ncols)?)?,
+        ])?,
+    ])
+}
+
 pub fn qr_in_place<T: ComplexField>(
     mut matrix: MatMut<'_, T>,
     mut householder_factor: ColMut<'_, T>,
@@ -250,116 +255,29 @@ pub fn qr_in_place<T: ComplexField>(
     }
 }
 
-fn make_householder_factor_unblocked<T: ComplexField>(
-    mut householder_factor: MatMut<'_, T>,
-    matrix: MatRef<'_, T>,
-    mut stack: DynStack<'_>,
-) {
+/// Computes the QR decomposition of the given matrix, replacing the matrix with its factors in
+/// place.
+///
+/// This is the fallible variant of [`qr_in_place`], which returns an error instead of panicking
+/// if the number of rows of `householder_factor` isn't equal to the minimum of the number of rows
+/// and the number of columns of `matrix`, or if the provided memory in `stack` is insufficient.
+pub fn try_qr_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_factor: ColMut<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: QrComputeParams,
+) -> Result<(), FaerError> {
     let m = matrix.nrows();
     let n = matrix.ncols();
-    let size = m.min(n);
-
-    fancy_assert!(householder_factor.nrows() == size);
-    fancy_assert!(householder_factor.ncols() == size);
-    fancy_assert!(householder_factor.col_stride() == 1);
-
-    for i in (0..size).rev() {
-        let rs = m - i - 1;
-        let rt = size - i - 1;
-
-        if rt > 0 {
-            let factor = -*householder_factor.rb().get(i, i);
-
-            let mut tail_row = householder_factor.rb_mut().row(i).split_at(size - rt).1;
-
-            use faer_core::mul::triangular::BlockStructure::*;
-
-            let mut dst = tail_row.rb_mut().as_2d();
-            let lhs = matrix.col(i).split_at(m - rs).1.transpose().as_2d();
-
-            let rhs = matrix.submatrix(m - rs, n - rt, rs, rt);
-            triangular::matmul(
-                dst.rb_mut(),
-                Rectangular,
-                Conj::No,
-                lhs.split_at(0, rt).2,
-                Rectangular,
-                Conj::Yes,
-                rhs.split_at(rt, 0).1,
-                UnitTriangularLower,
-                Conj::No,
-                None,
-                factor,
-                Parallelism::None,
-            );
-
-            let lhs = lhs.split_at(0, rt).3;
-            let rhs = rhs.split_at(rt, 0).3;
-
-            struct Gevm<'a, T> {
-                dst: MatMut<'a, T>,
-                lhs: MatRef<'a, T>,
-                rhs: MatRef<'a, T>,
-                beta: T,
-            }
-            impl<'a, T: ComplexField> pulp::WithSimd for Gevm<'a, T> {
-                type Output = ();
-
-                #[inline(always)]
-                fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
-                    let Self {
-                        dst,
-                        lhs,
-                        rhs,
-                        beta,
-                    } = self;
-                    let mut dst = dst.row(0);
-                    let lhs = lhs.row(0).transpose();
-                    fancy_assert!(rhs.row_stride() == 1);
-
-                    for (i, rhs) in rhs.into_col_iter().enumerate() {
-                        let dst = &mut dst[i];
-                        *dst = *dst + beta * dot(simd, lhs, rhs);
-                    }
-                }
-            }
-
-            pulp::Arch::new().dispatch(Gevm {
-                dst: dst.rb_mut(),
-                lhs,
-                rhs,
-                beta: factor,
-            });
-
-            temp_mat_uninit! {
-                let (mut tmp, _) = unsafe { temp_mat_uninit::<T>(rt, 1, stack.rb_mut()) };
-            }
-
-            triangular::matmul(
-                tmp.rb_mut().transpose(),
-                Rectangular,
-                Conj::No,
-                householder_factor.rb().submatrix(i, size - rt, 1, rt),
-                Rectangular,
-                Conj::No,
-                householder_factor
-                    .rb()
-                    .submatrix(size - rt, size - rt, rt, rt),
-                TriangularUpper,
-                Conj::No,
-                None,
-                T::one(),
-                Parallelism::None,
-            );
-            householder_factor
-                .rb_mut()
-                .submatrix(i, size - rt, 1, rt)
-                .row(0)
-                .cwise()
-                .zip(tmp.transpose().row(0))
-                .for_each(|a, b| *a = *b);
-        }
-    }
+    check_dimensions(
+        "householder_factor",
+        (householder_factor.nrows(), 1),
+        (m.min(n), 1),
+    )?;
+    check_workspace(&stack, qr_in_place_req::<T>(m, n, parallelism, params))?;
+    qr_in_place(matrix, householder_factor, parallelism, stack, params);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -368,8 +286,8 @@ mod tests {
     use std::cell::RefCell;
 
     use assert_approx_eq::assert_approx_eq;
-    use dyn_stack::{GlobalMemBuffer, StackReq};
-    use faer_core::{c64, mul::matmul, zip::Diag, ColRef, Mat, MatRef};
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c64, mul::matmul, zip::Diag, ColRef, Conj, Mat, MatRef};
 
     use super::*;
 
@@ -543,4 +461,44 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_try_qr() {
+        let mut mat = Mat::with_dims(|_, _| random_value(), 5, 3);
+        let mut householder = Mat::zeros(3, 1);
+
+        let err = try_qr_in_place(
+            mat.as_mut(),
+            householder.as_mut().submatrix(0, 0, 2, 1).col(0),
+            Parallelism::None,
+            placeholder_stack!(),
+            Default::default(),
+        );
+        assert_eq!(
+            err,
+            Err(FaerError::DimensionMismatch {
+                operand: "householder_factor",
+                expected: (3, 1),
+                found: (2, 1),
+            })
+        );
+
+        let err = try_qr_in_place(
+            mat.as_mut(),
+            householder.as_mut().col(0),
+            Parallelism::None,
+            DynStack::new(&mut GlobalMemBuffer::new(StackReq::new::<u8>(0))),
+            Default::default(),
+        );
+        assert!(matches!(err, Err(FaerError::InsufficientWorkspace { .. })));
+
+        try_qr_in_place(
+            mat.as_mut(),
+            householder.as_mut().col(0),
+            Parallelism::None,
+            placeholder_stack!(),
+            Default::default(),
+        )
+        .unwrap();
+    }
 }
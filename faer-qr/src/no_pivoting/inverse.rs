@@ -0,0 +1,206 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    householder::{
+        apply_householder_sequence_on_the_left, apply_householder_sequence_on_the_left_req,
+    },
+    inverse::invert_upper_triangular_to,
+    temp_mat_req, temp_mat_zeroed, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+#[track_caller]
+fn invert_impl<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: Option<MatRef<'_, T>>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let qr_factors = match qr_factors {
+        Some(qr_factors) => qr_factors,
+        None => dst.rb(),
+    };
+
+    // A = QR
+    // A^-1 = R^-1 Q^*
+    //      = (Q R^*^-1)^*
+
+    let n = qr_factors.ncols();
+
+    temp_mat_zeroed! {
+        let (mut inv, stack) = temp_mat_zeroed::<T>(n, n, stack);
+    }
+
+    // inv <- R^*^-1
+    invert_upper_triangular_to(inv.rb_mut().transpose(), qr_factors, Conj::Yes, parallelism);
+
+    // inv <- Q R^*^-1
+    apply_householder_sequence_on_the_left(
+        inv.rb_mut(),
+        qr_factors,
+        householder_factor,
+        true,
+        parallelism,
+        stack,
+    );
+
+    dst.cwise()
+        .zip(inv.rb().transpose())
+        .for_each(|dst, src| *dst = (*src).conj());
+}
+
+/// Computes the size and alignment of required workspace for computing the inverse of a
+/// matrix, given its QR decomposition.
+pub fn invert_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    StackReq::try_all_of([
+        temp_mat_req::<T>(nrows, ncols)?,
+        apply_householder_sequence_on_the_left_req::<T>(nrows, ncols, ncols)?,
+    ])
+}
+
+/// Computes the inverse of a matrix, given its QR decomposition, and stores the result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if the QR factors are not a square matrix.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the matrix.
+/// - Panics if the destination shape doesn't match the shape of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn invert_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = qr_factors.nrows();
+    fancy_assert!(qr_factors.ncols() == n);
+    fancy_assert!(householder_factor.nrows() == n);
+    fancy_assert!(dst.nrows() == n);
+    fancy_assert!(dst.ncols() == n);
+    invert_impl(
+        dst,
+        Some(qr_factors),
+        householder_factor,
+        parallelism,
+        stack,
+    )
+}
+
+/// Computes the inverse of a matrix, given its QR decomposition, and stores the result in
+/// `qr_factors`.
+///
+/// # Panics
+///
+/// - Panics if the QR factors are not a square matrix.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn invert_in_place<T: ComplexField>(
+    qr_factors: MatMut<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let n = qr_factors.nrows();
+    fancy_assert!(qr_factors.ncols() == n);
+    fancy_assert!(householder_factor.nrows() == n);
+    invert_impl(qr_factors, None, householder_factor, parallelism, stack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::no_pivoting::compute::qr_in_place;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, mul::matmul, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn test_inverse<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for n in [0, 1, 2, 4, 20, 63, 100, 256] {
+            let a = Mat::with_dims(|_, _| gen(), n, n);
+            let mut qr = a.clone();
+            let mut householder = Mat::zeros(n, 1);
+            let parallelism = Parallelism::Rayon(0);
+            qr_in_place(
+                qr.as_mut(),
+                householder.as_mut().col(0),
+                parallelism,
+                make_stack!(StackReq::new::<T>(1024 * 1024)),
+                Default::default(),
+            );
+
+            let mut inv = Mat::zeros(n, n);
+            invert_to(
+                inv.as_mut(),
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                parallelism,
+                make_stack!(invert_req::<T>(n, n, parallelism).unwrap()),
+            );
+
+            let mut inv_in_place = qr.clone();
+            invert_in_place(
+                inv_in_place.as_mut(),
+                householder.as_ref().col(0),
+                parallelism,
+                make_stack!(invert_req::<T>(n, n, parallelism).unwrap()),
+            );
+
+            let mut prod = Mat::zeros(n, n);
+            matmul(
+                prod.as_mut(),
+                Conj::No,
+                a.as_ref(),
+                Conj::No,
+                inv.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                parallelism,
+            );
+
+            for j in 0..n {
+                for i in 0..n {
+                    let target = if i == j { T::one() } else { T::zero() };
+                    fancy_assert!((prod[(i, j)] - target).abs() < epsilon);
+                    fancy_assert!((inv_in_place[(i, j)] - inv[(i, j)]).abs() < epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse_f64() {
+        test_inverse(random::<f64>, 1e-6);
+    }
+
+    #[test]
+    fn test_inverse_f32() {
+        test_inverse(random::<f32>, 1e-1);
+    }
+
+    #[test]
+    fn test_inverse_c64() {
+        test_inverse(|| c64::new(random(), random()), 1e-6);
+    }
+
+    #[test]
+    fn test_inverse_c32() {
+        test_inverse(|| c32::new(random(), random()), 1e-1);
+    }
+}
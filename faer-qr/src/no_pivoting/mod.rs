@@ -1 +1,9 @@
+//! The QR decomposition is such that:
+//! $$A = QR,$$
+//! where $Q$ is a unitary matrix, stored implicitly as a sequence of householder reflections, and
+//! $R$ is an upper trapezoidal matrix.
+
 pub mod compute;
+pub mod inverse;
+pub mod reconstruct;
+pub mod solve;
@@ -0,0 +1,376 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    householder::{
+        apply_householder_sequence_on_the_left, apply_householder_sequence_on_the_left_req,
+    },
+    temp_mat_req, temp_mat_zeroed,
+    zip::Diag,
+    ColRef, ComplexField, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+#[track_caller]
+fn reconstruct_impl<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: Option<MatRef<'_, T>>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let qr_factors = match qr_factors {
+        Some(qr_factors) => qr_factors,
+        None => dst.rb(),
+    };
+
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+
+    temp_mat_zeroed! {
+        let (mut qr, stack) = temp_mat_zeroed::<T>(m, n, stack);
+    }
+
+    // qr <- R
+    qr.rb_mut()
+        .cwise()
+        .zip(qr_factors)
+        .for_each_triangular_upper(Diag::Include, |dst, src| *dst = *src);
+
+    // qr <- Q × R
+    apply_householder_sequence_on_the_left(
+        qr.rb_mut(),
+        qr_factors,
+        householder_factor,
+        true,
+        parallelism,
+        stack,
+    );
+
+    dst.cwise().zip(qr.rb()).for_each(|dst, src| *dst = *src);
+}
+
+#[track_caller]
+fn extract_q_impl<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let mut dst = dst;
+    let size = dst.nrows().min(dst.ncols());
+
+    // dst <- the leading columns of the identity matrix
+    dst.rb_mut().cwise().for_each(|x| *x = T::zero());
+    dst.rb_mut()
+        .submatrix(0, 0, size, size)
+        .diagonal()
+        .cwise()
+        .for_each(|x| *x = T::one());
+
+    apply_householder_sequence_on_the_left(
+        dst,
+        qr_factors,
+        householder_factor,
+        true,
+        parallelism,
+        stack,
+    );
+}
+
+/// Computes the reconstructed matrix, given its QR decomposition, and stores the result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if the destination shape doesn't match the shape of the matrix.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn reconstruct_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(householder_factor.nrows() == qr_factors.nrows().min(qr_factors.ncols()));
+    fancy_assert!(dst.nrows() == qr_factors.nrows());
+    fancy_assert!(dst.ncols() == qr_factors.ncols());
+    reconstruct_impl(
+        dst,
+        Some(qr_factors),
+        householder_factor,
+        parallelism,
+        stack,
+    )
+}
+
+/// Computes the reconstructed matrix, given its QR decomposition, and stores the result in
+/// `qr_factors`.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn reconstruct_in_place<T: ComplexField>(
+    qr_factors: MatMut<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(householder_factor.nrows() == qr_factors.nrows().min(qr_factors.ncols()));
+    reconstruct_impl(qr_factors, None, householder_factor, parallelism, stack)
+}
+
+/// Computes the size and alignment of required workspace for reconstructing a matrix,
+/// given its QR decomposition.
+pub fn reconstruct_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    StackReq::try_all_of([
+        temp_mat_req::<T>(nrows, ncols)?,
+        apply_householder_sequence_on_the_left_req::<T>(nrows, ncols, ncols)?,
+    ])
+}
+
+/// Computes the first $\min(m, n)$ columns of the unitary factor $Q$ of a matrix with
+/// dimensions $m\times n$, given its QR decomposition, and stores the result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if `dst` doesn't have the same number of rows as `qr_factors`, or if its number of
+///   columns isn't equal to the minimum of the number of rows and the number of columns of
+///   `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn extract_thin_q<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let size = qr_factors.nrows().min(qr_factors.ncols());
+    fancy_assert!(householder_factor.nrows() == size);
+    fancy_assert!(dst.nrows() == qr_factors.nrows());
+    fancy_assert!(dst.ncols() == size);
+    extract_q_impl(dst, qr_factors, householder_factor, parallelism, stack)
+}
+
+/// Computes the full unitary factor $Q$ of a matrix with dimensions $m\times n$, given its QR
+/// decomposition, and stores the result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `householder_factor` doesn't have the same number of rows as the minimum of the
+///   number of rows and the number of columns of `qr_factors`.
+/// - Panics if `dst` isn't a square matrix with the same number of rows as `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn extract_full_q<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(householder_factor.nrows() == qr_factors.nrows().min(qr_factors.ncols()));
+    fancy_assert!(dst.nrows() == qr_factors.nrows());
+    fancy_assert!(dst.ncols() == qr_factors.nrows());
+    extract_q_impl(dst, qr_factors, householder_factor, parallelism, stack)
+}
+
+/// Computes the size and alignment of required workspace for extracting the first $\min(m, n)$
+/// columns of the unitary factor of a matrix with dimensions $m\times n$, given its QR
+/// decomposition.
+pub fn extract_thin_q_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    apply_householder_sequence_on_the_left_req::<T>(nrows, ncols, nrows.min(ncols))
+}
+
+/// Computes the size and alignment of required workspace for extracting the full unitary factor of
+/// a matrix with dimensions $m\times n$, given its QR decomposition.
+pub fn extract_full_q_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    apply_householder_sequence_on_the_left_req::<T>(nrows, ncols, nrows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::no_pivoting::compute::qr_in_place;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, mul::matmul, Conj, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn compute_qr<T: ComplexField>(a: MatRef<'_, T>) -> (Mat<T>, Mat<T>) {
+        let m = a.nrows();
+        let n = a.ncols();
+        let mut qr = Mat::with_dims(|i, j| a[(i, j)], m, n);
+        let mut householder = Mat::zeros(m.min(n), 1);
+        qr_in_place(
+            qr.as_mut(),
+            householder.as_mut().col(0),
+            Parallelism::Rayon(0),
+            make_stack!(StackReq::new::<T>(1024 * 1024)),
+            Default::default(),
+        );
+        (qr, householder)
+    }
+
+    fn test_reconstruct<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for (m, n) in [
+            (0, 0),
+            (1, 1),
+            (4, 4),
+            (6, 3),
+            (3, 6),
+            (20, 20),
+            (63, 32),
+            (32, 63),
+            (256, 256),
+        ] {
+            let a = Mat::with_dims(|_, _| gen(), m, n);
+            let (qr, householder) = compute_qr(a.as_ref());
+            let size = m.min(n);
+            let parallelism = Parallelism::Rayon(0);
+
+            let mut reconstructed = Mat::zeros(m, n);
+            reconstruct_to(
+                reconstructed.as_mut(),
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                parallelism,
+                make_stack!(reconstruct_req::<T>(m, n, parallelism).unwrap()),
+            );
+
+            let mut reconstructed_in_place = qr.clone();
+            reconstruct_in_place(
+                reconstructed_in_place.as_mut(),
+                householder.as_ref().col(0),
+                parallelism,
+                make_stack!(reconstruct_req::<T>(m, n, parallelism).unwrap()),
+            );
+
+            for j in 0..n {
+                for i in 0..m {
+                    fancy_assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < epsilon);
+                    fancy_assert!((reconstructed_in_place[(i, j)] - a[(i, j)]).abs() < epsilon);
+                }
+            }
+
+            let mut thin_q = Mat::zeros(m, size);
+            extract_thin_q(
+                thin_q.as_mut(),
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                parallelism,
+                make_stack!(extract_thin_q_req::<T>(m, n, parallelism).unwrap()),
+            );
+
+            let mut full_q = Mat::zeros(m, m);
+            extract_full_q(
+                full_q.as_mut(),
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                parallelism,
+                make_stack!(extract_full_q_req::<T>(m, n, parallelism).unwrap()),
+            );
+
+            // the thin factor is the leading block of the full one
+            for j in 0..size {
+                for i in 0..m {
+                    fancy_assert!((thin_q[(i, j)] - full_q[(i, j)]).abs() < epsilon);
+                }
+            }
+
+            // Q^* Q = I
+            let mut qhq = Mat::zeros(m, m);
+            matmul(
+                qhq.as_mut(),
+                Conj::No,
+                full_q.as_ref().transpose(),
+                Conj::Yes,
+                full_q.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                parallelism,
+            );
+            for j in 0..m {
+                for i in 0..m {
+                    let target = if i == j { T::one() } else { T::zero() };
+                    fancy_assert!((qhq[(i, j)] - target).abs() < epsilon);
+                }
+            }
+
+            // thin Q × R = A
+            let mut r = Mat::zeros(size, n);
+            r.as_mut()
+                .cwise()
+                .zip(qr.as_ref().submatrix(0, 0, size, n))
+                .for_each_triangular_upper(Diag::Include, |dst, src| *dst = *src);
+
+            let mut thin_qr = Mat::zeros(m, n);
+            matmul(
+                thin_qr.as_mut(),
+                Conj::No,
+                thin_q.as_ref(),
+                Conj::No,
+                r.as_ref(),
+                Conj::No,
+                None,
+                T::one(),
+                parallelism,
+            );
+            for j in 0..n {
+                for i in 0..m {
+                    fancy_assert!((thin_qr[(i, j)] - a[(i, j)]).abs() < epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_f64() {
+        test_reconstruct(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_reconstruct_f32() {
+        test_reconstruct(random::<f32>, 1e-3);
+    }
+
+    #[test]
+    fn test_reconstruct_c64() {
+        test_reconstruct(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_reconstruct_c32() {
+        test_reconstruct(|| c32::new(random(), random()), 1e-3);
+    }
+}
@@ -0,0 +1,681 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    error::{check_dimensions, check_workspace, FaerError},
+    householder::{
+        apply_householder_sequence_on_the_left, apply_householder_sequence_on_the_left_req,
+    },
+    solve::*,
+    temp_mat_req, temp_mat_uninit,
+    zip::MatUninit,
+    ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use reborrow::*;
+
+fn solve_in_place_impl<T: ComplexField>(
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    // A = QR
+    // conj(A) = conj(Q) conj(R)
+    //
+    // X = ConjA?(R)^-1 ConjA?(Q^* ConjA?(ConjB?(B)))[:n]
+    //
+    // the outer conjugation of the product is folded into the triangular solve
+
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    let mut rhs = rhs;
+
+    // rhs <- ConjA?(ConjB?(B))
+    if conj_lhs != conj_rhs {
+        rhs.rb_mut().cwise().for_each(|x| *x = (*x).conj());
+    }
+
+    // rhs <- Q^* ConjA?(ConjB?(B))
+    apply_householder_sequence_on_the_left(
+        rhs.rb_mut(),
+        qr_factors,
+        householder_factor,
+        false,
+        parallelism,
+        stack,
+    );
+
+    // rhs[:n] <- ConjA?(R)^-1 ConjA?(Q^* ConjA?(ConjB?(B)))[:n]
+    solve_upper_triangular_in_place(
+        qr_factors.submatrix(0, 0, n, n),
+        conj_lhs,
+        rhs.rb_mut().submatrix(0, 0, n, k),
+        conj_lhs,
+        parallelism,
+    );
+}
+
+fn solve_transpose_in_place_impl<T: ComplexField>(
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    // A = QR
+    //
+    // ConjA?(A)^T X = ConjB?(B)
+    // ConjA?(R)^T ConjA?(Q)^T X = ConjB?(B)
+    //
+    // the minimum norm solution is given by
+    // X = conj(ConjA?(Q)) [ConjA?(R)^T^-1 ConjB?(B); 0]
+    //
+    // if ConjA? is the identity, this is equal to conj(Q [R^*^-1 conj(ConjB?(B)); 0])
+    // otherwise, this is equal to Q [R^*^-1 ConjB?(B); 0]
+
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    let mut rhs = rhs;
+
+    // rhs[:n] <- R^*^-1 conj(ConjB?(B)) or R^*^-1 ConjB?(B)
+    solve_lower_triangular_in_place(
+        qr_factors.submatrix(0, 0, n, n).transpose(),
+        Conj::Yes,
+        rhs.rb_mut().submatrix(0, 0, n, k),
+        if conj_lhs == conj_rhs {
+            Conj::Yes
+        } else {
+            Conj::No
+        },
+        parallelism,
+    );
+
+    // rhs[n:] <- 0
+    rhs.rb_mut()
+        .submatrix(n, 0, m - n, k)
+        .cwise()
+        .for_each(|x| *x = T::zero());
+
+    // rhs <- Q [rhs[:n]; 0]
+    apply_householder_sequence_on_the_left(
+        rhs.rb_mut(),
+        qr_factors,
+        householder_factor,
+        true,
+        parallelism,
+        stack,
+    );
+
+    if conj_lhs == Conj::No {
+        rhs.cwise().for_each(|x| *x = (*x).conj());
+    }
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by a
+/// matrix, given its QR decomposition.
+pub fn solve_req<T: 'static>(
+    qr_nrows: usize,
+    qr_ncols: usize,
+    rhs_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    StackReq::try_all_of([
+        temp_mat_req::<T>(qr_nrows, rhs_ncols)?,
+        apply_householder_sequence_on_the_left_req::<T>(qr_nrows, qr_ncols, rhs_ncols)?,
+    ])
+}
+
+/// Given the QR factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
+/// the least squares solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in the top rows of `rhs`, while the remaining rows
+/// are clobbered.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` has fewer rows than columns.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the number of columns
+///   of `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_in_place<T: ComplexField>(
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(qr_factors.nrows() >= qr_factors.ncols());
+    fancy_assert!(householder_factor.nrows() == qr_factors.ncols());
+    fancy_assert!(rhs.nrows() == qr_factors.nrows());
+    solve_in_place_impl(
+        qr_factors,
+        householder_factor,
+        conj_lhs,
+        rhs,
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+}
+
+/// Given the QR factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
+/// the least squares solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// This is the fallible variant of [`solve_in_place`], which returns an error instead of
+/// panicking if `qr_factors` has fewer rows than columns, if the dimensions of the inputs don't
+/// match, or if the provided memory in `stack` is insufficient.
+pub fn try_solve_in_place<T: ComplexField>(
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> Result<(), FaerError> {
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    check_dimensions("qr_factors", (m, n), (m.max(n), n))?;
+    check_dimensions(
+        "householder_factor",
+        (householder_factor.nrows(), 1),
+        (n, 1),
+    )?;
+    check_dimensions("rhs", (rhs.nrows(), k), (m, k))?;
+    check_workspace(&stack, solve_req::<T>(m, n, k, parallelism))?;
+    solve_in_place(
+        qr_factors,
+        householder_factor,
+        conj_lhs,
+        rhs,
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+    Ok(())
+}
+
+/// Given the QR factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
+/// the least squares solution of the linear system:
+/// $$\text{Op}_A(A)X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` has fewer rows than columns.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the number of columns
+///   of `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as `qr_factors`.
+/// - Panics if `dst` doesn't have the same number of rows as the number of columns of
+///   `qr_factors`, or the same number of columns as `rhs`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatRef<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    fancy_assert!(m >= n);
+    fancy_assert!(householder_factor.nrows() == n);
+    fancy_assert!(rhs.nrows() == m);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, k));
+
+    temp_mat_uninit! {
+        let (mut temp, stack) = unsafe { temp_mat_uninit::<T>(m, k, stack) };
+    }
+
+    MatUninit(temp.rb_mut())
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| unsafe { *dst = *src });
+
+    solve_in_place_impl(
+        qr_factors,
+        householder_factor,
+        conj_lhs,
+        temp.rb_mut(),
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+
+    dst.cwise()
+        .zip(temp.rb().submatrix(0, 0, n, k))
+        .for_each(|dst, src| *dst = *src);
+}
+
+/// Given the QR factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
+/// the minimum norm solution of the linear system:
+/// $$\text{Op}_A(A)^\top X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// $B$ is read from the top rows of `rhs`, which must have as many rows as `qr_factors`, and the
+/// solution of the linear system is stored in `rhs`.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` has fewer rows than columns.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the number of columns
+///   of `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_transpose_in_place<T: ComplexField>(
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    fancy_assert!(qr_factors.nrows() >= qr_factors.ncols());
+    fancy_assert!(householder_factor.nrows() == qr_factors.ncols());
+    fancy_assert!(rhs.nrows() == qr_factors.nrows());
+    solve_transpose_in_place_impl(
+        qr_factors,
+        householder_factor,
+        conj_lhs,
+        rhs,
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+}
+
+/// Given the QR factors of a matrix $A$ and a matrix $B$ stored in `rhs`, this function computes
+/// the minimum norm solution of the linear system:
+/// $$\text{Op}_A(A)^\top X = \text{Op}_B(B).$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.  
+/// $\text{Op}_B$ is either the identity or the conjugation depending on the value of `conj_rhs`.  
+///
+/// The solution of the linear system is stored in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` has fewer rows than columns.
+/// - Panics if `householder_factor` doesn't have the same number of rows as the number of columns
+///   of `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the number of columns of
+///   `qr_factors`.
+/// - Panics if `dst` doesn't have the same number of rows as `qr_factors`, or the same number of
+///   columns as `rhs`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn solve_transpose_to<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    qr_factors: MatRef<'_, T>,
+    householder_factor: ColRef<'_, T>,
+    conj_lhs: Conj,
+    rhs: MatRef<'_, T>,
+    conj_rhs: Conj,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let m = qr_factors.nrows();
+    let n = qr_factors.ncols();
+    let k = rhs.ncols();
+    fancy_assert!(m >= n);
+    fancy_assert!(householder_factor.nrows() == n);
+    fancy_assert!(rhs.nrows() == n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (m, k));
+
+    let mut dst = dst;
+    dst.rb_mut()
+        .submatrix(0, 0, n, k)
+        .cwise()
+        .zip(rhs)
+        .for_each(|dst, src| *dst = *src);
+
+    solve_transpose_in_place_impl(
+        qr_factors,
+        householder_factor,
+        conj_lhs,
+        dst,
+        conj_rhs,
+        parallelism,
+        stack,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::no_pivoting::compute::qr_in_place;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, mul::matmul, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    #[test]
+    fn test_try_solve() {
+        let a = Mat::with_dims(|_, _| random::<f64>(), 6, 4);
+        let (qr, householder) = compute_qr(a.as_ref());
+        let req = solve_req::<f64>(6, 4, 2, Parallelism::None).unwrap();
+
+        let mut rhs = Mat::zeros(5, 2);
+        fancy_assert!(matches!(
+            try_solve_in_place(
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                Conj::No,
+                rhs.as_mut(),
+                Conj::No,
+                Parallelism::None,
+                make_stack!(req),
+            ),
+            Err(FaerError::DimensionMismatch { operand: "rhs", .. })
+        ));
+
+        let mut rhs = Mat::zeros(6, 2);
+        fancy_assert!(matches!(
+            try_solve_in_place(
+                qr.as_ref(),
+                householder.as_ref().col(0),
+                Conj::No,
+                rhs.as_mut(),
+                Conj::No,
+                Parallelism::None,
+                DynStack::new(&mut []),
+            ),
+            Err(FaerError::InsufficientWorkspace { .. })
+        ));
+        fancy_assert!(try_solve_in_place(
+            qr.as_ref(),
+            householder.as_ref().col(0),
+            Conj::No,
+            rhs.as_mut(),
+            Conj::No,
+            Parallelism::None,
+            make_stack!(req),
+        )
+        .is_ok());
+    }
+
+    fn compute_qr<T: ComplexField>(a: MatRef<'_, T>) -> (Mat<T>, Mat<T>) {
+        let m = a.nrows();
+        let n = a.ncols();
+        let mut qr = Mat::with_dims(|i, j| a[(i, j)], m, n);
+        let mut householder = Mat::zeros(m.min(n), 1);
+        qr_in_place(
+            qr.as_mut(),
+            householder.as_mut().col(0),
+            Parallelism::Rayon(0),
+            make_stack!(StackReq::new::<T>(1024 * 1024)),
+            Default::default(),
+        );
+        (qr, householder)
+    }
+
+    fn norm<T: ComplexField>(mat: MatRef<'_, T>) -> T::Real {
+        let mut norm2 = T::Real::zero();
+        for j in 0..mat.ncols() {
+            for i in 0..mat.nrows() {
+                norm2 = norm2 + (mat[(i, j)] * mat[(i, j)].conj()).real();
+            }
+        }
+        norm2.sqrt()
+    }
+
+    // the error bounds are relative to the norms of the inputs and the solution, since those may
+    // be large when the matrix is ill-conditioned
+
+    fn test_solve_to<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for (m, n) in [
+            (0, 0),
+            (1, 1),
+            (4, 4),
+            (6, 3),
+            (20, 20),
+            (63, 32),
+            (100, 64),
+            (256, 256),
+        ] {
+            for conj_lhs in [Conj::No, Conj::Yes] {
+                for conj_rhs in [Conj::No, Conj::Yes] {
+                    let a = Mat::with_dims(|_, _| gen(), m, n);
+                    let (qr, householder) = compute_qr(a.as_ref());
+
+                    let k = 5;
+                    let rhs = Mat::with_dims(|_, _| gen(), m, k);
+                    let mut sol = Mat::<T>::zeros(n, k);
+                    let parallelism = Parallelism::Rayon(0);
+
+                    solve_to(
+                        sol.as_mut(),
+                        qr.as_ref(),
+                        householder.as_ref().col(0),
+                        conj_lhs,
+                        rhs.as_ref(),
+                        conj_rhs,
+                        parallelism,
+                        make_stack!(solve_req::<T>(m, n, k, parallelism).unwrap()),
+                    );
+
+                    // residual = Op_A(A) X - Op_B(B)
+                    let mut residual = Mat::with_dims(
+                        |i, j| match conj_rhs {
+                            Conj::No => rhs[(i, j)],
+                            Conj::Yes => rhs[(i, j)].conj(),
+                        },
+                        m,
+                        k,
+                    );
+                    matmul(
+                        residual.as_mut(),
+                        Conj::No,
+                        a.as_ref(),
+                        conj_lhs,
+                        sol.as_ref(),
+                        Conj::No,
+                        Some(-T::one()),
+                        T::one(),
+                        parallelism,
+                    );
+
+                    let norm_a = norm(a.as_ref());
+                    let scale = norm_a * norm(sol.as_ref()) + norm(rhs.as_ref());
+
+                    // the residual of the least squares solution is orthogonal to the range of
+                    // Op_A(A)
+                    let mut normal = Mat::zeros(n, k);
+                    matmul(
+                        normal.as_mut(),
+                        Conj::No,
+                        a.as_ref().transpose(),
+                        match conj_lhs {
+                            Conj::No => Conj::Yes,
+                            Conj::Yes => Conj::No,
+                        },
+                        residual.as_ref(),
+                        Conj::No,
+                        None,
+                        T::one(),
+                        parallelism,
+                    );
+
+                    for j in 0..k {
+                        for i in 0..n {
+                            fancy_assert!(normal[(i, j)].abs() < epsilon * norm_a * scale);
+                        }
+                    }
+                    if m == n {
+                        for j in 0..k {
+                            for i in 0..m {
+                                fancy_assert!(residual[(i, j)].abs() < epsilon * scale);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn test_solve_transpose_to<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for (m, n) in [
+            (0, 0),
+            (1, 1),
+            (4, 4),
+            (6, 3),
+            (20, 20),
+            (63, 32),
+            (100, 64),
+            (256, 256),
+        ] {
+            for conj_lhs in [Conj::No, Conj::Yes] {
+                for conj_rhs in [Conj::No, Conj::Yes] {
+                    let a = Mat::with_dims(|_, _| gen(), m, n);
+                    let (qr, householder) = compute_qr(a.as_ref());
+
+                    let k = 5;
+                    let rhs = Mat::with_dims(|_, _| gen(), n, k);
+                    let mut sol = Mat::<T>::zeros(m, k);
+                    let parallelism = Parallelism::Rayon(0);
+
+                    solve_transpose_to(
+                        sol.as_mut(),
+                        qr.as_ref(),
+                        householder.as_ref().col(0),
+                        conj_lhs,
+                        rhs.as_ref(),
+                        conj_rhs,
+                        parallelism,
+                        make_stack!(solve_req::<T>(m, n, k, parallelism).unwrap()),
+                    );
+
+                    let mut rhs_reconstructed = Mat::zeros(n, k);
+                    matmul(
+                        rhs_reconstructed.as_mut(),
+                        Conj::No,
+                        a.as_ref().transpose(),
+                        conj_lhs,
+                        sol.as_ref(),
+                        Conj::No,
+                        None,
+                        T::one(),
+                        parallelism,
+                    );
+
+                    let norm_a = norm(a.as_ref());
+                    let scale = norm_a * norm(sol.as_ref()) + norm(rhs.as_ref());
+                    for j in 0..k {
+                        for i in 0..n {
+                            let target = match conj_rhs {
+                                Conj::No => rhs[(i, j)],
+                                Conj::Yes => rhs[(i, j)].conj(),
+                            };
+                            fancy_assert!(
+                                (rhs_reconstructed[(i, j)] - target).abs() < epsilon * scale
+                            );
+                        }
+                    }
+
+                    if m > n {
+                        // the minimum norm solution lies in the range of conj(Op_A(A)), so it is
+                        // left unchanged by the orthogonal projection onto that range. this is
+                        // trivially true for square matrices, so those are skipped
+                        let mut projected = sol.clone();
+                        solve_in_place(
+                            qr.as_ref(),
+                            householder.as_ref().col(0),
+                            match conj_lhs {
+                                Conj::No => Conj::Yes,
+                                Conj::Yes => Conj::No,
+                            },
+                            projected.as_mut(),
+                            Conj::No,
+                            parallelism,
+                            make_stack!(solve_req::<T>(m, n, k, parallelism).unwrap()),
+                        );
+                        let mut sol_reconstructed = Mat::zeros(m, k);
+                        matmul(
+                            sol_reconstructed.as_mut(),
+                            Conj::No,
+                            a.as_ref(),
+                            match conj_lhs {
+                                Conj::No => Conj::Yes,
+                                Conj::Yes => Conj::No,
+                            },
+                            projected.as_ref().submatrix(0, 0, n, k),
+                            Conj::No,
+                            None,
+                            T::one(),
+                            parallelism,
+                        );
+                        let scale = norm_a * norm(projected.as_ref().submatrix(0, 0, n, k))
+                            + norm(sol.as_ref());
+                        for j in 0..k {
+                            for i in 0..m {
+                                fancy_assert!(
+                                    (sol_reconstructed[(i, j)] - sol[(i, j)]).abs()
+                                        < epsilon * scale
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_to_f64() {
+        test_solve_to(random::<f64>, 1e-10_f64);
+        test_solve_transpose_to(random::<f64>, 1e-10_f64);
+    }
+
+    #[test]
+    fn test_solve_to_f32() {
+        test_solve_to(random::<f32>, 1e-3_f32);
+        test_solve_transpose_to(random::<f32>, 1e-3_f32);
+    }
+
+    #[test]
+    fn test_solve_to_c64() {
+        test_solve_to(|| c64::new(random(), random()), 1e-10_f64);
+        test_solve_transpose_to(|| c64::new(random(), random()), 1e-10_f64);
+    }
+
+    #[test]
+    fn test_solve_to_c32() {
+        test_solve_to(|| c32::new(random(), random()), 1e-3_f32);
+        test_solve_transpose_to(|| c32::new(random(), random()), 1e-3_f32);
+    }
+}
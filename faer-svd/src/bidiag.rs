@@ -0,0 +1,503 @@
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    householder::{
+        apply_block_househodler_on_the_left, apply_househodler_on_the_left, default_blocksize,
+        make_householder_factor_unblocked, make_householder_factor_unblocked_req,
+        make_householder_in_place,
+    },
+    mul::matmul,
+    temp_mat_req, temp_mat_uninit, ColMut, ComplexField, Conj, MatMut, Parallelism,
+};
+use reborrow::*;
+
+fn disable_blocking(m: usize, n: usize) -> bool {
+    let prod = m * n;
+    prod < 48 * 48
+}
+
+/// Computes the householder reflection that eliminates `tail` when applied to the column
+/// `[head, tail]`, replacing `tail` with its essential part, and returns its coefficient along
+/// with the new value of `head`.
+fn make_left_reflection<T: ComplexField>(head: T, tail: ColMut<'_, T>) -> (T, T) {
+    let mut tail_squared_norm = T::Real::zero();
+    for &elem in tail.rb() {
+        tail_squared_norm = tail_squared_norm + (elem * elem.conj()).real();
+    }
+
+    if tail_squared_norm == T::Real::zero() {
+        (T::zero(), head)
+    } else {
+        make_householder_in_place(tail, head, tail_squared_norm)
+    }
+}
+
+/// Computes the householder reflection that eliminates `tail` when applied to the row
+/// `[head, tail]` from the right, replacing `tail` with the conjugate of its essential part, and
+/// returns its coefficient along with the new value of `head`.
+fn make_right_reflection<T: ComplexField>(head: T, tail: ColMut<'_, T>) -> (T, T) {
+    let mut tail = tail;
+    let mut tail_squared_norm = T::Real::zero();
+    for &elem in tail.rb() {
+        tail_squared_norm = tail_squared_norm + (elem * elem.conj()).real();
+    }
+
+    if tail_squared_norm == T::Real::zero() {
+        (T::zero(), head)
+    } else {
+        // the reflection is computed from the conjugate of the row, then its essential part is
+        // conjugated back, so that it can be applied to the transposed matrix
+        tail.rb_mut().cwise().for_each(|x| *x = (*x).conj());
+        let (tau, beta) = make_householder_in_place(tail.rb_mut(), head.conj(), tail_squared_norm);
+        tail.rb_mut().cwise().for_each(|x| *x = (*x).conj());
+        (tau, beta.conj())
+    }
+}
+
+fn bidiagonalize_in_place_unblocked<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_left: ColMut<'_, T>,
+    householder_right: ColMut<'_, T>,
+    stack: DynStack<'_>,
+) {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+
+    let mut matrix = matrix;
+    let mut householder_left = householder_left;
+    let mut householder_right = householder_right;
+    let mut stack = stack;
+
+    for k in 0..n {
+        // left reflection, eliminating the elements below the diagonal in the k-th column
+        {
+            let (_, _, col, a22) = matrix.rb_mut().submatrix(k, k, m - k, n - k).split_at(0, 1);
+            let (mut head, mut tail) = col.col(0).split_at(1);
+
+            let (tau, beta) = make_left_reflection(head[0], tail.rb_mut());
+            head[0] = beta;
+            householder_left[k] = tau;
+
+            apply_househodler_on_the_left(a22, tail.rb(), tau, stack.rb_mut());
+        }
+
+        if k + 1 == n {
+            break;
+        }
+
+        // right reflection, eliminating the elements to the right of the superdiagonal in the
+        // k-th row
+        {
+            let (_, row, _, a22) = matrix
+                .rb_mut()
+                .submatrix(k, k + 1, m - k, n - k - 1)
+                .split_at(1, 0);
+            let (mut head, mut tail) = row.row(0).transpose().split_at(1);
+
+            let (tau, beta) = make_right_reflection(head[0], tail.rb_mut());
+            head[0] = beta;
+            householder_right[k] = tau;
+
+            apply_househodler_on_the_left(a22.transpose(), tail.rb(), tau, stack.rb_mut());
+        }
+    }
+}
+
+/// Computes the first `bs` left and right householder reflections of the bidiagonalization of
+/// `matrix`, and applies them to the trailing submatrix as block reflections.
+///
+/// The matrix is left untouched while the reflections of the panel are computed. Each column and
+/// row of the partially reduced matrix is instead obtained on demand by applying the reflections
+/// computed so far to a matrix-vector product with the original matrix, and the reflections are
+/// accumulated in temporary bases.
+fn bidiagonalize_panel<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_left: ColMut<'_, T>,
+    householder_right: ColMut<'_, T>,
+    bs: usize,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let n_right = bs.min(n - 1);
+
+    let mut matrix = matrix;
+    let mut householder_left = householder_left;
+    let mut householder_right = householder_right;
+    let mut stack = stack;
+
+    temp_mat_uninit! {
+        let (mut left_basis, stack) = unsafe { temp_mat_uninit::<T>(m, bs, stack.rb_mut()) };
+        let (mut right_basis, stack) = unsafe { temp_mat_uninit::<T>(n - 1, n_right, stack) };
+        let (col, stack) = unsafe { temp_mat_uninit::<T>(m, 1, stack) };
+        let (row, mut stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+    }
+    let mut col = col.col(0);
+    let mut row = row.col(0);
+
+    for j in 0..bs {
+        // column j of the partially reduced matrix, computed as L A conj(R e_j), where L and R
+        // are the products of the left and right reflections computed so far
+        row.rb_mut().cwise().for_each(|e| *e = T::zero());
+        row[j] = T::one();
+        for i in (0..j).rev() {
+            apply_househodler_on_the_left(
+                row.rb_mut().subrows(i + 1, n - i - 1).as_2d(),
+                right_basis.rb().col(i).subrows(i + 1, n - i - 2),
+                householder_right[i],
+                stack.rb_mut(),
+            );
+        }
+        matmul(
+            col.rb_mut().as_2d(),
+            Conj::No,
+            matrix.rb(),
+            Conj::No,
+            row.rb().as_2d(),
+            Conj::Yes,
+            None,
+            T::one(),
+            parallelism,
+        );
+        for i in 0..j {
+            apply_househodler_on_the_left(
+                col.rb_mut().subrows(i, m - i).as_2d(),
+                left_basis.rb().col(i).subrows(i + 1, m - i - 1),
+                householder_left[i],
+                stack.rb_mut(),
+            );
+        }
+
+        let (head, tail) = col.rb_mut().subrows(j, m - j).split_at(1);
+        let (tau, beta) = make_left_reflection(head[0], tail);
+        householder_left[j] = tau;
+        left_basis[(j, j)] = beta;
+        left_basis
+            .rb_mut()
+            .col(j)
+            .subrows(j + 1, m - j - 1)
+            .cwise()
+            .zip(col.rb().subrows(j + 1, m - j - 1))
+            .for_each(|dst, src| *dst = *src);
+
+        if j + 1 == n {
+            break;
+        }
+
+        // row j of the partially reduced matrix, computed as the transpose of
+        // R^* A^T conj(L^* e_j)
+        col.rb_mut().cwise().for_each(|e| *e = T::zero());
+        col[j] = T::one();
+        for i in (0..j + 1).rev() {
+            apply_househodler_on_the_left(
+                col.rb_mut().subrows(i, m - i).as_2d(),
+                left_basis.rb().col(i).subrows(i + 1, m - i - 1),
+                householder_left[i],
+                stack.rb_mut(),
+            );
+        }
+        matmul(
+            row.rb_mut().as_2d(),
+            Conj::No,
+            matrix.rb().transpose(),
+            Conj::No,
+            col.rb().as_2d(),
+            Conj::Yes,
+            None,
+            T::one(),
+            parallelism,
+        );
+        for i in 0..j {
+            apply_househodler_on_the_left(
+                row.rb_mut().subrows(i + 1, n - i - 1).as_2d(),
+                right_basis.rb().col(i).subrows(i + 1, n - i - 2),
+                householder_right[i],
+                stack.rb_mut(),
+            );
+        }
+
+        let (head, tail) = row.rb_mut().subrows(j + 1, n - j - 1).split_at(1);
+        let (tau, beta) = make_right_reflection(head[0], tail);
+        householder_right[j] = tau;
+        right_basis[(j, j)] = beta;
+        right_basis
+            .rb_mut()
+            .col(j)
+            .subrows(j + 1, n - j - 2)
+            .cwise()
+            .zip(row.rb().subrows(j + 2, n - j - 2))
+            .for_each(|dst, src| *dst = *src);
+    }
+
+    if bs < n {
+        // A22 = (L A conj(R))_22, where the right reflections only act on the columns with
+        // indices greater than zero
+        temp_mat_uninit! {
+            let (t, mut stack) = unsafe { temp_mat_uninit::<T>(bs, bs, stack.rb_mut()) };
+        }
+        let mut t = t.transpose();
+
+        for i in 0..bs {
+            t[(i, i)] = householder_left[i];
+        }
+        make_householder_factor_unblocked(t.rb_mut(), left_basis.rb(), stack.rb_mut());
+        apply_block_househodler_on_the_left(
+            matrix.rb_mut().submatrix(0, 1, m, n - 1),
+            left_basis.rb(),
+            t.rb(),
+            false,
+            parallelism,
+            stack.rb_mut(),
+        );
+
+        for i in 0..bs {
+            t[(i, i)] = householder_right[i];
+        }
+        make_householder_factor_unblocked(t.rb_mut(), right_basis.rb(), stack.rb_mut());
+        apply_block_househodler_on_the_left(
+            matrix.rb_mut().submatrix(bs, 1, m - bs, n - 1).transpose(),
+            right_basis.rb(),
+            t.rb(),
+            false,
+            parallelism,
+            stack.rb_mut(),
+        );
+    }
+
+    for j in 0..bs {
+        matrix
+            .rb_mut()
+            .col(j)
+            .subrows(j, m - j)
+            .cwise()
+            .zip(left_basis.rb().col(j).subrows(j, m - j))
+            .for_each(|dst, src| *dst = *src);
+        if j < n_right {
+            matrix
+                .rb_mut()
+                .row(j)
+                .transpose()
+                .subrows(j + 1, n - j - 1)
+                .cwise()
+                .zip(right_basis.rb().col(j).subrows(j, n - j - 1))
+                .for_each(|dst, src| *dst = *src);
+        }
+    }
+}
+
+/// Computes the size and alignment of required workspace for reducing a matrix to bidiagonal
+/// form.
+pub fn bidiagonalize_in_place_req<T: 'static>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    let bs = default_blocksize(nrows, ncols).min(ncols);
+    StackReq::try_all_of([
+        temp_mat_req::<T>(nrows, bs)?,
+        temp_mat_req::<T>(ncols.saturating_sub(1), bs)?,
+        temp_mat_req::<T>(nrows, 1)?,
+        temp_mat_req::<T>(ncols, 1)?,
+        temp_mat_req::<T>(bs, bs)?,
+        StackReq::try_any_of([
+            temp_mat_req::<T>(nrows.max(ncols), 1)?,
+            make_householder_factor_unblocked_req::<T>(bs)?,
+            temp_mat_req::<T>(bs, nrows.max(ncols))?
+                .try_and(temp_mat_req::<T>(bs, nrows.max(ncols))?)?,
+        ])?,
+    ])
+}
+
+/// Reduces a matrix $A$ with at least as many rows as columns to upper bidiagonal form $B$, such
+/// that
+/// $$A = QBP^*,$$
+/// where $Q$ and $P$ are unitary matrices, each stored as a sequence of householder reflections.
+///
+/// On exit, the diagonal and the first superdiagonal of `matrix` contain the diagonal and the
+/// superdiagonal of $B$.
+///
+/// The essential parts of the left householder reflections are stored below the diagonal, and
+/// their coefficients are stored in `householder_left`, so that the $k$-th reflection acts on
+/// the rows with indices greater than or equal to $k$, and $Q = H_0 H_1 \dots H_{n - 1}$.
+///
+/// The conjugates of the essential parts of the right householder reflections are stored above
+/// the first superdiagonal, and their coefficients are stored in `householder_right`, so that the
+/// $k$-th reflection acts on the columns with indices greater than $k$, and
+/// $P = G_0 G_1 \dots G_{n - 2}$.
+///
+/// # Panics
+///
+/// - Panics if the matrix has fewer rows than columns.
+/// - Panics if `householder_left` doesn't have $n$ rows, where $n$ is the number of columns of
+///   the matrix.
+/// - Panics if `householder_right` doesn't have $\max(n - 1, 0)$ rows.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn bidiagonalize_in_place<T: ComplexField>(
+    matrix: MatMut<'_, T>,
+    householder_left: ColMut<'_, T>,
+    householder_right: ColMut<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    fancy_assert!(m >= n);
+    fancy_assert!(householder_left.nrows() == n);
+    fancy_assert!(householder_right.nrows() == n.saturating_sub(1));
+
+    let mut matrix = matrix;
+    let mut householder_left = householder_left;
+    let mut householder_right = householder_right;
+    let mut stack = stack;
+
+    let mut k = 0;
+    while k < n {
+        let matrix = matrix.rb_mut().submatrix(k, k, m - k, n - k);
+        let householder_left = householder_left.rb_mut().subrows(k, n - k);
+        let householder_right = householder_right.rb_mut().subrows(k, n - k - 1);
+
+        if disable_blocking(m - k, n - k) {
+            bidiagonalize_in_place_unblocked(
+                matrix,
+                householder_left,
+                householder_right,
+                stack.rb_mut(),
+            );
+            break;
+        }
+
+        let bs = default_blocksize(m - k, n - k).min(n - k);
+        bidiagonalize_panel(
+            matrix,
+            householder_left,
+            householder_right,
+            bs,
+            parallelism,
+            stack.rb_mut(),
+        );
+        k += bs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{
+        c64,
+        householder::{
+            apply_householder_sequence_on_the_left, apply_householder_sequence_on_the_left_req,
+        },
+        mul::matmul,
+        Conj, Mat,
+    };
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn test_bidiag<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for (m, n) in [
+            (0, 0),
+            (1, 1),
+            (2, 1),
+            (2, 2),
+            (3, 2),
+            (4, 4),
+            (10, 3),
+            (33, 33),
+            (64, 20),
+            (100, 100),
+            (200, 60),
+            (150, 150),
+            (2000, 2),
+        ] {
+            let a = Mat::with_dims(|_, _| gen(), m, n);
+
+            let mut bidiag = a.clone();
+            let mut householder_left = Mat::zeros(n, 1);
+            let mut householder_right = Mat::zeros(n.saturating_sub(1), 1);
+            bidiagonalize_in_place(
+                bidiag.as_mut(),
+                householder_left.as_mut().col(0),
+                householder_right.as_mut().col(0),
+                Parallelism::Rayon(0),
+                make_stack!(bidiagonalize_in_place_req::<T>(m, n, Parallelism::Rayon(0)).unwrap()),
+            );
+
+            // B, padded to m×n
+            let mut b = Mat::zeros(m, n);
+            for i in 0..n {
+                b[(i, i)] = bidiag[(i, i)];
+                if i + 1 < n {
+                    b[(i, i + 1)] = bidiag[(i, i + 1)];
+                }
+            }
+
+            // Q B
+            apply_householder_sequence_on_the_left(
+                b.as_mut(),
+                bidiag.as_ref(),
+                householder_left.as_ref().col(0),
+                true,
+                Parallelism::Rayon(0),
+                make_stack!(apply_householder_sequence_on_the_left_req::<T>(m, n, n).unwrap()),
+            );
+
+            // P^*, computed as conj(P^T) = conj(P)^*, the essential parts of conj(P) being the
+            // ones stored in the matrix
+            let mut ph = Mat::zeros(n, n);
+            for i in 0..n {
+                ph[(i, i)] = T::one();
+            }
+            if n > 0 {
+                apply_householder_sequence_on_the_left(
+                    ph.as_mut().submatrix(1, 0, n - 1, n),
+                    bidiag.as_ref().submatrix(0, 1, n - 1, n - 1).transpose(),
+                    householder_right.as_ref().col(0),
+                    false,
+                    Parallelism::Rayon(0),
+                    make_stack!(
+                        apply_householder_sequence_on_the_left_req::<T>(n - 1, n - 1, n).unwrap()
+                    ),
+                );
+            }
+            // ph now contains conj(P)^* = P^T, so P^* is its conjugate
+            let mut reconstructed = Mat::zeros(m, n);
+            matmul(
+                reconstructed.as_mut(),
+                Conj::No,
+                b.as_ref(),
+                Conj::No,
+                ph.as_ref(),
+                Conj::Yes,
+                None,
+                T::one(),
+                Parallelism::None,
+            );
+
+            for j in 0..n {
+                for i in 0..m {
+                    fancy_assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bidiag_f64() {
+        test_bidiag(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_bidiag_c64() {
+        test_bidiag(|| c64::new(random(), random()), 1e-10);
+    }
+}
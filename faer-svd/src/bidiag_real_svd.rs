@@ -0,0 +1,382 @@
+use assert2::assert as fancy_assert;
+use faer_core::{error::ConvergenceError, permutation::swap_cols, MatMut, RealField};
+use reborrow::*;
+
+/// Maximum number of implicit QR sweeps performed for each singular value.
+const MAX_ITERATIONS_PER_SINGULAR_VALUE: usize = 30;
+
+/// Returns `(c, s)` such that $cy + sz = \sqrt{y^2 + z^2}$ and $cz - sy = 0$.
+fn givens<T: RealField>(y: T, z: T) -> (T, T) {
+    let r = y.hypot(z);
+    if r == T::zero() {
+        (T::one(), T::zero())
+    } else {
+        (y / r, z / r)
+    }
+}
+
+/// Replaces the columns `p` and `q` of `matrix` by $c x_p + s x_q$ and $c x_q - s x_p$.
+fn rotate_cols<T: RealField>(matrix: Option<&mut MatMut<'_, T>>, p: usize, q: usize, c: T, s: T) {
+    if let Some(matrix) = matrix {
+        for i in 0..matrix.nrows() {
+            let x = matrix[(i, p)];
+            let y = matrix[(i, q)];
+            matrix[(i, p)] = c * x + s * y;
+            matrix[(i, q)] = c * y - s * x;
+        }
+    }
+}
+
+/// Performs an implicit QR sweep with a Wilkinson shift on the unreduced block of the bidiagonal
+/// matrix between the indices `lo` and `hi`, inclusive.
+fn golub_kahan_step<T: RealField>(
+    d: &mut [T],
+    e: &mut [T],
+    lo: usize,
+    hi: usize,
+    u: &mut Option<MatMut<'_, T>>,
+    v: &mut Option<MatMut<'_, T>>,
+) {
+    let two = T::one() + T::one();
+
+    // shift by the eigenvalue of the trailing 2×2 block of B^T B that is closer to its last
+    // diagonal element
+    let t11 = d[hi - 1] * d[hi - 1]
+        + if hi - 1 > lo {
+            e[hi - 2] * e[hi - 2]
+        } else {
+            T::zero()
+        };
+    let t12 = d[hi - 1] * e[hi - 1];
+    let t22 = d[hi] * d[hi] + e[hi - 1] * e[hi - 1];
+    let delta = (t11 - t22) / two;
+    let shift = if t12 == T::zero() {
+        t22
+    } else {
+        let h = delta.hypot(t12);
+        t22 - t12 * t12 / (delta + if delta >= T::zero() { h } else { -h })
+    };
+
+    let mut y = d[lo] * d[lo] - shift;
+    let mut z = d[lo] * e[lo];
+    let mut bulge = T::zero();
+
+    for k in lo..hi {
+        // rotate the columns k and k + 1, which moves the bulge below the diagonal
+        let (c, s) = givens(y, z);
+        if k > lo {
+            e[k - 1] = c * e[k - 1] + s * bulge;
+        }
+        let (a, b) = (d[k], e[k]);
+        d[k] = c * a + s * b;
+        e[k] = c * b - s * a;
+        bulge = s * d[k + 1];
+        d[k + 1] = c * d[k + 1];
+        rotate_cols(v.as_mut(), k, k + 1, c, s);
+
+        // rotate the rows k and k + 1, which moves the bulge above the superdiagonal
+        let (c, s) = givens(d[k], bulge);
+        d[k] = c * d[k] + s * bulge;
+        let (a, b) = (e[k], d[k + 1]);
+        e[k] = c * a + s * b;
+        d[k + 1] = c * b - s * a;
+        if k + 1 < hi {
+            bulge = s * e[k + 1];
+            e[k + 1] = c * e[k + 1];
+        }
+        rotate_cols(u.as_mut(), k, k + 1, c, s);
+
+        y = e[k];
+        z = bulge;
+    }
+}
+
+/// Zeroes the superdiagonal element in the row `i`, whose diagonal element is zero, by rotating
+/// it with the rows below it, up to the row `hi`.
+fn zero_row<T: RealField>(
+    d: &mut [T],
+    e: &mut [T],
+    i: usize,
+    hi: usize,
+    u: &mut Option<MatMut<'_, T>>,
+) {
+    let mut f = e[i];
+    e[i] = T::zero();
+    for j in i + 1..hi + 1 {
+        let (c, s) = givens(d[j], f);
+        d[j] = c * d[j] + s * f;
+        if j < hi {
+            f = -s * e[j];
+            e[j] = c * e[j];
+        }
+        rotate_cols(u.as_mut(), j, i, c, s);
+    }
+}
+
+/// Zeroes the superdiagonal element in the column `hi`, whose diagonal element is zero, by
+/// rotating it with the columns to its left, down to the column `lo`.
+fn zero_col<T: RealField>(
+    d: &mut [T],
+    e: &mut [T],
+    lo: usize,
+    hi: usize,
+    v: &mut Option<MatMut<'_, T>>,
+) {
+    let mut f = e[hi - 1];
+    e[hi - 1] = T::zero();
+    for j in (lo..hi).rev() {
+        let (c, s) = givens(d[j], f);
+        d[j] = c * d[j] + s * f;
+        if j > lo {
+            f = -s * e[j - 1];
+            e[j - 1] = c * e[j - 1];
+        }
+        rotate_cols(v.as_mut(), j, hi, c, s);
+    }
+}
+
+/// Computes the singular values of a real upper bidiagonal matrix $B$, and optionally its
+/// singular vectors, using the implicit QR algorithm with Wilkinson shifts, such that
+/// $$B = U S V^T.$$
+///
+/// `diag` contains the diagonal of $B$, and `superdiag` contains its superdiagonal, such that
+/// `superdiag[i]` is the element at the position $(i, i + 1)$.
+///
+/// On exit, `diag` contains the singular values of $B$ in nonincreasing order, and `superdiag` is
+/// clobbered.
+///
+/// If `u` (resp. `v`) is provided, it is multiplied on the right by the orthogonal matrix whose
+/// columns are the left (resp. right) singular vectors of $B$, in the same order as the singular
+/// values. If it is initially the identity matrix, then on exit it contains the singular vectors
+/// of $B$.
+///
+/// An error is returned if $B$ contains non-finite values, or if one of the singular values fails
+/// to converge within the maximum number of iterations. In that case, the contents of `diag`,
+/// `u` and `v` are unspecified.
+///
+/// # Panics
+///
+/// - Panics if `superdiag` doesn't have $\max(n - 1, 0)$ elements, where $n$ is the length of
+///   `diag`.
+/// - Panics if `u` or `v` is provided and doesn't have $n$ columns.
+#[track_caller]
+pub fn compute_bidiag_real_svd<T: RealField>(
+    diag: &mut [T],
+    superdiag: &mut [T],
+    u: Option<MatMut<'_, T>>,
+    v: Option<MatMut<'_, T>>,
+) -> Result<(), ConvergenceError> {
+    let n = diag.len();
+    fancy_assert!(superdiag.len() == n.saturating_sub(1));
+    if let Some(u) = &u {
+        fancy_assert!(u.ncols() == n);
+    }
+    if let Some(v) = &v {
+        fancy_assert!(v.ncols() == n);
+    }
+
+    let mut u = u;
+    let mut v = v;
+    let d = diag;
+    let e = superdiag;
+    let epsilon = T::epsilon();
+    let min_positive = T::min_positive();
+
+    let mut norm = T::zero();
+    for i in 0..n {
+        if !d[i].is_finite() || (i + 1 < n && !e[i].is_finite()) {
+            return Err(ConvergenceError { index: i });
+        }
+        let abs = d[i].abs();
+        if abs > norm {
+            norm = abs;
+        }
+        if i + 1 < n {
+            let abs = e[i].abs();
+            if abs > norm {
+                norm = abs;
+            }
+        }
+    }
+
+    // scale the matrix so that its largest element is one, to avoid overflow in the shifts
+    if norm > T::zero() {
+        let inv = norm.inv();
+        d.iter_mut().for_each(|x| *x = *x * inv);
+        e.iter_mut().for_each(|x| *x = *x * inv);
+    }
+
+    let mut hi = n;
+    while hi > 1 {
+        let last = hi - 1;
+        let mut iter = 0;
+        loop {
+            // look for negligible superdiagonal elements, to split the matrix
+            for i in 0..last {
+                let abs = e[i].abs();
+                if abs <= epsilon * (d[i].abs() + d[i + 1].abs()) || abs <= min_positive {
+                    e[i] = T::zero();
+                }
+            }
+            if e[last - 1] == T::zero() {
+                break;
+            }
+            if iter == MAX_ITERATIONS_PER_SINGULAR_VALUE {
+                return Err(ConvergenceError { index: last });
+            }
+            iter += 1;
+
+            let mut lo = last - 1;
+            while lo > 0 && e[lo - 1] != T::zero() {
+                lo -= 1;
+            }
+
+            // a negligible diagonal element yields a zero singular value, and allows splitting
+            // the matrix after zeroing the superdiagonal element in the same row or column
+            let zero_idx = (lo..last + 1).find(|&i| d[i].abs() <= epsilon);
+            if let Some(i) = zero_idx {
+                d[i] = T::zero();
+            }
+
+            match zero_idx {
+                Some(i) if i < last => zero_row(d, e, i, last, &mut u),
+                Some(_) => zero_col(d, e, lo, last, &mut v),
+                None => golub_kahan_step(d, e, lo, last, &mut u, &mut v),
+            }
+        }
+        hi -= 1;
+    }
+
+    for (i, d) in d.iter_mut().enumerate() {
+        *d = *d * norm;
+        if *d < T::zero() {
+            *d = -*d;
+            if let Some(v) = v.as_mut() {
+                v.rb_mut().col(i).cwise().for_each(|x| *x = -*x);
+            }
+        }
+    }
+
+    // sort the singular values in nonincreasing order, along with the singular vectors
+    for i in 0..n {
+        let mut max_idx = i;
+        for j in i + 1..n {
+            if d[j] > d[max_idx] {
+                max_idx = j;
+            }
+        }
+        if max_idx != i {
+            d.swap(i, max_idx);
+            if let Some(u) = u.as_mut() {
+                swap_cols(u.rb_mut(), i, max_idx);
+            }
+            if let Some(v) = v.as_mut() {
+                swap_cols(v.rb_mut(), i, max_idx);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use faer_core::{mul::matmul, Conj, Mat, Parallelism};
+    use rand::random;
+
+    fn check_bidiag_svd(diag: &[f64], superdiag: &[f64]) {
+        let n = diag.len();
+
+        let mut s = diag.to_vec();
+        let mut e = superdiag.to_vec();
+        let mut u = Mat::with_dims(|i, j| if i == j { 1.0 } else { 0.0 }, n, n);
+        let mut v = Mat::with_dims(|i, j| if i == j { 1.0 } else { 0.0 }, n, n);
+        compute_bidiag_real_svd(&mut s, &mut e, Some(u.as_mut()), Some(v.as_mut())).unwrap();
+
+        let mut us = u.clone();
+        for j in 0..n {
+            for i in 0..n {
+                us[(i, j)] = u[(i, j)] * s[j];
+            }
+        }
+        let mut reconstructed = Mat::zeros(n, n);
+        matmul(
+            reconstructed.as_mut(),
+            Conj::No,
+            us.as_ref(),
+            Conj::No,
+            v.as_ref().transpose(),
+            Conj::No,
+            None,
+            1.0,
+            Parallelism::None,
+        );
+
+        let norm = diag
+            .iter()
+            .chain(superdiag)
+            .fold(1.0f64, |acc, x| acc.max(x.abs()));
+        for j in 0..n {
+            if j + 1 < n {
+                fancy_assert!(s[j] >= s[j + 1]);
+            }
+            fancy_assert!(s[j] >= 0.0);
+            for i in 0..n {
+                let expected = if i == j {
+                    diag[i]
+                } else if i + 1 == j {
+                    superdiag[i]
+                } else {
+                    0.0
+                };
+                fancy_assert!((reconstructed[(i, j)] - expected).abs() < 1e-12 * norm);
+            }
+        }
+
+        // the singular values are the same when the singular vectors are not requested
+        let mut s_only = diag.to_vec();
+        let mut e = superdiag.to_vec();
+        compute_bidiag_real_svd(&mut s_only, &mut e, None, None).unwrap();
+        for i in 0..n {
+            fancy_assert!((s_only[i] - s[i]).abs() < 1e-12 * norm);
+        }
+    }
+
+    #[test]
+    fn test_bidiag_real_svd() {
+        for n in [0usize, 1, 2, 3, 4, 10, 33, 64] {
+            let diag = (0..n).map(|_| random::<f64>() - 0.5).collect::<Vec<_>>();
+            let superdiag = (0..n.saturating_sub(1))
+                .map(|_| random::<f64>() - 0.5)
+                .collect::<Vec<_>>();
+            check_bidiag_svd(&diag, &superdiag);
+        }
+    }
+
+    #[test]
+    fn test_bidiag_real_svd_zero_diagonal() {
+        let n = 10;
+        for k in [0, 4, n - 1] {
+            let mut diag = (0..n).map(|_| random::<f64>()).collect::<Vec<_>>();
+            let superdiag = (0..n - 1).map(|_| random::<f64>()).collect::<Vec<_>>();
+            diag[k] = 0.0;
+            check_bidiag_svd(&diag, &superdiag);
+        }
+
+        check_bidiag_svd(&[0.0; 5], &[1.0, 0.0, 2.0, 3.0]);
+        check_bidiag_svd(&[1e200, 1e-200, 1.0], &[1e200, 1e-200]);
+    }
+
+    #[test]
+    fn test_bidiag_real_svd_non_finite() {
+        let mut diag = vec![1.0, f64::NAN, 2.0];
+        let mut superdiag = vec![1.0, 1.0];
+        fancy_assert!(compute_bidiag_real_svd(&mut diag, &mut superdiag, None, None).is_err());
+
+        let mut diag = vec![1.0, 2.0, 3.0];
+        let mut superdiag = vec![1.0, f64::INFINITY];
+        fancy_assert!(compute_bidiag_real_svd(&mut diag, &mut superdiag, None, None).is_err());
+    }
+}
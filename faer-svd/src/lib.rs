@@ -0,0 +1,899 @@
+//! The singular value decomposition of a matrix $A$ of shape $(m, n)$ is a decomposition of the
+//! form
+//! $$A = U S V^*,$$
+//! where $U$ and $V$ are unitary matrices whose columns are the left and right singular vectors of
+//! $A$, and $S$ is a real rectangular diagonal matrix whose diagonal contains the singular values
+//! of $A$.
+//!
+//! The decomposition is computed by first reducing $A$ to bidiagonal form using householder
+//! reflections, then computing the singular value decomposition of the bidiagonal matrix using
+//! the implicit QR algorithm.
+//!
+//! The implicit QR algorithm works directly on the diagonal and the superdiagonal, so each sweep
+//! takes $O(n)$ operations, plus the cost of applying its plane rotations to the singular vectors
+//! when they are requested. A Jacobi solver would instead need the bidiagonal matrix to be stored
+//! densely, since its rotations fill in the zero elements, making each sweep cost $O(n^2)$
+//! operations even when only the singular values are computed.
+
+#![allow(clippy::too_many_arguments)]
+
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, SizeOverflow, StackReq};
+use faer_core::{
+    error::ConvergenceError,
+    householder::{
+        apply_householder_sequence_on_the_left, apply_householder_sequence_on_the_left_req,
+    },
+    mul::matmul,
+    temp_mat_req, temp_mat_uninit, temp_mat_zeroed, ColMut, ComplexField, Conj, MatMut, MatRef,
+    Parallelism,
+};
+use reborrow::*;
+
+pub mod bidiag;
+pub mod bidiag_real_svd;
+
+/// Specifies which singular vectors to compute.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ComputeVectors {
+    /// The singular vectors are not computed.
+    No,
+    /// Only the first $\min(m, n)$ singular vectors are computed.
+    Thin,
+    /// All the singular vectors are computed.
+    Full,
+}
+
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct SvdParams {}
+
+fn compute_svd_impl_req<T: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    compute_u: ComputeVectors,
+    compute_v: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let m = nrows;
+    let n = ncols;
+    let u_ncols = match compute_u {
+        ComputeVectors::No => 0,
+        ComputeVectors::Thin => n,
+        ComputeVectors::Full => m,
+    };
+    let v_ncols = if compute_v { n } else { 0 };
+
+    StackReq::try_all_of([
+        temp_mat_req::<T>(m, n)?,
+        temp_mat_req::<T>(n, 1)?,
+        temp_mat_req::<T>(n.saturating_sub(1), 1)?,
+        temp_mat_req::<T>(n, 1)?,
+        temp_mat_req::<T>(n, 1)?,
+        temp_mat_req::<T::Real>(n, if u_ncols > 0 { n } else { 0 })?,
+        temp_mat_req::<T::Real>(n, v_ncols)?,
+        StackReq::try_new::<T::Real>(n)?,
+        StackReq::try_new::<T::Real>(n.saturating_sub(1))?,
+        StackReq::try_any_of([
+            bidiag::bidiagonalize_in_place_req::<T>(m, n, parallelism)?,
+            apply_householder_sequence_on_the_left_req::<T>(m, n, u_ncols)?,
+            apply_householder_sequence_on_the_left_req::<T>(
+                n.saturating_sub(1),
+                n.saturating_sub(1),
+                v_ncols,
+            )?,
+        ])?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for performing a singular value
+/// decomposition.
+pub fn compute_svd_req<T: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    compute_u: ComputeVectors,
+    compute_v: ComputeVectors,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = params;
+    if nrows >= ncols {
+        compute_svd_impl_req::<T>(
+            nrows,
+            ncols,
+            compute_u,
+            compute_v != ComputeVectors::No,
+            parallelism,
+        )
+    } else {
+        compute_svd_impl_req::<T>(
+            ncols,
+            nrows,
+            compute_v,
+            compute_u != ComputeVectors::No,
+            parallelism,
+        )
+    }
+}
+
+/// Returns `value / |value|`, or one if `value` is zero.
+fn phase<T: ComplexField>(value: T) -> T {
+    let abs = value.abs();
+    if abs == T::Real::zero() {
+        T::one()
+    } else {
+        value.scale(abs.inv())
+    }
+}
+
+/// Computes the singular value decomposition of `matrix`, or of its adjoint if
+/// `conj_transpose` is `true`, whose number of rows must be greater than or equal to its number of
+/// columns.
+fn compute_svd_impl<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    conj_transpose: bool,
+    s: ColMut<'_, T::Real>,
+    u: Option<MatMut<'_, T>>,
+    v: Option<MatMut<'_, T>>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+) -> Result<(), ConvergenceError> {
+    let (m, n) = if conj_transpose {
+        (matrix.ncols(), matrix.nrows())
+    } else {
+        (matrix.nrows(), matrix.ncols())
+    };
+    fancy_assert!(m >= n);
+
+    let mut s = s;
+    let mut u = u;
+    let mut v = v;
+    let mut stack = stack;
+
+    temp_mat_uninit! {
+        let (mut bidiag, stack) = unsafe { temp_mat_uninit::<T>(m, n, stack.rb_mut()) };
+        let (mut householder_left, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut householder_right, stack) =
+            unsafe { temp_mat_uninit::<T>(n.saturating_sub(1), 1, stack) };
+        let (mut d_left, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+        let (mut d_right, stack) = unsafe { temp_mat_uninit::<T>(n, 1, stack) };
+    }
+    temp_mat_zeroed! {
+        let (mut x, stack) =
+            temp_mat_zeroed::<T::Real>(n, if u.is_some() { n } else { 0 }, stack);
+        let (mut y, stack) =
+            temp_mat_zeroed::<T::Real>(n, if v.is_some() { n } else { 0 }, stack);
+    }
+    let (mut diag, stack) = stack.make_with(n, |_| T::Real::zero());
+    let (mut superdiag, mut stack) = stack.make_with(n.saturating_sub(1), |_| T::Real::zero());
+
+    let mut householder_left = householder_left.rb_mut().col(0);
+    let mut householder_right = householder_right.rb_mut().col(0);
+    let mut d_left = d_left.rb_mut().col(0);
+    let mut d_right = d_right.rb_mut().col(0);
+
+    if conj_transpose {
+        bidiag
+            .rb_mut()
+            .cwise()
+            .zip(matrix.transpose())
+            .for_each(|dst, src| *dst = (*src).conj());
+    } else {
+        bidiag
+            .rb_mut()
+            .cwise()
+            .zip(matrix)
+            .for_each(|dst, src| *dst = *src);
+    }
+
+    bidiag::bidiagonalize_in_place(
+        bidiag.rb_mut(),
+        householder_left.rb_mut(),
+        householder_right.rb_mut(),
+        parallelism,
+        stack.rb_mut(),
+    );
+    let bidiag = bidiag.into_const();
+
+    // the bidiagonal matrix is unitarily equivalent to a real bidiagonal matrix,
+    // B = D_left R D_right^*, where D_left and D_right are diagonal
+    for k in 0..n {
+        if k == 0 {
+            d_right[k] = T::one();
+        }
+        let d = bidiag[(k, k)] * d_right[k];
+        d_left[k] = phase(d);
+        diag[k] = d.abs();
+
+        if k + 1 < n {
+            let e = d_left[k].conj() * bidiag[(k, k + 1)];
+            d_right[k + 1] = phase(e).conj();
+            superdiag[k] = e.abs();
+        }
+    }
+
+    for i in 0..x.ncols() {
+        x[(i, i)] = T::Real::one();
+    }
+    for i in 0..y.ncols() {
+        y[(i, i)] = T::Real::one();
+    }
+
+    bidiag_real_svd::compute_bidiag_real_svd(
+        &mut diag,
+        &mut superdiag,
+        if u.is_some() { Some(x.rb_mut()) } else { None },
+        if v.is_some() { Some(y.rb_mut()) } else { None },
+    )?;
+    for k in 0..n {
+        s[k] = diag[k];
+    }
+
+    // U = Q D_left X
+    if let Some(u) = u.as_mut() {
+        let u_ncols = u.ncols();
+        u.rb_mut().cwise().for_each(|e| *e = T::zero());
+        for j in 0..n {
+            for i in 0..n {
+                u[(i, j)] = d_left[i].scale(x[(i, j)]);
+            }
+        }
+        for j in n..u_ncols {
+            u[(j, j)] = T::one();
+        }
+
+        apply_householder_sequence_on_the_left(
+            u.rb_mut(),
+            bidiag,
+            householder_left.rb(),
+            true,
+            parallelism,
+            stack.rb_mut(),
+        );
+    }
+
+    // V = P D_right Y
+    if let Some(v) = v.as_mut() {
+        for j in 0..n {
+            for i in 0..n {
+                v[(i, j)] = d_right[i].scale(y[(i, j)]);
+            }
+        }
+
+        // the essential parts of the reflections of conj(P) are the ones stored in the matrix,
+        // so we compute P V = conj(conj(P) conj(V))
+        if n > 0 {
+            v.rb_mut().cwise().for_each(|e| *e = (*e).conj());
+            apply_householder_sequence_on_the_left(
+                v.rb_mut().submatrix(1, 0, n - 1, n),
+                bidiag.submatrix(0, 1, n - 1, n - 1).transpose(),
+                householder_right.rb(),
+                true,
+                parallelism,
+                stack.rb_mut(),
+            );
+            v.rb_mut().cwise().for_each(|e| *e = (*e).conj());
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the singular value decomposition of `matrix`, such that
+/// $$A = U S V^*.$$
+///
+/// The singular values are stored in `s` in nonincreasing order. If `u` (resp. `v`) is
+/// provided, the corresponding left (resp. right) singular vectors are stored in its columns.
+///
+/// `u` may have either $\min(m, n)$ columns, in which case only the first singular vectors are
+/// computed, or $m$ columns, in which case all of them are computed. Similarly, `v` may have either
+/// $\min(m, n)$ or $n$ columns.
+///
+/// An error is returned if the matrix contains non-finite values, or if the iterations fail to
+/// converge.
+///
+/// # Panics
+///
+/// - Panics if `s` doesn't have $\min(m, n)$ rows.
+/// - Panics if `u` is provided and doesn't have $m$ rows, and either $\min(m, n)$ or $m$ columns.
+/// - Panics if `v` is provided and doesn't have $n$ rows, and either $\min(m, n)$ or $n$ columns.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn compute_svd<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    s: ColMut<'_, T::Real>,
+    u: Option<MatMut<'_, T>>,
+    v: Option<MatMut<'_, T>>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: SvdParams,
+) -> Result<(), ConvergenceError> {
+    let _ = params;
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = m.min(n);
+    fancy_assert!(s.nrows() == size);
+    if let Some(u) = &u {
+        fancy_assert!(u.nrows() == m);
+        fancy_assert!(u.ncols() == size || u.ncols() == m);
+    }
+    if let Some(v) = &v {
+        fancy_assert!(v.nrows() == n);
+        fancy_assert!(v.ncols() == size || v.ncols() == n);
+    }
+
+    if m >= n {
+        compute_svd_impl(matrix, false, s, u, v, parallelism, stack)
+    } else {
+        // A^* = U' S V'^*, so that A = V' S U'^*
+        compute_svd_impl(matrix, true, s, v, u, parallelism, stack)
+    }
+}
+
+/// Computes the size and alignment of required workspace for computing the pseudo-inverse of a
+/// matrix.
+pub fn compute_pseudoinverse_req<T: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let size = nrows.min(ncols);
+    StackReq::try_all_of([
+        temp_mat_req::<T::Real>(size, 1)?,
+        temp_mat_req::<T>(nrows, size)?,
+        temp_mat_req::<T>(ncols, size)?,
+        compute_svd_req::<T>(
+            nrows,
+            ncols,
+            ComputeVectors::Thin,
+            ComputeVectors::Thin,
+            parallelism,
+            params,
+        )?,
+    ])
+}
+
+/// Computes the Moore-Penrose pseudo-inverse of `matrix`, and stores the result in `dst`.
+///
+/// Singular values smaller than or equal to `tolerance` times the largest singular value are
+/// treated as zero.
+///
+/// An error is returned if the singular value decomposition fails. See [`compute_svd`].
+///
+/// # Panics
+///
+/// - Panics if `dst` doesn't have the same shape as the transpose of `matrix`.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn compute_pseudoinverse<T: ComplexField>(
+    dst: MatMut<'_, T>,
+    matrix: MatRef<'_, T>,
+    tolerance: T::Real,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: SvdParams,
+) -> Result<(), ConvergenceError> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = m.min(n);
+    fancy_assert!((dst.nrows(), dst.ncols()) == (n, m));
+
+    let mut stack = stack;
+
+    temp_mat_uninit! {
+        let (mut s, stack) = unsafe { temp_mat_uninit::<T::Real>(size, 1, stack.rb_mut()) };
+        let (mut u, stack) = unsafe { temp_mat_uninit::<T>(m, size, stack) };
+        let (mut v, stack) = unsafe { temp_mat_uninit::<T>(n, size, stack) };
+    }
+
+    compute_svd(
+        matrix,
+        s.rb_mut().col(0),
+        Some(u.rb_mut()),
+        Some(v.rb_mut()),
+        parallelism,
+        stack,
+        params,
+    )?;
+
+    // A^+ = V S^+ U^*
+    let threshold = if size > 0 {
+        tolerance * s[(0, 0)]
+    } else {
+        T::Real::zero()
+    };
+    for j in 0..size {
+        let sigma = s[(j, 0)];
+        let factor = if sigma > threshold {
+            sigma.inv()
+        } else {
+            T::Real::zero()
+        };
+        v.rb_mut()
+            .col(j)
+            .cwise()
+            .for_each(|e| *e = (*e).scale(factor));
+    }
+
+    matmul(
+        dst,
+        Conj::No,
+        v.rb(),
+        Conj::No,
+        u.rb().transpose(),
+        Conj::Yes,
+        None,
+        T::one(),
+        parallelism,
+    );
+
+    Ok(())
+}
+
+/// Computes the size and alignment of required workspace for computing the 2-norm condition
+/// number of a matrix.
+pub fn compute_condition_number_req<T: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    temp_mat_req::<T::Real>(nrows.min(ncols), 1)?.try_and(compute_svd_req::<T>(
+        nrows,
+        ncols,
+        ComputeVectors::No,
+        ComputeVectors::No,
+        parallelism,
+        params,
+    )?)
+}
+
+/// Computes the 2-norm condition number of `matrix`, defined as the ratio between its largest
+/// and smallest singular values.
+///
+/// An error is returned if the singular value decomposition fails. See [`compute_svd`].
+///
+/// # Panics
+///
+/// - Panics if the matrix has no rows or no columns.
+/// - Panics if the provided memory in `stack` is insufficient.
+#[track_caller]
+pub fn compute_condition_number<T: ComplexField>(
+    matrix: MatRef<'_, T>,
+    parallelism: Parallelism,
+    stack: DynStack<'_>,
+    params: SvdParams,
+) -> Result<T::Real, ConvergenceError> {
+    let size = matrix.nrows().min(matrix.ncols());
+    fancy_assert!(size > 0);
+
+    let mut stack = stack;
+    temp_mat_uninit! {
+        let (mut s, stack) = unsafe { temp_mat_uninit::<T::Real>(size, 1, stack.rb_mut()) };
+    }
+    compute_svd(
+        matrix,
+        s.rb_mut().col(0),
+        None,
+        None,
+        parallelism,
+        stack,
+        params,
+    )?;
+
+    Ok(s[(0, 0)] / s[(size - 1, 0)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert as fancy_assert;
+    use dyn_stack::GlobalMemBuffer;
+    use faer_core::{c32, c64, Mat};
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            DynStack::new(&mut GlobalMemBuffer::new($req))
+        };
+    }
+
+    fn norm<T: ComplexField>(a: MatRef<'_, T>) -> T::Real {
+        let mut sum = T::Real::zero();
+        for j in 0..a.ncols() {
+            for i in 0..a.nrows() {
+                sum = sum + (a[(i, j)] * a[(i, j)].conj()).real();
+            }
+        }
+        sum.sqrt()
+    }
+
+    fn mul<T: ComplexField>(lhs: MatRef<'_, T>, conj_lhs: bool, rhs: MatRef<'_, T>) -> Mat<T> {
+        let lhs = if conj_lhs { lhs.transpose() } else { lhs };
+        let mut dst = Mat::zeros(lhs.nrows(), rhs.ncols());
+        matmul(
+            dst.as_mut(),
+            Conj::No,
+            lhs,
+            if conj_lhs { Conj::Yes } else { Conj::No },
+            rhs,
+            Conj::No,
+            None,
+            T::one(),
+            Parallelism::None,
+        );
+        dst
+    }
+
+    fn orthogonality_error<T: ComplexField>(u: MatRef<'_, T>) -> T::Real {
+        let mut uhu = mul(u, true, u);
+        for j in 0..u.ncols() {
+            uhu[(j, j)] = uhu[(j, j)] - T::one();
+        }
+        norm(uhu.as_ref())
+    }
+
+    fn test_svd<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for (m, n) in [
+            (0, 0),
+            (1, 1),
+            (2, 1),
+            (1, 2),
+            (3, 3),
+            (4, 2),
+            (2, 4),
+            (10, 10),
+            (33, 20),
+            (20, 33),
+            (64, 64),
+            (100, 70),
+        ] {
+            let a = Mat::with_dims(|_, _| gen(), m, n);
+            let size = m.min(n);
+            let a_norm = norm(a.as_ref());
+
+            for (compute_u, compute_v) in [
+                (ComputeVectors::Thin, ComputeVectors::Thin),
+                (ComputeVectors::Full, ComputeVectors::Full),
+                (ComputeVectors::Thin, ComputeVectors::Full),
+                (ComputeVectors::Full, ComputeVectors::No),
+                (ComputeVectors::No, ComputeVectors::Thin),
+            ] {
+                let u_ncols = if compute_u == ComputeVectors::Full {
+                    m
+                } else {
+                    size
+                };
+                let v_ncols = if compute_v == ComputeVectors::Full {
+                    n
+                } else {
+                    size
+                };
+
+                let mut s = Mat::zeros(size, 1);
+                let mut u = Mat::zeros(m, u_ncols);
+                let mut v = Mat::zeros(n, v_ncols);
+                compute_svd(
+                    a.as_ref(),
+                    s.as_mut().col(0),
+                    if compute_u != ComputeVectors::No {
+                        Some(u.as_mut())
+                    } else {
+                        None
+                    },
+                    if compute_v != ComputeVectors::No {
+                        Some(v.as_mut())
+                    } else {
+                        None
+                    },
+                    Parallelism::Rayon(0),
+                    make_stack!(compute_svd_req::<T>(
+                        m,
+                        n,
+                        compute_u,
+                        compute_v,
+                        Parallelism::Rayon(0),
+                        Default::default(),
+                    )
+                    .unwrap()),
+                    Default::default(),
+                )
+                .unwrap();
+
+                for j in 0..size {
+                    fancy_assert!(s[(j, 0)] >= T::Real::zero());
+                    if j + 1 < size {
+                        fancy_assert!(s[(j, 0)] >= s[(j + 1, 0)]);
+                    }
+                }
+
+                if compute_u != ComputeVectors::No {
+                    fancy_assert!(orthogonality_error(u.as_ref()) <= epsilon * norm(u.as_ref()));
+                    // the left singular vectors past min(m, n) are orthogonal to the column space
+                    // of A
+                    let uha = mul(u.as_ref(), true, a.as_ref());
+                    for i in size..u_ncols {
+                        for j in 0..n {
+                            fancy_assert!(uha[(i, j)].abs() <= epsilon * a_norm);
+                        }
+                    }
+                }
+                if compute_v != ComputeVectors::No {
+                    fancy_assert!(orthogonality_error(v.as_ref()) <= epsilon * norm(v.as_ref()));
+                    // the right singular vectors past min(m, n) span the null space of A
+                    let av = mul(a.as_ref(), false, v.as_ref());
+                    for j in size..v_ncols {
+                        for i in 0..m {
+                            fancy_assert!(av[(i, j)].abs() <= epsilon * a_norm);
+                        }
+                    }
+                }
+
+                if compute_u != ComputeVectors::No && compute_v != ComputeVectors::No {
+                    // A = U S V^*
+                    let mut us = Mat::zeros(m, size);
+                    for j in 0..size {
+                        for i in 0..m {
+                            us[(i, j)] = u[(i, j)].scale(s[(j, 0)]);
+                        }
+                    }
+                    let mut reconstructed = Mat::zeros(m, n);
+                    matmul(
+                        reconstructed.as_mut(),
+                        Conj::No,
+                        us.as_ref(),
+                        Conj::No,
+                        v.as_ref().submatrix(0, 0, n, size).transpose(),
+                        Conj::Yes,
+                        None,
+                        T::one(),
+                        Parallelism::None,
+                    );
+                    for j in 0..n {
+                        for i in 0..m {
+                            reconstructed[(i, j)] = reconstructed[(i, j)] - a[(i, j)];
+                        }
+                    }
+                    fancy_assert!(norm(reconstructed.as_ref()) <= epsilon * a_norm);
+                }
+
+                // the singular values are the same when the singular vectors are not requested
+                let mut s_only = Mat::zeros(size, 1);
+                compute_svd(
+                    a.as_ref(),
+                    s_only.as_mut().col(0),
+                    None,
+                    None,
+                    Parallelism::Rayon(0),
+                    make_stack!(compute_svd_req::<T>(
+                        m,
+                        n,
+                        ComputeVectors::No,
+                        ComputeVectors::No,
+                        Parallelism::Rayon(0),
+                        Default::default(),
+                    )
+                    .unwrap()),
+                    Default::default(),
+                )
+                .unwrap();
+                for j in 0..size {
+                    fancy_assert!((s_only[(j, 0)] - s[(j, 0)]).abs() <= epsilon * a_norm);
+                }
+            }
+        }
+    }
+
+    fn test_pseudoinverse<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        for (m, n, rank) in [
+            (0, 0, 0),
+            (1, 1, 1),
+            (4, 4, 4),
+            (6, 4, 4),
+            (4, 6, 4),
+            (6, 4, 2),
+            (20, 20, 15),
+            (63, 32, 10),
+            (32, 63, 20),
+        ] {
+            // rank deficient matrix, A = B C
+            let b = Mat::with_dims(|_, _| gen(), m, rank);
+            let c = Mat::with_dims(|_, _| gen(), rank, n);
+            let a = mul(b.as_ref(), false, c.as_ref());
+            let a_norm = norm(a.as_ref());
+
+            let mut pinv = Mat::zeros(n, m);
+            compute_pseudoinverse(
+                pinv.as_mut(),
+                a.as_ref(),
+                epsilon,
+                Parallelism::Rayon(0),
+                make_stack!(compute_pseudoinverse_req::<T>(
+                    m,
+                    n,
+                    Parallelism::Rayon(0),
+                    Default::default(),
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .unwrap();
+            let pinv_norm = norm(pinv.as_ref());
+
+            // A A^+ A = A
+            let a_pinv = mul(a.as_ref(), false, pinv.as_ref());
+            let mut residual = mul(a_pinv.as_ref(), false, a.as_ref());
+            for j in 0..n {
+                for i in 0..m {
+                    residual[(i, j)] = residual[(i, j)] - a[(i, j)];
+                }
+            }
+            fancy_assert!(norm(residual.as_ref()) <= epsilon * a_norm * a_norm * pinv_norm);
+
+            // A^+ A A^+ = A^+
+            let mut residual = mul(pinv.as_ref(), false, a_pinv.as_ref());
+            for j in 0..m {
+                for i in 0..n {
+                    residual[(i, j)] = residual[(i, j)] - pinv[(i, j)];
+                }
+            }
+            fancy_assert!(norm(residual.as_ref()) <= epsilon * a_norm * pinv_norm * pinv_norm);
+
+            // A A^+ and A^+ A are hermitian
+            let pinv_a = mul(pinv.as_ref(), false, a.as_ref());
+            for j in 0..m {
+                for i in 0..m {
+                    fancy_assert!(
+                        (a_pinv[(i, j)] - a_pinv[(j, i)].conj()).abs()
+                            <= epsilon * a_norm * pinv_norm
+                    );
+                }
+            }
+            for j in 0..n {
+                for i in 0..n {
+                    fancy_assert!(
+                        (pinv_a[(i, j)] - pinv_a[(j, i)].conj()).abs()
+                            <= epsilon * a_norm * pinv_norm
+                    );
+                }
+            }
+        }
+    }
+
+    fn test_condition_number<T: ComplexField>(mut gen: impl FnMut() -> T, epsilon: T::Real) {
+        // diagonal matrix with entries 1, 2, ..., n, scaled by unimodular factors
+        let n = 8;
+        let mut a = Mat::zeros(n + 2, n);
+        let mut value = T::zero();
+        for j in 0..n {
+            value = value + T::one();
+            a[(j, j)] = value * phase(gen());
+        }
+
+        let cond = compute_condition_number(
+            a.as_ref(),
+            Parallelism::None,
+            make_stack!(compute_condition_number_req::<T>(
+                n + 2,
+                n,
+                Parallelism::None,
+                Default::default()
+            )
+            .unwrap()),
+            Default::default(),
+        )
+        .unwrap();
+        fancy_assert!((cond - value.real()).abs() <= epsilon * value.real());
+
+        let cond_transpose = compute_condition_number(
+            a.as_ref().transpose(),
+            Parallelism::None,
+            make_stack!(compute_condition_number_req::<T>(
+                n,
+                n + 2,
+                Parallelism::None,
+                Default::default()
+            )
+            .unwrap()),
+            Default::default(),
+        )
+        .unwrap();
+        fancy_assert!((cond_transpose - value.real()).abs() <= epsilon * value.real());
+    }
+
+    #[test]
+    fn test_svd_f64() {
+        test_svd(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_svd_f32() {
+        test_svd(random::<f32>, 1e-3);
+    }
+
+    #[test]
+    fn test_svd_c64() {
+        test_svd(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_svd_c32() {
+        test_svd(|| c32::new(random(), random()), 1e-3);
+    }
+
+    #[test]
+    fn test_pseudoinverse_f64() {
+        test_pseudoinverse(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_pseudoinverse_f32() {
+        test_pseudoinverse(random::<f32>, 1e-3);
+    }
+
+    #[test]
+    fn test_pseudoinverse_c64() {
+        test_pseudoinverse(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_pseudoinverse_c32() {
+        test_pseudoinverse(|| c32::new(random(), random()), 1e-3);
+    }
+
+    #[test]
+    fn test_condition_number_f64() {
+        test_condition_number(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_condition_number_c64() {
+        test_condition_number(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_svd_non_finite() {
+        for value in [f64::NAN, f64::INFINITY] {
+            let (m, n) = (7, 4);
+            let mut a = Mat::with_dims(|_, _| random::<f64>(), m, n);
+            a[(3, 2)] = value;
+
+            let mut s = Mat::zeros(n, 1);
+            let mut u = Mat::zeros(m, n);
+            let mut v = Mat::zeros(n, n);
+            fancy_assert!(compute_svd(
+                a.as_ref(),
+                s.as_mut().col(0),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_svd_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::Thin,
+                    ComputeVectors::Thin,
+                    Parallelism::None,
+                    Default::default(),
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .is_err());
+
+            fancy_assert!(compute_condition_number(
+                a.as_ref().transpose(),
+                Parallelism::None,
+                make_stack!(compute_condition_number_req::<f64>(
+                    n,
+                    m,
+                    Parallelism::None,
+                    Default::default()
+                )
+                .unwrap()),
+                Default::default(),
+            )
+            .is_err());
+        }
+    }
+}
@@ -1,6 +1,12 @@
 pub use faer_cholesky as chol;
 pub use faer_core as core;
+pub use faer_evd as evd;
 pub use faer_lu as lu;
 pub use faer_qr as qr;
+pub use faer_svd as svd;
 
-pub use faer_core::{ColMut, ColRef, ComplexField, Mat, MatMut, MatRef, RealField, RowMut, RowRef};
+pub use faer_core::{
+    mat, ColMut, ColRef, ComplexField, Mat, MatMut, MatRef, RealField, RowMut, RowRef,
+};
+
+pub mod solvers;
@@ -0,0 +1,1207 @@
+//! Owned matrix decompositions.
+//!
+//! Each decomposition owns its factors and permutations, and allocates the workspace it needs
+//! internally, so that solving a linear system only takes a couple of lines.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{mat, solvers::PartialPivLu};
+//!
+//! let a = mat![[2.0, 1.0], [1.0, 3.0]];
+//! let b = mat![[3.0], [4.0]];
+//!
+//! let lu = PartialPivLu::new(a.as_ref());
+//! let x = lu.solve(b.as_ref());
+//!
+//! assert!((x[(0, 0)] - 1.0f64).abs() < 1e-12);
+//! assert!((x[(1, 0)] - 1.0f64).abs() < 1e-12);
+//! assert!((lu.determinant() - 5.0f64).abs() < 1e-12);
+//! ```
+
+use assert2::assert as fancy_assert;
+use dyn_stack::{DynStack, GlobalMemBuffer};
+use faer_cholesky::{ldlt_diagonal, llt, llt::CholeskyError};
+use faer_core::{
+    error::FaerError, mul::matmul, permutation::Permutation, ComplexField, Conj, Mat, MatMut,
+    MatRef, Parallelism, RealField,
+};
+use faer_lu::{full_pivoting, partial_pivoting};
+use faer_qr::{
+    col_pivoting::{self, solve::LeastSquaresSolution},
+    no_pivoting,
+};
+
+macro_rules! make_stack {
+    ($req: expr) => {
+        DynStack::new(&mut GlobalMemBuffer::new($req.unwrap()))
+    };
+}
+
+fn default_parallelism() -> Parallelism {
+    Parallelism::Rayon(0)
+}
+
+/// Copies the conjugate of the strictly lower triangular part of `mat` to its strictly upper
+/// triangular part.
+fn make_hermitian_from_lower<T: ComplexField>(mat: MatMut<'_, T>) {
+    let mut mat = mat;
+    let n = mat.nrows();
+    for j in 0..n {
+        for i in 0..j {
+            let x = mat[(j, i)].conj();
+            mat[(i, j)] = x;
+        }
+    }
+}
+
+/// Builds an owned permutation from the index arrays filled by a decomposition.
+fn into_permutation(forward: Vec<usize>, inverse: Vec<usize>) -> Permutation {
+    // SAFETY: the arrays were filled by the decomposition, which guarantees their validity
+    unsafe { Permutation::new_unchecked(forward.into_boxed_slice(), inverse.into_boxed_slice()) }
+}
+
+/// Returns the determinant of the orthogonal factor of a QR decomposition.
+///
+/// Each householder reflection $I - \tau vv^*$ with a nonzero coefficient has a determinant equal
+/// to $-1$, and the ones with a zero coefficient are the identity.
+fn householder_sequence_sign<T: ComplexField>(householder_factor: MatRef<'_, T>) -> T {
+    let mut sign = T::one();
+    for k in 0..householder_factor.nrows() {
+        if householder_factor[(k, 0)].abs() > T::Real::zero() {
+            sign = -sign;
+        }
+    }
+    sign
+}
+
+/// LU decomposition with partial pivoting of a square matrix $A$, such that $PA = LU$.
+pub struct PartialPivLu<T: 'static> {
+    factors: Mat<T>,
+    row_perm: Permutation,
+    n_transpositions: usize,
+    parallelism: Parallelism,
+}
+
+impl<T: ComplexField> PartialPivLu<T> {
+    /// Computes the LU decomposition with partial pivoting of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use all the available threads.
+    /// See [`Self::new_with_parallelism`] to choose a different strategy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, T>) -> Self {
+        Self::new_with_parallelism(matrix, default_parallelism())
+    }
+
+    /// Computes the LU decomposition with partial pivoting of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use `parallelism`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new_with_parallelism(matrix: MatRef<'_, T>, parallelism: Parallelism) -> Self {
+        fancy_assert!(matrix.nrows() == matrix.ncols());
+        let n = matrix.nrows();
+        let mut factors = Mat::with_dims(|i, j| matrix[(i, j)], matrix.nrows(), matrix.ncols());
+        let mut perm = vec![0; n];
+        let mut perm_inv = vec![0; n];
+
+        let (n_transpositions, _) = partial_pivoting::compute::lu_in_place(
+            factors.as_mut(),
+            &mut perm,
+            &mut perm_inv,
+            parallelism,
+            make_stack!(partial_pivoting::compute::lu_in_place_req::<T>(
+                n,
+                n,
+                parallelism,
+                Default::default()
+            )),
+            Default::default(),
+        );
+
+        Self {
+            factors,
+            row_perm: into_permutation(perm, perm_inv),
+            n_transpositions,
+            parallelism,
+        }
+    }
+
+    /// Returns the LU factors, with $L$ stored in the strictly lower triangular part, with an
+    /// implicit unit diagonal, and $U$ stored in the upper triangular part.
+    pub fn factors(&self) -> MatRef<'_, T> {
+        self.factors.as_ref()
+    }
+
+    /// Returns the row permutation $P$.
+    pub fn row_permutation(&self) -> &Permutation {
+        &self.row_perm
+    }
+
+    /// Solves the linear system $AX = B$ in place, where $B$ is stored in `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve_in_place(&self, rhs: MatMut<'_, T>) {
+        let n = self.factors.nrows();
+        let k = rhs.ncols();
+        partial_pivoting::solve::solve_in_place(
+            self.factors.as_ref(),
+            Conj::No,
+            self.row_perm.as_ref(),
+            rhs,
+            Conj::No,
+            self.parallelism,
+            make_stack!(partial_pivoting::solve::solve_req::<T>(
+                n,
+                n,
+                k,
+                self.parallelism
+            )),
+        );
+    }
+
+    /// Returns the solution of the linear system $AX = B$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, T>) -> Mat<T> {
+        let mut sol = Mat::with_dims(|i, j| rhs[(i, j)], rhs.nrows(), rhs.ncols());
+        self.solve_in_place(sol.as_mut());
+        sol
+    }
+
+    /// Returns the inverse of $A$.
+    pub fn inverse(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let mut inv = Mat::zeros(n, n);
+        partial_pivoting::inverse::invert_to(
+            inv.as_mut(),
+            self.factors.as_ref(),
+            self.row_perm.as_ref(),
+            self.parallelism,
+            make_stack!(partial_pivoting::inverse::invert_req::<T>(
+                n,
+                n,
+                self.parallelism
+            )),
+        );
+        inv
+    }
+
+    /// Returns the matrix $A$, reconstructed from its factors.
+    pub fn reconstruct(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let mut rec = Mat::zeros(n, n);
+        partial_pivoting::reconstruct::reconstruct_to(
+            rec.as_mut(),
+            self.factors.as_ref(),
+            self.row_perm.as_ref(),
+            self.parallelism,
+            make_stack!(partial_pivoting::reconstruct::reconstruct_req::<T>(
+                n,
+                n,
+                self.parallelism
+            )),
+        );
+        rec
+    }
+
+    /// Returns the determinant of $A$.
+    pub fn determinant(&self) -> T {
+        partial_pivoting::determinant::determinant(self.factors.as_ref(), self.n_transpositions)
+    }
+}
+
+/// LU decomposition with full pivoting of a square matrix $A$, such that $PAQ^\top = LU$.
+pub struct FullPivLu<T: 'static> {
+    factors: Mat<T>,
+    row_perm: Permutation,
+    col_perm: Permutation,
+    n_transpositions: usize,
+    parallelism: Parallelism,
+}
+
+impl<T: ComplexField> FullPivLu<T> {
+    /// Computes the LU decomposition with full pivoting of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use all the available threads.
+    /// See [`Self::new_with_parallelism`] to choose a different strategy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, T>) -> Self {
+        Self::new_with_parallelism(matrix, default_parallelism())
+    }
+
+    /// Computes the LU decomposition with full pivoting of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use `parallelism`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new_with_parallelism(matrix: MatRef<'_, T>, parallelism: Parallelism) -> Self {
+        fancy_assert!(matrix.nrows() == matrix.ncols());
+        let n = matrix.nrows();
+        let mut factors = Mat::with_dims(|i, j| matrix[(i, j)], matrix.nrows(), matrix.ncols());
+        let mut row_perm = vec![0; n];
+        let mut row_perm_inv = vec![0; n];
+        let mut col_perm = vec![0; n];
+        let mut col_perm_inv = vec![0; n];
+
+        let (n_transpositions, _, _) = full_pivoting::compute::lu_in_place(
+            factors.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut col_perm_inv,
+            parallelism,
+            make_stack!(full_pivoting::compute::lu_in_place_req::<T>(
+                n,
+                n,
+                parallelism,
+                Default::default()
+            )),
+            Default::default(),
+        );
+
+        Self {
+            factors,
+            row_perm: into_permutation(row_perm, row_perm_inv),
+            col_perm: into_permutation(col_perm, col_perm_inv),
+            n_transpositions,
+            parallelism,
+        }
+    }
+
+    /// Returns the LU factors, with $L$ stored in the strictly lower triangular part, with an
+    /// implicit unit diagonal, and $U$ stored in the upper triangular part.
+    pub fn factors(&self) -> MatRef<'_, T> {
+        self.factors.as_ref()
+    }
+
+    /// Returns the row permutation $P$.
+    pub fn row_permutation(&self) -> &Permutation {
+        &self.row_perm
+    }
+
+    /// Returns the column permutation $Q$.
+    pub fn col_permutation(&self) -> &Permutation {
+        &self.col_perm
+    }
+
+    /// Solves the linear system $AX = B$ in place, where $B$ is stored in `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve_in_place(&self, rhs: MatMut<'_, T>) {
+        let n = self.factors.nrows();
+        let k = rhs.ncols();
+        full_pivoting::solve::solve_in_place(
+            self.factors.as_ref(),
+            Conj::No,
+            self.row_perm.as_ref(),
+            self.col_perm.as_ref(),
+            rhs,
+            Conj::No,
+            self.parallelism,
+            make_stack!(full_pivoting::solve::solve_req::<T>(
+                n,
+                n,
+                k,
+                self.parallelism
+            )),
+        );
+    }
+
+    /// Returns the solution of the linear system $AX = B$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, T>) -> Mat<T> {
+        let mut sol = Mat::with_dims(|i, j| rhs[(i, j)], rhs.nrows(), rhs.ncols());
+        self.solve_in_place(sol.as_mut());
+        sol
+    }
+
+    /// Returns the inverse of $A$.
+    pub fn inverse(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let mut inv = Mat::zeros(n, n);
+        full_pivoting::inverse::invert_to(
+            inv.as_mut(),
+            self.factors.as_ref(),
+            self.row_perm.as_ref(),
+            self.col_perm.as_ref(),
+            self.parallelism,
+            make_stack!(full_pivoting::inverse::invert_req::<T>(
+                n,
+                n,
+                self.parallelism
+            )),
+        );
+        inv
+    }
+
+    /// Returns the matrix $A$, reconstructed from its factors.
+    pub fn reconstruct(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let mut rec = Mat::zeros(n, n);
+        full_pivoting::reconstruct::reconstruct_to(
+            rec.as_mut(),
+            self.factors.as_ref(),
+            self.row_perm.as_ref(),
+            self.col_perm.as_ref(),
+            self.parallelism,
+            make_stack!(full_pivoting::reconstruct::reconstruct_req::<T>(
+                n,
+                n,
+                self.parallelism
+            )),
+        );
+        rec
+    }
+
+    /// Returns the determinant of $A$.
+    pub fn determinant(&self) -> T {
+        full_pivoting::determinant::determinant(self.factors.as_ref(), self.n_transpositions)
+    }
+}
+
+/// Cholesky decomposition of a hermitian positive definite matrix $A$, such that $A = LL^*$.
+pub struct Llt<T: 'static> {
+    factors: Mat<T>,
+    parallelism: Parallelism,
+}
+
+impl<T: ComplexField> Llt<T> {
+    /// Computes the Cholesky decomposition of `matrix`. Only the lower triangular half of the
+    /// matrix is accessed.
+    ///
+    /// The factorization and the operations on the decomposition use all the available threads.
+    /// See [`Self::new_with_parallelism`] to choose a different strategy.
+    ///
+    /// Returns an error if the matrix is not numerically positive definite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, T>) -> Result<Self, CholeskyError<T::Real>> {
+        Self::new_with_parallelism(matrix, default_parallelism())
+    }
+
+    /// Computes the Cholesky decomposition of `matrix`. Only the lower triangular half of the
+    /// matrix is accessed.
+    ///
+    /// The factorization and the operations on the decomposition use `parallelism`.
+    ///
+    /// Returns an error if the matrix is not numerically positive definite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new_with_parallelism(
+        matrix: MatRef<'_, T>,
+        parallelism: Parallelism,
+    ) -> Result<Self, CholeskyError<T::Real>> {
+        fancy_assert!(matrix.nrows() == matrix.ncols());
+        let n = matrix.nrows();
+        let mut factors = Mat::with_dims(|i, j| matrix[(i, j)], matrix.nrows(), matrix.ncols());
+
+        llt::compute::cholesky_in_place(
+            factors.as_mut(),
+            parallelism,
+            make_stack!(llt::compute::cholesky_in_place_req::<T>(
+                n,
+                parallelism,
+                Default::default()
+            )),
+            Default::default(),
+        )?;
+
+        Ok(Self {
+            factors,
+            parallelism,
+        })
+    }
+
+    /// Returns the Cholesky factor $L$, stored in the lower triangular part of the returned
+    /// matrix. The strictly upper triangular part is unspecified.
+    pub fn factors(&self) -> MatRef<'_, T> {
+        self.factors.as_ref()
+    }
+
+    /// Solves the linear system $AX = B$ in place, where $B$ is stored in `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve_in_place(&self, rhs: MatMut<'_, T>) {
+        llt::solve::solve_in_place(
+            self.factors.as_ref(),
+            Conj::No,
+            rhs,
+            Conj::No,
+            self.parallelism,
+        );
+    }
+
+    /// Returns the solution of the linear system $AX = B$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, T>) -> Mat<T> {
+        let mut sol = Mat::with_dims(|i, j| rhs[(i, j)], rhs.nrows(), rhs.ncols());
+        self.solve_in_place(sol.as_mut());
+        sol
+    }
+
+    /// Returns the inverse of $A$.
+    pub fn inverse(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let mut inv = Mat::zeros(n, n);
+        llt::inverse::invert_lower_to(
+            inv.as_mut(),
+            self.factors.as_ref(),
+            self.parallelism,
+            make_stack!(llt::inverse::invert_lower_req::<T>(n, self.parallelism)),
+        );
+        make_hermitian_from_lower(inv.as_mut());
+        inv
+    }
+
+    /// Returns the matrix $A$, reconstructed from its factors.
+    pub fn reconstruct(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let mut rec = Mat::zeros(n, n);
+        llt::reconstruct::reconstruct_lower_to(
+            rec.as_mut(),
+            self.factors.as_ref(),
+            self.parallelism,
+        );
+        make_hermitian_from_lower(rec.as_mut());
+        rec
+    }
+
+    /// Returns the determinant of $A$.
+    pub fn determinant(&self) -> T::Real {
+        let mut det = T::Real::one();
+        for k in 0..self.factors.nrows() {
+            let l = self.factors[(k, k)];
+            det = det * (l * l.conj()).real();
+        }
+        det
+    }
+}
+
+/// Cholesky decomposition without pivoting of a hermitian matrix $A$, such that $A = LDL^*$, where
+/// $L$ is unit lower triangular and $D$ is diagonal.
+///
+/// The decomposition exists if all the leading principal minors of $A$ are nonzero, which is the
+/// case for positive definite and negative definite matrices, for example.
+pub struct Ldlt<T: 'static> {
+    factors: Mat<T>,
+    parallelism: Parallelism,
+}
+
+impl<T: ComplexField> Ldlt<T> {
+    /// Computes the $LDL^*$ decomposition of `matrix`. Only the lower triangular half of the
+    /// matrix is accessed.
+    ///
+    /// The factorization and the operations on the decomposition use all the available threads.
+    /// See [`Self::new_with_parallelism`] to choose a different strategy.
+    ///
+    /// Returns [`FaerError::Singular`] if a diagonal element of $D$ is zero or not finite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, T>) -> Result<Self, FaerError> {
+        Self::new_with_parallelism(matrix, default_parallelism())
+    }
+
+    /// Computes the $LDL^*$ decomposition of `matrix`. Only the lower triangular half of the
+    /// matrix is accessed.
+    ///
+    /// The factorization and the operations on the decomposition use `parallelism`.
+    ///
+    /// Returns [`FaerError::Singular`] if a diagonal element of $D$ is zero or not finite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not a square matrix.
+    #[track_caller]
+    pub fn new_with_parallelism(
+        matrix: MatRef<'_, T>,
+        parallelism: Parallelism,
+    ) -> Result<Self, FaerError> {
+        fancy_assert!(matrix.nrows() == matrix.ncols());
+        let n = matrix.nrows();
+        let mut factors = Mat::with_dims(|i, j| matrix[(i, j)], matrix.nrows(), matrix.ncols());
+
+        ldlt_diagonal::compute::try_raw_cholesky_in_place(
+            factors.as_mut(),
+            parallelism,
+            make_stack!(ldlt_diagonal::compute::raw_cholesky_in_place_req::<T>(
+                n,
+                parallelism,
+                Default::default()
+            )),
+            Default::default(),
+        )?;
+
+        Ok(Self {
+            factors,
+            parallelism,
+        })
+    }
+
+    /// Returns the factors, with $L$ stored in the strictly lower triangular part, with an
+    /// implicit unit diagonal, and $D$ stored on the diagonal. The strictly upper triangular part
+    /// is unspecified.
+    pub fn factors(&self) -> MatRef<'_, T> {
+        self.factors.as_ref()
+    }
+
+    /// Solves the linear system $AX = B$ in place, where $B$ is stored in `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve_in_place(&self, rhs: MatMut<'_, T>) {
+        ldlt_diagonal::solve::solve_in_place(
+            self.factors.as_ref(),
+            Conj::No,
+            rhs,
+            Conj::No,
+            self.parallelism,
+        );
+    }
+
+    /// Returns the solution of the linear system $AX = B$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, T>) -> Mat<T> {
+        let mut sol = Mat::with_dims(|i, j| rhs[(i, j)], rhs.nrows(), rhs.ncols());
+        self.solve_in_place(sol.as_mut());
+        sol
+    }
+
+    /// Returns the inverse of $A$.
+    pub fn inverse(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let mut inv = Mat::with_dims(|i, j| if i == j { T::one() } else { T::zero() }, n, n);
+        self.solve_in_place(inv.as_mut());
+        inv
+    }
+
+    /// Returns the matrix $A$, reconstructed from its factors.
+    pub fn reconstruct(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        let l = Mat::with_dims(
+            |i, j| {
+                if i == j {
+                    T::one()
+                } else if i > j {
+                    self.factors[(i, j)]
+                } else {
+                    T::zero()
+                }
+            },
+            n,
+            n,
+        );
+        let ld = Mat::with_dims(|i, j| l[(i, j)] * self.factors[(j, j)], n, n);
+
+        let mut rec = Mat::zeros(n, n);
+        matmul(
+            rec.as_mut(),
+            Conj::No,
+            ld.as_ref(),
+            Conj::No,
+            l.as_ref().transpose(),
+            Conj::Yes,
+            None,
+            T::one(),
+            self.parallelism,
+        );
+        rec
+    }
+
+    /// Returns the determinant of $A$.
+    pub fn determinant(&self) -> T::Real {
+        ldlt_diagonal::inertia::determinant(self.factors.as_ref())
+    }
+}
+
+/// QR decomposition of a matrix $A$ with at least as many rows as columns, such that $A = QR$.
+pub struct Qr<T: 'static> {
+    factors: Mat<T>,
+    householder_factor: Mat<T>,
+    parallelism: Parallelism,
+}
+
+impl<T: ComplexField> Qr<T> {
+    /// Computes the QR decomposition of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use all the available threads.
+    /// See [`Self::new_with_parallelism`] to choose a different strategy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` has fewer rows than columns.
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, T>) -> Self {
+        Self::new_with_parallelism(matrix, default_parallelism())
+    }
+
+    /// Computes the QR decomposition of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use `parallelism`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` has fewer rows than columns.
+    #[track_caller]
+    pub fn new_with_parallelism(matrix: MatRef<'_, T>, parallelism: Parallelism) -> Self {
+        fancy_assert!(matrix.nrows() >= matrix.ncols());
+        let m = matrix.nrows();
+        let n = matrix.ncols();
+        let mut factors = Mat::with_dims(|i, j| matrix[(i, j)], matrix.nrows(), matrix.ncols());
+        let mut householder_factor = Mat::zeros(n, 1);
+
+        no_pivoting::compute::qr_in_place(
+            factors.as_mut(),
+            householder_factor.as_mut().col(0),
+            parallelism,
+            make_stack!(no_pivoting::compute::qr_in_place_req::<T>(
+                m,
+                n,
+                parallelism,
+                Default::default()
+            )),
+            Default::default(),
+        );
+
+        Self {
+            factors,
+            householder_factor,
+            parallelism,
+        }
+    }
+
+    /// Returns the QR factors, with $R$ stored in the upper triangular part, and the essential
+    /// parts of the householder reflections that form $Q$ stored in the strictly lower triangular
+    /// part.
+    pub fn factors(&self) -> MatRef<'_, T> {
+        self.factors.as_ref()
+    }
+
+    /// Returns the coefficients of the householder reflections that form $Q$.
+    pub fn householder_factor(&self) -> MatRef<'_, T> {
+        self.householder_factor.as_ref()
+    }
+
+    /// Computes the least squares solution of the linear system $AX = B$ in place, where $B$ is
+    /// stored in `rhs`. The solution is stored in the top rows of `rhs`, while the remaining rows
+    /// are clobbered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve_in_place(&self, rhs: MatMut<'_, T>) {
+        let m = self.factors.nrows();
+        let n = self.factors.ncols();
+        let k = rhs.ncols();
+        no_pivoting::solve::solve_in_place(
+            self.factors.as_ref(),
+            self.householder_factor.as_ref().col(0),
+            Conj::No,
+            rhs,
+            Conj::No,
+            self.parallelism,
+            make_stack!(no_pivoting::solve::solve_req::<T>(
+                m,
+                n,
+                k,
+                self.parallelism
+            )),
+        );
+    }
+
+    /// Returns the least squares solution of the linear system $AX = B$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, T>) -> Mat<T> {
+        let n = self.factors.ncols();
+        let mut sol = Mat::with_dims(|i, j| rhs[(i, j)], rhs.nrows(), rhs.ncols());
+        self.solve_in_place(sol.as_mut());
+        let k = sol.ncols();
+        sol.resize_with(|_, _| T::zero(), n, k);
+        sol
+    }
+
+    /// Returns the inverse of $A$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if $A$ is not a square matrix.
+    #[track_caller]
+    pub fn inverse(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        fancy_assert!(self.factors.ncols() == n);
+        let mut inv = Mat::zeros(n, n);
+        no_pivoting::inverse::invert_to(
+            inv.as_mut(),
+            self.factors.as_ref(),
+            self.householder_factor.as_ref().col(0),
+            self.parallelism,
+            make_stack!(no_pivoting::inverse::invert_req::<T>(
+                n,
+                n,
+                self.parallelism
+            )),
+        );
+        inv
+    }
+
+    /// Returns the matrix $A$, reconstructed from its factors.
+    pub fn reconstruct(&self) -> Mat<T> {
+        let m = self.factors.nrows();
+        let n = self.factors.ncols();
+        let mut rec = Mat::zeros(m, n);
+        no_pivoting::reconstruct::reconstruct_to(
+            rec.as_mut(),
+            self.factors.as_ref(),
+            self.householder_factor.as_ref().col(0),
+            self.parallelism,
+            make_stack!(no_pivoting::reconstruct::reconstruct_req::<T>(
+                m,
+                n,
+                self.parallelism
+            )),
+        );
+        rec
+    }
+
+    /// Returns the determinant of $A$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if $A$ is not a square matrix.
+    #[track_caller]
+    pub fn determinant(&self) -> T {
+        let n = self.factors.nrows();
+        fancy_assert!(self.factors.ncols() == n);
+        let mut det = householder_sequence_sign(self.householder_factor.as_ref());
+        for k in 0..n {
+            det = det * self.factors[(k, k)];
+        }
+        det
+    }
+}
+
+/// QR decomposition with column pivoting of a matrix $A$, such that $AP = QR$.
+pub struct ColPivQr<T: 'static> {
+    factors: Mat<T>,
+    householder_factor: Mat<T>,
+    col_perm: Permutation,
+    n_transpositions: usize,
+    rank: usize,
+    parallelism: Parallelism,
+}
+
+impl<T: ComplexField> ColPivQr<T> {
+    /// Computes the QR decomposition with column pivoting of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use all the available threads.
+    /// See [`Self::new_with_parallelism`] to choose a different strategy.
+    ///
+    /// The numerical rank used by [`ColPivQr::solve`] is the number of leading diagonal elements
+    /// of $R$ whose absolute value is greater than $\max(m, n)\,\epsilon$ times the absolute value
+    /// of the first one.
+    pub fn new(matrix: MatRef<'_, T>) -> Self {
+        Self::new_with_parallelism(matrix, default_parallelism())
+    }
+
+    /// Computes the QR decomposition with column pivoting of `matrix`.
+    ///
+    /// The factorization and the operations on the decomposition use `parallelism`.
+    ///
+    /// The numerical rank used by [`ColPivQr::solve`] is the number of leading diagonal elements
+    /// of $R$ whose absolute value is greater than $\max(m, n)\,\epsilon$ times the absolute value
+    /// of the first one.
+    pub fn new_with_parallelism(matrix: MatRef<'_, T>, parallelism: Parallelism) -> Self {
+        let m = matrix.nrows();
+        let n = matrix.ncols();
+        let size = m.min(n);
+        let mut factors = Mat::with_dims(|i, j| matrix[(i, j)], matrix.nrows(), matrix.ncols());
+        let mut householder_factor = Mat::zeros(size, 1);
+        let mut transpositions = vec![0; size];
+
+        let n_transpositions = col_pivoting::compute::qr_in_place(
+            factors.as_mut(),
+            householder_factor.as_mut().col(0),
+            &mut transpositions,
+            parallelism,
+            make_stack!(col_pivoting::compute::qr_in_place_req::<T>(
+                m,
+                n,
+                parallelism
+            )),
+            Default::default(),
+        );
+
+        let tolerance = T::Real::epsilon() * T::Real::from_f64(m.max(n) as f64);
+        let rank = col_pivoting::solve::numerical_rank(factors.as_ref(), tolerance);
+
+        Self {
+            factors,
+            householder_factor,
+            col_perm: Permutation::from_transpositions(n, &transpositions),
+            n_transpositions,
+            rank,
+            parallelism,
+        }
+    }
+
+    /// Returns the QR factors, with $R$ stored in the upper triangular part, and the essential
+    /// parts of the householder reflections that form $Q$ stored in the strictly lower triangular
+    /// part.
+    pub fn factors(&self) -> MatRef<'_, T> {
+        self.factors.as_ref()
+    }
+
+    /// Returns the coefficients of the householder reflections that form $Q$.
+    pub fn householder_factor(&self) -> MatRef<'_, T> {
+        self.householder_factor.as_ref()
+    }
+
+    /// Returns the column permutation $P$.
+    pub fn col_permutation(&self) -> &Permutation {
+        &self.col_perm
+    }
+
+    /// Returns the numerical rank of $A$.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Returns the minimum norm least squares solution of the linear system $AX = B$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` doesn't have the same number of rows as $A$.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, T>) -> Mat<T> {
+        let m = self.factors.nrows();
+        let n = self.factors.ncols();
+        let k = rhs.ncols();
+        fancy_assert!(rhs.nrows() == m);
+
+        let mut sol = Mat::with_dims(
+            |i, j| if i < m { rhs[(i, j)] } else { T::zero() },
+            m.max(n),
+            k,
+        );
+        col_pivoting::solve::solve_in_place(
+            self.factors.as_ref(),
+            self.householder_factor.as_ref().col(0),
+            self.col_perm.as_ref(),
+            self.rank,
+            Conj::No,
+            sol.as_mut(),
+            Conj::No,
+            LeastSquaresSolution::MinimumNorm,
+            self.parallelism,
+            make_stack!(col_pivoting::solve::solve_req::<T>(
+                m,
+                n,
+                self.rank,
+                k,
+                self.parallelism
+            )),
+        );
+        sol.resize_with(|_, _| T::zero(), n, k);
+        sol
+    }
+
+    /// Returns the inverse of $A$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if $A$ is not a square matrix.
+    #[track_caller]
+    pub fn inverse(&self) -> Mat<T> {
+        let n = self.factors.nrows();
+        fancy_assert!(self.factors.ncols() == n);
+        let mut inv = Mat::zeros(n, n);
+        col_pivoting::inverse::invert_to(
+            inv.as_mut(),
+            self.factors.as_ref(),
+            self.householder_factor.as_ref().col(0),
+            self.col_perm.as_ref(),
+            self.parallelism,
+            make_stack!(col_pivoting::inverse::invert_req::<T>(
+                n,
+                n,
+                self.parallelism
+            )),
+        );
+        inv
+    }
+
+    /// Returns the matrix $A$, reconstructed from its factors.
+    pub fn reconstruct(&self) -> Mat<T> {
+        let m = self.factors.nrows();
+        let n = self.factors.ncols();
+        let mut rec = Mat::zeros(m, n);
+        col_pivoting::reconstruct::reconstruct_to(
+            rec.as_mut(),
+            self.factors.as_ref(),
+            self.householder_factor.as_ref().col(0),
+            self.col_perm.as_ref(),
+            self.parallelism,
+            make_stack!(col_pivoting::reconstruct::reconstruct_req::<T>(
+                m,
+                n,
+                self.parallelism
+            )),
+        );
+        rec
+    }
+
+    /// Returns the determinant of $A$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if $A$ is not a square matrix.
+    #[track_caller]
+    pub fn determinant(&self) -> T {
+        let n = self.factors.nrows();
+        fancy_assert!(self.factors.ncols() == n);
+        let mut det = householder_sequence_sign(self.householder_factor.as_ref());
+        if self.n_transpositions % 2 == 1 {
+            det = -det;
+        }
+        for k in 0..n {
+            det = det * self.factors[(k, k)];
+        }
+        det
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use faer_core::c64;
+    use rand::random;
+
+    fn random_mat(m: usize, n: usize) -> Mat<c64> {
+        Mat::with_dims(|_, _| c64::new(random(), random()), m, n)
+    }
+
+    fn random_hpd(n: usize) -> Mat<c64> {
+        let a = random_mat(n, n);
+        let mut h = Mat::zeros(n, n);
+        matmul(
+            h.as_mut(),
+            Conj::No,
+            a.as_ref(),
+            Conj::No,
+            a.as_ref().transpose(),
+            Conj::Yes,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        for k in 0..n {
+            h[(k, k)] += c64::new(n as f64, 0.0);
+        }
+        h
+    }
+
+    fn product(lhs: MatRef<'_, c64>, rhs: MatRef<'_, c64>) -> Mat<c64> {
+        let mut dst = Mat::zeros(lhs.nrows(), rhs.ncols());
+        matmul(
+            dst.as_mut(),
+            Conj::No,
+            lhs,
+            Conj::No,
+            rhs,
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        dst
+    }
+
+    fn assert_close(lhs: MatRef<'_, c64>, rhs: MatRef<'_, c64>) {
+        fancy_assert!((lhs.nrows(), lhs.ncols()) == (rhs.nrows(), rhs.ncols()));
+        for j in 0..lhs.ncols() {
+            for i in 0..lhs.nrows() {
+                fancy_assert!((lhs[(i, j)] - rhs[(i, j)]).abs() < 1e-8);
+            }
+        }
+    }
+
+    fn identity(n: usize) -> Mat<c64> {
+        Mat::with_dims(|i, j| if i == j { c64::one() } else { c64::zero() }, n, n)
+    }
+
+    macro_rules! check_square {
+        ($a: expr, $dec: ident = $make: expr, $det: expr) => {{
+            let a = $a;
+            let $dec = $make;
+            let n = a.nrows();
+            let b = random_mat(n, 3);
+
+            let x = $dec.solve(b.as_ref());
+            assert_close(product(a.as_ref(), x.as_ref()).as_ref(), b.as_ref());
+            assert_close(
+                product(a.as_ref(), $dec.inverse().as_ref()).as_ref(),
+                identity(n).as_ref(),
+            );
+            assert_close($dec.reconstruct().as_ref(), a.as_ref());
+
+            let det: c64 = $det;
+            let expected = PartialPivLu::new(a.as_ref()).determinant();
+            fancy_assert!((det - expected).abs() < 1e-8 * expected.abs());
+        }};
+    }
+
+    #[test]
+    fn test_square_decompositions() {
+        for n in [1, 2, 5, 20, 64] {
+            let a = random_mat(n, n);
+            check_square!(&a, d = PartialPivLu::new(a.as_ref()), d.determinant());
+            check_square!(&a, d = FullPivLu::new(a.as_ref()), d.determinant());
+            check_square!(&a, d = Qr::new(a.as_ref()), d.determinant());
+            check_square!(&a, d = ColPivQr::new(a.as_ref()), d.determinant());
+
+            let h = random_hpd(n);
+            check_square!(
+                &h,
+                d = Llt::new(h.as_ref()).unwrap(),
+                c64::from_real(d.determinant())
+            );
+            check_square!(
+                &h,
+                d = Ldlt::new(h.as_ref()).unwrap(),
+                c64::from_real(d.determinant())
+            );
+        }
+    }
+
+    #[test]
+    fn test_least_squares() {
+        let (m, n) = (20, 7);
+        let a = random_mat(m, n);
+        let b = random_mat(m, 2);
+
+        let x_qr = Qr::new(a.as_ref()).solve(b.as_ref());
+        let col_piv_qr = ColPivQr::new(a.as_ref());
+        fancy_assert!(col_piv_qr.rank() == n);
+        let x_col_piv_qr = col_piv_qr.solve(b.as_ref());
+        assert_close(x_qr.as_ref(), x_col_piv_qr.as_ref());
+
+        // the residual is orthogonal to the column space of A
+        let mut residual = b.clone();
+        matmul(
+            residual.as_mut(),
+            Conj::No,
+            a.as_ref(),
+            Conj::No,
+            x_qr.as_ref(),
+            Conj::No,
+            Some(c64::one()),
+            -c64::one(),
+            Parallelism::None,
+        );
+        let mut normal = Mat::zeros(n, 2);
+        matmul(
+            normal.as_mut(),
+            Conj::No,
+            a.as_ref().transpose(),
+            Conj::Yes,
+            residual.as_ref(),
+            Conj::No,
+            None,
+            c64::one(),
+            Parallelism::None,
+        );
+        assert_close(normal.as_ref(), Mat::zeros(n, 2).as_ref());
+
+        assert_close(Qr::new(a.as_ref()).reconstruct().as_ref(), a.as_ref());
+        assert_close(col_piv_qr.reconstruct().as_ref(), a.as_ref());
+    }
+
+    #[test]
+    fn test_not_positive_definite() {
+        let a = Mat::with_dims(|i, j| if i == j { -c64::one() } else { c64::zero() }, 3, 3);
+        fancy_assert!(Llt::new(a.as_ref()).is_err());
+        fancy_assert!((Ldlt::new(a.as_ref()).unwrap().determinant() + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ldlt_singular() {
+        // the second pivot is 1 - 1 * 1 = 0
+        let a = Mat::with_dims(|_, _| c64::one(), 2, 2);
+        fancy_assert!(matches!(
+            Ldlt::new(a.as_ref()),
+            Err(FaerError::Singular { index: 1 })
+        ));
+
+        let mut a = identity(3);
+        a[(1, 1)] = c64::new(f64::NAN, 0.0);
+        fancy_assert!(matches!(
+            Ldlt::new(a.as_ref()),
+            Err(FaerError::Singular { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_parallelism_override() {
+        let a = random_mat(20, 20);
+        let lu = PartialPivLu::new_with_parallelism(a.as_ref(), Parallelism::None);
+        assert_close(lu.factors(), PartialPivLu::new(a.as_ref()).factors());
+
+        let h = random_hpd(20);
+        let ldlt = Ldlt::new_with_parallelism(h.as_ref(), Parallelism::None).unwrap();
+        assert_close(ldlt.factors(), Ldlt::new(h.as_ref()).unwrap().factors());
+    }
+}